@@ -1,5 +1,7 @@
 from keplemon._keplemon.catalogs import (  # type: ignore
     TLECatalog,
+    ObservationCatalog,
+    SiteCatalog,
 )
 
-__all__ = ["TLECatalog"]
+__all__ = ["TLECatalog", "ObservationCatalog", "SiteCatalog"]
@@ -7,6 +7,7 @@ from keplemon._keplemon.elements import (  # type: ignore
     Ephemeris,
     SphericalVector,
     TopocentricElements,
+    HorizonElements,
 )
 
 __all__ = [
@@ -18,4 +19,5 @@ __all__ = [
     "Ephemeris",
     "SphericalVector",
     "TopocentricElements",
+    "HorizonElements",
 ]
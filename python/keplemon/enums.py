@@ -5,6 +5,9 @@ from keplemon._keplemon.enums import (  # type: ignore
     KeplerianType,
     ReferenceFrame,
     CovarianceType,
+    SolverType,
+    MeasurementType,
+    EarthRotationModel,
 )
 
 __all__ = [
@@ -14,4 +17,7 @@ __all__ = [
     "KeplerianType",
     "ReferenceFrame",
     "CovarianceType",
+    "SolverType",
+    "MeasurementType",
+    "EarthRotationModel",
 ]
@@ -3,6 +3,27 @@ from keplemon._keplemon.estimation import (  # type: ignore
     ObservationResidual,
     BatchLeastSquares,
     Covariance,
+    SensorBias,
+    CorrelationCandidate,
+    CorrelationReport,
+    Tracklet,
+    TrackletBuilder,
+    ObservationSimulator,
+    ManeuverDetector,
+    TLEFitter,
 )
 
-__all__ = ["Observation", "ObservationResidual", "BatchLeastSquares", "Covariance"]
+__all__ = [
+    "Observation",
+    "ObservationResidual",
+    "BatchLeastSquares",
+    "Covariance",
+    "SensorBias",
+    "CorrelationCandidate",
+    "CorrelationReport",
+    "Tracklet",
+    "TrackletBuilder",
+    "ObservationSimulator",
+    "ManeuverDetector",
+    "TLEFitter",
+]
@@ -1,6 +1,7 @@
 from keplemon._keplemon.events import (  # type: ignore
     CloseApproach,
     CloseApproachReport,
+    ManeuverEvent,
 )
 
-__all__ = ["CloseApproach", "CloseApproachReport"]
+__all__ = ["CloseApproach", "CloseApproachReport", "ManeuverEvent"]
@@ -1,8 +1,13 @@
 from keplemon._keplemon.time import (  # type: ignore
     load_time_constants,
+    get_leap_seconds,
+    load_finals_file,
+    load_celestrak_eop_file,
     TimeSpan,
     TimeComponents,
     Epoch,
+    EpochRange,
+    IsoParseError,
 )
 import requests  # type: ignore
 from datetime import datetime
@@ -10,9 +15,14 @@ from keplemon._keplemon.enums import TimeSystem  # type: ignore
 
 __all__ = [
     "load_time_constants",
+    "get_leap_seconds",
+    "load_finals_file",
+    "load_celestrak_eop_file",
     "TimeSpan",
     "TimeComponents",
     "Epoch",
+    "EpochRange",
+    "IsoParseError",
     "request_time_constants_update",
 ]
 
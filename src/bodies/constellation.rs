@@ -2,12 +2,19 @@ use super::Satellite;
 use crate::catalogs::TLECatalog;
 use crate::configs;
 use crate::elements::{CartesianState, Ephemeris};
+use crate::estimation::{CorrelationCandidate, CorrelationReport, Observation};
 use crate::events::CloseApproachReport;
-use crate::time::{Epoch, TimeSpan};
+use crate::time::{Epoch, EpochRange, TimeSpan};
 use pyo3::prelude::*;
 use rayon::prelude::*;
 use std::collections::HashMap;
 
+// Reports span the first and last epochs searched
+fn get_report_span(epochs: &EpochRange) -> (Epoch, Epoch) {
+    let start = epochs.get_start();
+    (start, epochs.get_end().unwrap_or(start))
+}
+
 #[pyclass]
 #[derive(Default)]
 pub struct Constellation {
@@ -55,7 +62,20 @@ impl Constellation {
         end: Epoch,
         distance_threshold: f64,
     ) -> CloseApproachReport {
-        match sat.get_ephemeris(start, end, TimeSpan::from_minutes(configs::CONJUNCTION_STEP_MINUTES)) {
+        match EpochRange::new(start, end, TimeSpan::from_minutes(configs::CONJUNCTION_STEP_MINUTES)) {
+            Ok(epochs) => self.get_ca_report_vs_one_over(sat, &epochs, distance_threshold),
+            Err(_) => CloseApproachReport::new(start, end, distance_threshold),
+        }
+    }
+
+    pub fn get_ca_report_vs_one_over(
+        &self,
+        sat: &Satellite,
+        epochs: &EpochRange,
+        distance_threshold: f64,
+    ) -> CloseApproachReport {
+        let (start, end) = get_report_span(epochs);
+        match sat.get_ephemeris_over(epochs) {
             Some(ephemeris) => {
                 let close_approaches = self
                     .satellites
@@ -68,13 +88,10 @@ impl Constellation {
                         {
                             return None;
                         }
-                        match other_sat.get_ephemeris(
-                            start,
-                            end,
-                            TimeSpan::from_minutes(configs::CONJUNCTION_STEP_MINUTES),
-                        ) {
-                            Some(other_ephemeris) => ephemeris.get_close_approach(&other_ephemeris, distance_threshold),
-
+                        match other_sat.get_ephemeris_over(epochs) {
+                            Some(other_ephemeris) => {
+                                ephemeris.get_close_approach_over(&other_ephemeris, epochs, distance_threshold)
+                            }
                             None => None,
                         }
                     })
@@ -88,13 +105,19 @@ impl Constellation {
     }
 
     pub fn get_ca_report_vs_many(&self, start: Epoch, end: Epoch, distance_threshold: f64) -> CloseApproachReport {
+        match EpochRange::new(start, end, TimeSpan::from_minutes(configs::CONJUNCTION_STEP_MINUTES)) {
+            Ok(epochs) => self.get_ca_report_vs_many_over(&epochs, distance_threshold),
+            Err(_) => CloseApproachReport::new(start, end, distance_threshold),
+        }
+    }
+
+    pub fn get_ca_report_vs_many_over(&self, epochs: &EpochRange, distance_threshold: f64) -> CloseApproachReport {
+        let (start, end) = get_report_span(epochs);
         let mut report = CloseApproachReport::new(start, end, distance_threshold);
         let ephem_list: Vec<Ephemeris> = self
             .satellites
             .par_iter()
-            .filter_map(|(_, sat)| {
-                sat.get_ephemeris(start, end, TimeSpan::from_minutes(configs::CONJUNCTION_STEP_MINUTES))
-            })
+            .filter_map(|(_, sat)| sat.get_ephemeris_over(epochs))
             .collect();
         let num = ephem_list.len();
         let close_approaches = (0..num)
@@ -114,7 +137,7 @@ impl Constellation {
                         {
                             return None;
                         }
-                        pri_ephem.get_close_approach(sec_ephem, distance_threshold)
+                        pri_ephem.get_close_approach_over(sec_ephem, epochs, distance_threshold)
                     })
                     .collect::<Vec<_>>()
             })
@@ -128,16 +151,64 @@ impl Constellation {
         start_epoch: Epoch,
         end_epoch: Epoch,
         step_size: TimeSpan,
-    ) -> HashMap<i32, Option<Ephemeris>> {
+    ) -> PyResult<HashMap<i32, Option<Ephemeris>>> {
         self.satellites
             .par_iter()
             .map(|(satellite_id, sat)| {
-                let ephemeris = sat.get_ephemeris(start_epoch, end_epoch, step_size);
-                (*satellite_id, ephemeris)
+                let ephemeris = sat.get_ephemeris(start_epoch, end_epoch, step_size)?;
+                Ok((*satellite_id, ephemeris))
             })
             .collect()
     }
 
+    pub fn get_ephemeris_over(&self, epochs: &EpochRange) -> HashMap<i32, Option<Ephemeris>> {
+        self.satellites
+            .par_iter()
+            .map(|(satellite_id, sat)| (*satellite_id, sat.get_ephemeris_over(epochs)))
+            .collect()
+    }
+
+    #[pyo3(signature = (obs, significance = None))]
+    pub fn get_correlation_report(
+        &self,
+        obs: Vec<Observation>,
+        significance: Option<f64>,
+    ) -> PyResult<CorrelationReport> {
+        let significance = significance.unwrap_or(configs::DEFAULT_CORRELATION_SIGNIFICANCE);
+        let mut measurements = Vec::with_capacity(obs.len());
+        for ob in obs.iter() {
+            let (m_vec, w_vec) = ob
+                .get_measurement_and_weight_vector()
+                .map_err(pyo3::exceptions::PyValueError::new_err)?;
+            measurements.push((m_vec, w_vec));
+        }
+        let degrees_of_freedom = measurements.iter().map(|(m_vec, _)| m_vec.len()).sum();
+
+        // Satellites that cannot be propagated to every observation are not candidates
+        let candidates = self
+            .satellites
+            .par_iter()
+            .filter_map(|(satellite_id, sat)| {
+                let mut chi_square = 0.0;
+                for (ob, (m_vec, w_vec)) in obs.iter().zip(measurements.iter()) {
+                    let predicted = ob.get_predicted_vector(sat).ok()?;
+                    let residuals = ob.get_measurement_differences(m_vec, &predicted);
+                    chi_square += residuals.iter().zip(w_vec.iter()).map(|(r, w)| r * r * w).sum::<f64>();
+                }
+                Some(CorrelationCandidate::new(
+                    *satellite_id,
+                    chi_square,
+                    degrees_of_freedom,
+                    significance,
+                ))
+            })
+            .collect();
+
+        let mut report = CorrelationReport::new(significance);
+        report.set_candidates(candidates);
+        Ok(report)
+    }
+
     fn __getitem__(&self, satellite_id: i32) -> PyResult<Satellite> {
         match self.get(satellite_id) {
             Some(sat) => Ok(sat),
@@ -1,11 +1,13 @@
 use crate::configs::CONJUNCTION_STEP_MINUTES;
-use crate::elements::{CartesianState, Ephemeris, KeplerianState, TLE};
+use crate::elements::{
+    CartesianState, CartesianVector, Ephemeris, EquinoctialElements, ForcePerturbations, KeplerianState, TLE,
+};
 use crate::enums::{Classification, KeplerianType};
 use crate::estimation::Observation;
 use crate::events::CloseApproach;
 use crate::propagation::{ForceProperties, InertialPropagator};
-use crate::time::{Epoch, TimeSpan};
-use nalgebra::{DMatrix, DVector};
+use crate::time::{Epoch, EpochRange, TimeSpan};
+use nalgebra::{DMatrix, DVector, Vector3};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
@@ -20,9 +22,34 @@ pub struct Satellite {
 }
 
 impl Satellite {
-    pub fn get_jacobian(&self, ob: &Observation, use_drag: bool, use_srp: bool) -> Result<DMatrix<f64>, String> {
+    pub fn get_force_perturbations(&self, use_drag: bool, use_srp: bool) -> Result<ForcePerturbations, String> {
         match self.inertial_propagator {
-            Some(ref propagator) => propagator.get_jacobian(ob, use_drag, use_srp),
+            Some(ref propagator) => propagator.get_force_perturbations(use_drag, use_srp),
+            None => Err("Inertial propagator is not set".to_string()),
+        }
+    }
+
+    pub fn get_jacobian(&self, ob: &Observation, perturbations: &ForcePerturbations) -> Result<DMatrix<f64>, String> {
+        match self.inertial_propagator {
+            Some(ref propagator) => {
+                let (state, measurement_partials) = ob.get_state_partials(self)?;
+                let state_partials = propagator.get_state_partials(&state, perturbations)?;
+                Ok(measurement_partials * state_partials)
+            }
+            None => Err("Inertial propagator is not set".to_string()),
+        }
+    }
+
+    pub fn get_stm(&self, epoch: Epoch, use_drag: bool, use_srp: bool) -> Result<DMatrix<f64>, String> {
+        match self.inertial_propagator {
+            Some(ref propagator) => propagator.get_stm(epoch, use_drag, use_srp),
+            None => Err("Inertial propagator is not set".to_string()),
+        }
+    }
+
+    pub fn get_equinoctial_elements_at_epoch(&self, epoch: Epoch) -> Result<EquinoctialElements, String> {
+        match self.inertial_propagator {
+            Some(ref propagator) => propagator.get_equinoctial_elements_at_epoch(epoch),
             None => Err("Inertial propagator is not set".to_string()),
         }
     }
@@ -40,6 +67,44 @@ impl Satellite {
         Ok(new_satellite)
     }
 
+    // Partials of the equinoctial elements with respect to an impulsive RIC velocity change at an epoch
+    pub fn get_impulse_partials(&self, epoch: Epoch) -> Result<DMatrix<f64>, String> {
+        let state = match self.get_state_at_epoch(epoch) {
+            Some(state) => state,
+            None => {
+                return Err(format!(
+                    "Error propagating satellite {} to {}",
+                    self.satellite_id,
+                    epoch.to_iso()
+                ))
+            }
+        };
+        let element_partials = match EquinoctialElements::from(&state).get_cartesian_partials().try_inverse() {
+            Some(element_partials) => element_partials,
+            None => return Err("Cartesian partials of the equinoctial elements are singular".to_string()),
+        };
+        let position = Vector3::new(state.position[0], state.position[1], state.position[2]);
+        let velocity = Vector3::new(state.velocity[0], state.velocity[1], state.velocity[2]);
+        let radial = position.normalize();
+        let cross_track = position.cross(&velocity).normalize();
+        let in_track = cross_track.cross(&radial);
+        let ric_to_teme = DMatrix::from_fn(3, 3, |i, j| [radial, in_track, cross_track][j][i]);
+        Ok(element_partials.columns(3, 3) * ric_to_teme)
+    }
+
+    pub fn new_with_impulse(&self, epoch: Epoch, delta_v: CartesianVector) -> Result<Self, String> {
+        let delta_x = self.get_impulse_partials(epoch)? * DVector::from_vec(vec![delta_v[0], delta_v[1], delta_v[2]]);
+        let elements = self.get_equinoctial_elements_at_epoch(epoch)?;
+        if (elements[0] + delta_x[0]).hypot(elements[1] + delta_x[1]) >= 1.0 || elements[5] + delta_x[5] <= 0.0 {
+            return Err(format!(
+                "Impulse at {} produces an invalid orbit for satellite {}",
+                epoch.to_iso(),
+                self.satellite_id
+            ));
+        }
+        self.clone_at_epoch(epoch)?.new_with_delta_x(&delta_x, false, false)
+    }
+
     pub fn get_prior_node(&self, epoch: Epoch) -> Result<Epoch, String> {
         match self.inertial_propagator {
             Some(ref propagator) => propagator.get_prior_node(epoch),
@@ -180,26 +245,22 @@ impl Satellite {
         self.force_properties
     }
 
-    pub fn get_ephemeris(&self, start_epoch: Epoch, end_epoch: Epoch, step: TimeSpan) -> Option<Ephemeris> {
-        match self.get_state_at_epoch(start_epoch) {
-            Some(state) => {
-                let ephemeris = Ephemeris::new(self.satellite_id, state);
-                let mut next_epoch: Epoch = start_epoch + step;
-                while next_epoch <= end_epoch {
-                    match self.get_state_at_epoch(next_epoch) {
-                        Some(state) => {
-                            ephemeris.add_state(state);
-                            next_epoch += step;
-                        }
-                        None => {
-                            return None;
-                        }
-                    }
-                }
-                Some(ephemeris)
-            }
-            None => None,
+    pub fn get_ephemeris(&self, start_epoch: Epoch, end_epoch: Epoch, step: TimeSpan) -> PyResult<Option<Ephemeris>> {
+        // An end before the start still yields the state at the start
+        let epochs = match end_epoch < start_epoch {
+            true => EpochRange::from_step_and_count(start_epoch, step, 1).map_err(PyValueError::new_err)?,
+            false => EpochRange::new(start_epoch, end_epoch, step)?,
+        };
+        Ok(self.get_ephemeris_over(&epochs))
+    }
+
+    pub fn get_ephemeris_over(&self, epochs: &EpochRange) -> Option<Ephemeris> {
+        let mut epochs = epochs.iter();
+        let ephemeris = Ephemeris::new(self.satellite_id, self.get_state_at_epoch(epochs.next()?)?);
+        for epoch in epochs {
+            ephemeris.add_state(self.get_state_at_epoch(epoch)?);
         }
+        Some(ephemeris)
     }
 
     #[getter]
@@ -213,6 +274,16 @@ impl Satellite {
         start_epoch: Epoch,
         end_epoch: Epoch,
         distance_threshold: f64,
+    ) -> Option<CloseApproach> {
+        let epochs = EpochRange::new(start_epoch, end_epoch, TimeSpan::from_minutes(CONJUNCTION_STEP_MINUTES)).ok()?;
+        self.get_close_approach_over(other, &epochs, distance_threshold)
+    }
+
+    pub fn get_close_approach_over(
+        &self,
+        other: &Satellite,
+        epochs: &EpochRange,
+        distance_threshold: f64,
     ) -> Option<CloseApproach> {
         if (self.keplerian_state.is_none() || other.keplerian_state.is_none())
             || self.get_apoapsis()? < other.get_periapsis()? - distance_threshold
@@ -223,14 +294,8 @@ impl Satellite {
             return None;
         }
 
-        match self.get_ephemeris(start_epoch, end_epoch, TimeSpan::from_minutes(CONJUNCTION_STEP_MINUTES)) {
-            Some(ephemeris) => {
-                match other.get_ephemeris(start_epoch, end_epoch, TimeSpan::from_minutes(CONJUNCTION_STEP_MINUTES)) {
-                    Some(other_ephemeris) => ephemeris.get_close_approach(&other_ephemeris, distance_threshold),
-                    None => None,
-                }
-            }
-            None => None,
-        }
+        let ephemeris = self.get_ephemeris_over(epochs)?;
+        let other_ephemeris = other.get_ephemeris_over(epochs)?;
+        ephemeris.get_close_approach_over(&other_ephemeris, epochs, distance_threshold)
     }
 }
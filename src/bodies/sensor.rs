@@ -1,3 +1,4 @@
+use crate::enums::MeasurementType;
 use pyo3::prelude::*;
 
 #[pyclass]
@@ -9,6 +10,15 @@ pub struct Sensor {
     range_noise: Option<f64>,
     range_rate_noise: Option<f64>,
     angular_rate_noise: Option<f64>,
+    horizon_angles: bool,
+    right_ascension_bias: f64,
+    declination_bias: f64,
+    range_bias: f64,
+    range_rate_bias: f64,
+    azimuth_bias: f64,
+    elevation_bias: f64,
+    min_elevation: Option<f64>,
+    max_range: Option<f64>,
 }
 
 #[pymethods]
@@ -22,6 +32,15 @@ impl Sensor {
             range_noise: None,
             range_rate_noise: None,
             angular_rate_noise: None,
+            horizon_angles: false,
+            right_ascension_bias: 0.0,
+            declination_bias: 0.0,
+            range_bias: 0.0,
+            range_rate_bias: 0.0,
+            azimuth_bias: 0.0,
+            elevation_bias: 0.0,
+            min_elevation: None,
+            max_range: None,
         }
     }
 
@@ -30,6 +49,11 @@ impl Sensor {
         self.sensor_id
     }
 
+    #[setter]
+    pub fn set_sensor_id(&mut self, sensor_id: i32) {
+        self.sensor_id = Some(sensor_id);
+    }
+
     #[getter]
     pub fn get_name(&self) -> String {
         self.name.clone()
@@ -69,4 +93,141 @@ impl Sensor {
     pub fn set_angular_rate_noise(&mut self, angular_rate_noise: f64) {
         self.angular_rate_noise = Some(angular_rate_noise);
     }
+
+    #[getter]
+    pub fn get_horizon_angles(&self) -> bool {
+        self.horizon_angles
+    }
+
+    #[setter]
+    pub fn set_horizon_angles(&mut self, horizon_angles: bool) {
+        self.horizon_angles = horizon_angles;
+    }
+
+    #[getter]
+    pub fn get_right_ascension_bias(&self) -> f64 {
+        self.right_ascension_bias
+    }
+
+    #[setter]
+    pub fn set_right_ascension_bias(&mut self, right_ascension_bias: f64) {
+        self.right_ascension_bias = right_ascension_bias;
+    }
+
+    #[getter]
+    pub fn get_declination_bias(&self) -> f64 {
+        self.declination_bias
+    }
+
+    #[setter]
+    pub fn set_declination_bias(&mut self, declination_bias: f64) {
+        self.declination_bias = declination_bias;
+    }
+
+    #[getter]
+    pub fn get_range_bias(&self) -> f64 {
+        self.range_bias
+    }
+
+    #[setter]
+    pub fn set_range_bias(&mut self, range_bias: f64) {
+        self.range_bias = range_bias;
+    }
+
+    #[getter]
+    pub fn get_range_rate_bias(&self) -> f64 {
+        self.range_rate_bias
+    }
+
+    #[setter]
+    pub fn set_range_rate_bias(&mut self, range_rate_bias: f64) {
+        self.range_rate_bias = range_rate_bias;
+    }
+
+    #[getter]
+    pub fn get_azimuth_bias(&self) -> f64 {
+        self.azimuth_bias
+    }
+
+    #[setter]
+    pub fn set_azimuth_bias(&mut self, azimuth_bias: f64) {
+        self.azimuth_bias = azimuth_bias;
+    }
+
+    #[getter]
+    pub fn get_elevation_bias(&self) -> f64 {
+        self.elevation_bias
+    }
+
+    #[setter]
+    pub fn set_elevation_bias(&mut self, elevation_bias: f64) {
+        self.elevation_bias = elevation_bias;
+    }
+
+    #[getter]
+    pub fn get_min_elevation(&self) -> Option<f64> {
+        self.min_elevation
+    }
+
+    #[setter]
+    pub fn set_min_elevation(&mut self, min_elevation: Option<f64>) {
+        self.min_elevation = min_elevation;
+    }
+
+    #[getter]
+    pub fn get_max_range(&self) -> Option<f64> {
+        self.max_range
+    }
+
+    #[setter]
+    pub fn set_max_range(&mut self, max_range: Option<f64>) {
+        self.max_range = max_range;
+    }
+}
+
+impl Sensor {
+    pub fn get_noise(&self, measurement_type: MeasurementType) -> Option<f64> {
+        match measurement_type {
+            MeasurementType::RightAscension
+            | MeasurementType::Declination
+            | MeasurementType::Azimuth
+            | MeasurementType::Elevation => Some(self.angular_noise),
+            MeasurementType::Range => self.range_noise,
+            MeasurementType::RangeRate => self.range_rate_noise,
+            MeasurementType::RightAscensionRate
+            | MeasurementType::DeclinationRate
+            | MeasurementType::AzimuthRate
+            | MeasurementType::ElevationRate => self.angular_rate_noise,
+        }
+    }
+
+    pub fn get_bias(&self, measurement_type: MeasurementType) -> f64 {
+        match measurement_type {
+            MeasurementType::RightAscension => self.right_ascension_bias,
+            MeasurementType::Declination => self.declination_bias,
+            MeasurementType::Range => self.range_bias,
+            MeasurementType::RangeRate => self.range_rate_bias,
+            MeasurementType::Azimuth => self.azimuth_bias,
+            MeasurementType::Elevation => self.elevation_bias,
+            _ => 0.0,
+        }
+    }
+
+    pub fn set_bias(&mut self, measurement_type: MeasurementType, bias: f64) -> Result<(), String> {
+        match measurement_type {
+            MeasurementType::RightAscension => self.right_ascension_bias = bias,
+            MeasurementType::Declination => self.declination_bias = bias,
+            MeasurementType::Range => self.range_bias = bias,
+            MeasurementType::RangeRate => self.range_rate_bias = bias,
+            MeasurementType::Azimuth => self.azimuth_bias = bias,
+            MeasurementType::Elevation => self.elevation_bias = bias,
+            _ => {
+                return Err(format!(
+                    "Biases are not supported for {:?} measurements",
+                    measurement_type
+                ))
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,11 +1,20 @@
+mod b3;
+mod crd;
+mod observation_catalog;
+mod site_catalog;
+mod tdm;
 mod tle_catalog;
+pub use observation_catalog::ObservationCatalog;
 use pyo3::prelude::*;
 use pyo3::py_run;
+pub use site_catalog::SiteCatalog;
 pub use tle_catalog::TLECatalog;
 
 pub fn register_catalogs(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let catalogs = PyModule::new(parent_module.py(), "catalogs")?;
     catalogs.add_class::<TLECatalog>()?;
+    catalogs.add_class::<ObservationCatalog>()?;
+    catalogs.add_class::<SiteCatalog>()?;
     py_run!(
         parent_module.py(),
         catalogs,
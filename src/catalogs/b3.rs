@@ -0,0 +1,327 @@
+// Fixed-column B3 observation records
+//
+//  Columns  Field
+//  1        Security classification
+//  2-6      Satellite number
+//  7-9      Sensor number
+//  10-23    Epoch (YYDDDHHMMSSsss, UTC)
+//  24-29    Elevation (DDdddd) or declination (sDDddd) in degrees
+//  31-37    Azimuth (DDDdddd) in degrees or right ascension (HHMMSSs)
+//  39-45    Range mantissa (XXxxxxx) in kilometers
+//  46       Range exponent
+//  48-54    Range rate (sXxxxxx) in kilometers per second
+//  47-73    Space-based sensor TEME position (3 x sXXXXXxxx) in kilometers for type 9
+//  75       Observation type
+//  76       Equinox of right ascension and declination for types 5 and 9
+
+use super::observation_catalog::SiteLookup;
+use crate::elements::{CartesianVector, HorizonElements, TopocentricElements};
+use crate::enums::TimeSystem;
+use crate::estimation::Observation;
+use crate::saal::{astro_func_interface, time_func_interface};
+use crate::time::Epoch;
+
+const RECORD_LENGTH: usize = 80;
+const MILLISECONDS_PER_DAY: f64 = 86_400_000.0;
+
+fn get_field(record: &str, start: usize, end: usize) -> &str {
+    record.get(start - 1..end.min(record.len())).unwrap_or("")
+}
+
+// Reads a signed number with an implied decimal point after the leading integer digits
+fn parse_implied_decimal(field: &str, integer_digits: usize) -> Result<f64, String> {
+    let field = field.trim_end();
+    let (sign, digits) = match field.chars().next() {
+        Some('-') => (-1.0, &field[1..]),
+        Some('+') | Some(' ') => (1.0, &field[1..]),
+        _ => (1.0, field),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid numeric field '{}'", field));
+    }
+    let value: f64 = digits
+        .parse()
+        .map_err(|_| format!("Invalid numeric field '{}'", field))?;
+    let decimals = digits.len().saturating_sub(integer_digits) as i32;
+    Ok(sign * value / 10f64.powi(decimals))
+}
+
+fn format_implied_decimal(value: f64, width: usize, decimals: i32, signed: bool) -> Result<String, String> {
+    let digits = match signed {
+        true => width - 1,
+        false => width,
+    };
+    let scaled = (value.abs() * 10f64.powi(decimals)).round() as u64;
+    let formatted = format!("{:0digits$}", scaled, digits = digits);
+    if formatted.len() > digits || (!signed && value < 0.0) {
+        return Err(format!("Value {} does not fit in a B3 field", value));
+    }
+    match signed {
+        true => Ok(format!("{}{}", if value < 0.0 { '-' } else { '+' }, formatted)),
+        false => Ok(formatted),
+    }
+}
+
+fn parse_epoch(record: &str) -> Result<Epoch, String> {
+    let field = get_field(record, 10, 23);
+    if field.len() != 14 || !field.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid epoch '{}'", field));
+    }
+    let two_digit_year: i32 = field[0..2].parse().unwrap();
+    let year = match two_digit_year < 57 {
+        true => 2000 + two_digit_year,
+        false => 1900 + two_digit_year,
+    };
+    let day_of_year: f64 = field[2..5].parse().unwrap();
+    let hour: f64 = field[5..7].parse().unwrap();
+    let minute: f64 = field[7..9].parse().unwrap();
+    let milliseconds: f64 = field[9..14].parse().unwrap();
+    let seconds_of_day = 3600.0 * hour + 60.0 * minute + milliseconds / 1000.0;
+    let days_since_1950 = time_func_interface::year_doy_to_ds50(year, day_of_year) + seconds_of_day / 86400.0;
+    Ok(Epoch::from_days_since_1950(days_since_1950, TimeSystem::UTC))
+}
+
+fn format_epoch(epoch: Epoch) -> String {
+    // Round to the record precision before splitting so milliseconds never carry into the next day
+    let days_since_1950 = (epoch.days_since_1950 * MILLISECONDS_PER_DAY).round() / MILLISECONDS_PER_DAY;
+    let (year, day_of_year) = time_func_interface::ds50_to_year_doy(days_since_1950 + 0.1 / MILLISECONDS_PER_DAY);
+    let day = day_of_year.floor();
+    let milliseconds = (((day_of_year - day) * MILLISECONDS_PER_DAY) as u64).min(86_399_999);
+    format!(
+        "{:02}{:03}{:02}{:02}{:05}",
+        year % 100,
+        day as u32,
+        milliseconds / 3_600_000,
+        milliseconds / 60_000 % 60,
+        milliseconds % 60_000
+    )
+}
+
+fn parse_right_ascension(field: &str) -> Result<f64, String> {
+    if field.len() != 7 || !field.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid right ascension '{}'", field));
+    }
+    let hours: f64 = field[0..2].parse().unwrap();
+    let minutes: f64 = field[2..4].parse().unwrap();
+    let seconds: f64 = field[4..7].parse::<f64>().unwrap() / 10.0;
+    Ok(15.0 * (hours + minutes / 60.0 + seconds / 3600.0))
+}
+
+fn format_right_ascension(right_ascension: f64) -> String {
+    let tenths = ((right_ascension.rem_euclid(360.0) / 15.0 * 36000.0).round() as u64) % 864_000;
+    format!("{:02}{:02}{:03}", tenths / 36000, tenths / 600 % 60, tenths % 600)
+}
+
+fn parse_range(record: &str) -> Result<Option<f64>, String> {
+    let mantissa = get_field(record, 39, 45);
+    if mantissa.trim().is_empty() {
+        return Ok(None);
+    }
+    let exponent = get_field(record, 46, 46).trim();
+    let exponent: i32 = match exponent.is_empty() {
+        true => 0,
+        false => exponent
+            .parse()
+            .map_err(|_| format!("Invalid range exponent '{}'", exponent))?,
+    };
+    Ok(Some(parse_implied_decimal(mantissa, 2)? * 10f64.powi(exponent)))
+}
+
+fn format_range(range: f64) -> Result<String, String> {
+    let mut exponent = (range.log10().floor() as i32 - 1).max(0);
+    let mut mantissa = (range / 10f64.powi(exponent) * 1e5).round();
+    if mantissa >= 1e7 {
+        exponent += 1;
+        mantissa = (range / 10f64.powi(exponent) * 1e5).round();
+    }
+    if exponent > 9 || !range.is_finite() || range < 0.0 {
+        return Err(format!("Range {} does not fit in a B3 field", range));
+    }
+    Ok(format!("{:07}{}", mantissa as u64, exponent))
+}
+
+fn parse_optional(record: &str, start: usize, end: usize, integer_digits: usize) -> Result<Option<f64>, String> {
+    let field = get_field(record, start, end);
+    match field.trim().is_empty() {
+        true => Ok(None),
+        false => Ok(Some(parse_implied_decimal(field, integer_digits)?)),
+    }
+}
+
+fn parse_record(record: &str, sites: &SiteLookup) -> Result<Option<Observation>, String> {
+    let satellite_id: i32 = get_field(record, 2, 6)
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid satellite number '{}'", get_field(record, 2, 6)))?;
+    let sensor_number: i32 = get_field(record, 7, 9)
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid sensor number '{}'", get_field(record, 7, 9)))?;
+    let epoch = parse_epoch(record)?;
+    let observation_type = get_field(record, 75, 75);
+    let (sensor, observatory) = match sites.find_by_number(sensor_number) {
+        Some(site) => site,
+        None => return Err(format!("Sensor {} is not defined by any observatory", sensor_number)),
+    };
+    let site_position = observatory.get_state_at_epoch(epoch).position;
+
+    let mut ob = match observation_type {
+        "0" => {
+            let range_rate = parse_optional(record, 48, 54, 1)?.ok_or("Missing range rate")?;
+            Observation::from_range_rate(sensor, epoch, range_rate, site_position)
+        }
+        "1" | "2" | "3" | "4" => {
+            let elevation = parse_implied_decimal(get_field(record, 24, 29), 2)?;
+            let azimuth = parse_implied_decimal(get_field(record, 31, 37), 3)?;
+            let mut horizon = HorizonElements::new(azimuth, elevation);
+            if observation_type != "1" {
+                horizon.set_range(parse_range(record)?);
+            }
+            if observation_type == "3" || observation_type == "4" {
+                horizon.set_range_rate(parse_optional(record, 48, 54, 1)?);
+            }
+            Observation::from_horizon(sensor, epoch, horizon, site_position)
+        }
+        "5" | "9" => {
+            let declination = parse_implied_decimal(get_field(record, 24, 29), 2)?;
+            let right_ascension = parse_right_ascension(get_field(record, 31, 37))?;
+            let topocentric = match get_field(record, 76, 76).trim() {
+                "" | "0" => TopocentricElements::new(right_ascension, declination),
+                equinox @ ("1" | "2" | "3") => {
+                    let (ra, dec) = astro_func_interface::topo_equinox_to_date(
+                        equinox.parse().unwrap(),
+                        epoch.days_since_1950,
+                        right_ascension,
+                        declination,
+                    );
+                    TopocentricElements::new(ra, dec)
+                }
+                equinox => return Err(format!("Invalid equinox indicator '{}'", equinox)),
+            };
+            match observation_type {
+                "9" => {
+                    let observer_position = CartesianVector::new(
+                        parse_implied_decimal(get_field(record, 47, 55), 5)?,
+                        parse_implied_decimal(get_field(record, 56, 64), 5)?,
+                        parse_implied_decimal(get_field(record, 65, 73), 5)?,
+                    );
+                    Observation::from_space_position(sensor, epoch, topocentric, observer_position)
+                }
+                _ => Observation::new(sensor, epoch, topocentric, site_position),
+            }
+        }
+        "6" => {
+            let range = parse_range(record)?.ok_or("Missing range")?;
+            Observation::from_range(sensor, epoch, range, site_position)
+        }
+        _ => return Ok(None),
+    };
+    ob.set_observed_satellite_id(satellite_id);
+    Ok(Some(ob))
+}
+
+pub fn read(contents: &str, sites: &SiteLookup) -> Result<Vec<Observation>, String> {
+    let mut observations = Vec::new();
+    for (i, record) in contents.lines().enumerate() {
+        if record.trim().is_empty() {
+            continue;
+        }
+        if !record.is_ascii() {
+            return Err(format!("Line {}: B3 records must be ASCII", i + 1));
+        }
+        match parse_record(record, sites) {
+            Ok(Some(ob)) => observations.push(ob),
+            Ok(None) => {}
+            Err(e) => return Err(format!("Line {}: {}", i + 1, e)),
+        }
+    }
+    Ok(observations)
+}
+
+pub fn format_record(ob: &Observation) -> Result<String, String> {
+    let satellite_id = ob
+        .get_observed_satellite_id()
+        .ok_or("Observed satellite ID is not set")?;
+    let sensor_number = ob.get_sensor().get_sensor_id().ok_or("Sensor ID is not set")?;
+    if !(0..=99999).contains(&satellite_id) || !(0..=999).contains(&sensor_number) {
+        return Err("Satellite or sensor number does not fit in a B3 record".to_string());
+    }
+    let mut record = vec![b' '; RECORD_LENGTH];
+    let mut set = |start: usize, text: &str| {
+        record[start - 1..start - 1 + text.len()].copy_from_slice(text.as_bytes());
+    };
+    set(1, "U");
+    set(2, &format!("{:05}", satellite_id));
+    set(7, &format!("{:03}", sensor_number));
+    set(
+        10,
+        &format_epoch(ob.get_epoch().to_system(TimeSystem::UTC).map_err(|e| e.to_string())?),
+    );
+
+    let range = ob.get_range();
+    let range_rate = ob.get_range_rate();
+    let observation_type = match (ob.get_azimuth(), ob.get_elevation()) {
+        (Some(azimuth), Some(elevation)) => {
+            set(
+                24,
+                &format_implied_decimal(elevation, 6, 4 - (elevation < 0.0) as i32, elevation < 0.0)?,
+            );
+            set(31, &format_implied_decimal(azimuth.rem_euclid(360.0), 7, 4, false)?);
+            if let Some(range) = range {
+                set(39, &format_range(range)?);
+            }
+            if let Some(range_rate) = range_rate {
+                set(48, &format_implied_decimal(range_rate, 7, 5, true)?);
+            }
+            match (range, range_rate) {
+                (None, None) => "1",
+                (Some(_), None) => "2",
+                (Some(_), Some(_)) => "3",
+                (None, Some(_)) => return Err("B3 records cannot carry range rate without range".to_string()),
+            }
+        }
+        _ => match (ob.get_right_ascension(), ob.get_declination()) {
+            (Some(right_ascension), Some(declination)) => {
+                if range.is_some() || range_rate.is_some() {
+                    return Err("B3 records cannot carry range with right ascension and declination".to_string());
+                }
+                set(24, &format_implied_decimal(declination, 6, 3, true)?);
+                set(31, &format_right_ascension(right_ascension));
+                set(76, "0");
+                match ob.get_space_based() {
+                    true => {
+                        let position = ob.get_observer_teme_position();
+                        for i in 0..3 {
+                            set(47 + 9 * i, &format_implied_decimal(position[i], 9, 3, true)?);
+                        }
+                        "9"
+                    }
+                    false => "5",
+                }
+            }
+            _ => match (range, range_rate) {
+                (Some(range), None) => {
+                    set(39, &format_range(range)?);
+                    "6"
+                }
+                (None, Some(range_rate)) => {
+                    set(48, &format_implied_decimal(range_rate, 7, 5, true)?);
+                    "0"
+                }
+                _ => return Err("Observation has no measurements that B3 records can carry".to_string()),
+            },
+        },
+    };
+    set(75, observation_type);
+    Ok(String::from_utf8(record).unwrap().trim_end().to_string())
+}
+
+pub fn write(observations: &[Observation]) -> Result<String, String> {
+    let mut contents = String::new();
+    for ob in observations {
+        let record = format_record(ob).map_err(|e| format!("Observation at {}: {}", ob.get_epoch().to_iso(), e))?;
+        contents.push_str(&record);
+        contents.push('\n');
+    }
+    Ok(contents)
+}
@@ -0,0 +1,124 @@
+// ILRS Consolidated Laser Ranging Data (CRD) records
+use super::observation_catalog::SiteLookup;
+use crate::bodies::{Observatory, Sensor};
+use crate::enums::TimeSystem;
+use crate::estimation::Observation;
+use crate::saal::time_func_interface;
+use crate::time::Epoch;
+
+const SPEED_OF_LIGHT: f64 = 299_792.458;
+const TWO_WAY_RANGE_TYPE: &str = "2";
+
+struct Session<'a> {
+    sensor: Sensor,
+    observatory: &'a Observatory,
+    satellite_id: Option<i32>,
+    start_day: f64,
+    start_seconds: f64,
+    two_way: bool,
+}
+
+fn parse_field<T: std::str::FromStr>(fields: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let field = fields.get(index).ok_or_else(|| format!("Missing {}", name))?;
+    field.parse().map_err(|_| format!("Invalid {} '{}'", name, field))
+}
+
+// Ranges are time of flight halved, tagged at the spacecraft bounce time
+fn parse_range_record(fields: &[&str], session: &Session) -> Result<Observation, String> {
+    let seconds_of_day: f64 = parse_field(fields, 1, "seconds of day")?;
+    let time_of_flight: f64 = parse_field(fields, 2, "time of flight")?;
+    let epoch_event: u8 = parse_field(fields, 4, "epoch event")?;
+    let bounce_offset = match epoch_event {
+        0 => -0.5 * time_of_flight,
+        1 => 0.0,
+        2 => 0.5 * time_of_flight,
+        _ => return Err(format!("Epoch event {} is not a two-way event", epoch_event)),
+    };
+
+    // Sessions may cross midnight while seconds of day restart from zero
+    let day = match seconds_of_day < session.start_seconds {
+        true => session.start_day + 1.0,
+        false => session.start_day,
+    };
+    let epoch = Epoch::from_days_since_1950(day + (seconds_of_day + bounce_offset) / 86400.0, TimeSystem::UTC);
+    let site_position = session.observatory.get_state_at_epoch(epoch).position;
+    let range = 0.5 * SPEED_OF_LIGHT * time_of_flight;
+    let mut ob = Observation::from_range(session.sensor.clone(), epoch, range, site_position);
+    if let Some(satellite_id) = session.satellite_id {
+        ob.set_observed_satellite_id(satellite_id);
+    }
+    Ok(ob)
+}
+
+fn parse_session_header<'a>(
+    fields: &[&str],
+    sensor: Sensor,
+    observatory: &'a Observatory,
+    satellite_id: Option<i32>,
+) -> Result<Session<'a>, String> {
+    let year: i32 = parse_field(fields, 2, "start year")?;
+    let month: i32 = parse_field(fields, 3, "start month")?;
+    let day: i32 = parse_field(fields, 4, "start day")?;
+    let hour: f64 = parse_field(fields, 5, "start hour")?;
+    let minute: f64 = parse_field(fields, 6, "start minute")?;
+    let second: f64 = parse_field(fields, 7, "start second")?;
+    Ok(Session {
+        sensor,
+        observatory,
+        satellite_id,
+        start_day: time_func_interface::ymd_components_to_ds50(year, month, day, 0, 0, 0.0),
+        start_seconds: 3600.0 * hour + 60.0 * minute + second,
+        two_way: fields.get(20) == Some(&TWO_WAY_RANGE_TYPE),
+    })
+}
+
+pub fn read(contents: &str, sites: &SiteLookup) -> Result<Vec<Observation>, String> {
+    let mut observations = Vec::new();
+    let mut station: Option<(Sensor, &Observatory)> = None;
+    let mut satellite_id = None;
+    let mut session: Option<Session> = None;
+    for (i, line) in contents.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let record_type = match fields.first() {
+            Some(record_type) => record_type.to_ascii_uppercase(),
+            None => continue,
+        };
+        let result: Result<(), String> = match record_type.as_str() {
+            "H2" => {
+                let name = fields.get(1).copied().unwrap_or("");
+                let pad_id: Option<i32> = fields.get(2).and_then(|field| field.parse().ok());
+                station = pad_id
+                    .and_then(|pad_id| sites.find_by_number(pad_id))
+                    .or_else(|| sites.find_by_name(name));
+                match station {
+                    Some(_) => Ok(()),
+                    None => Err(format!("Station {} is not defined by any observatory", name)),
+                }
+            }
+            "H3" => {
+                satellite_id = fields.get(4).and_then(|field| field.parse().ok());
+                Ok(())
+            }
+            "H4" => match station.clone() {
+                Some((sensor, observatory)) => parse_session_header(&fields, sensor, observatory, satellite_id)
+                    .map(|header| session = Some(header)),
+                None => Err("Session header precedes station header".to_string()),
+            },
+            "10" | "11" => match &session {
+                Some(session) if session.two_way => {
+                    parse_range_record(&fields, session).map(|ob| observations.push(ob))
+                }
+                Some(_) => Ok(()),
+                None => Err("Range record is outside of a session".to_string()),
+            },
+            "H8" => {
+                session = None;
+                Ok(())
+            }
+            "H9" => break,
+            _ => Ok(()),
+        };
+        result.map_err(|e| format!("Line {}: {}", i + 1, e))?;
+    }
+    Ok(observations)
+}
@@ -0,0 +1,154 @@
+use super::{b3, crd, tdm};
+use crate::bodies::{Observatory, Sensor};
+use crate::estimation::Observation;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use std::fs;
+
+const TDM_ORIGINATOR: &str = "KEPLEMON";
+
+// Resolves the station identifiers used by observation files to the sensors of known observatories
+pub struct SiteLookup<'a> {
+    observatories: &'a [Observatory],
+}
+
+impl<'a> SiteLookup<'a> {
+    pub fn new(observatories: &'a [Observatory]) -> Self {
+        Self { observatories }
+    }
+
+    pub fn find_by_number(&self, number: i32) -> Option<(Sensor, &'a Observatory)> {
+        self.find(
+            |sensor| sensor.get_sensor_id() == Some(number),
+            |observatory| observatory.get_site_id() == Some(number),
+        )
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<(Sensor, &'a Observatory)> {
+        self.find(
+            |sensor| sensor.get_name().eq_ignore_ascii_case(name),
+            |observatory| observatory.get_name().eq_ignore_ascii_case(name),
+        )
+    }
+
+    // Sensors match first, then observatories which report through their first sensor
+    fn find(
+        &self,
+        sensor_matches: impl Fn(&Sensor) -> bool,
+        observatory_matches: impl Fn(&Observatory) -> bool,
+    ) -> Option<(Sensor, &'a Observatory)> {
+        for observatory in self.observatories {
+            if let Some(sensor) = observatory
+                .get_sensors()
+                .into_iter()
+                .find(|sensor| sensor_matches(sensor))
+            {
+                return Some((sensor, observatory));
+            }
+        }
+        self.observatories
+            .iter()
+            .filter(|observatory| observatory_matches(observatory))
+            .find_map(|observatory| {
+                observatory
+                    .get_sensors()
+                    .into_iter()
+                    .next()
+                    .map(|sensor| (sensor, observatory))
+            })
+    }
+}
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ObservationCatalog {
+    name: Option<String>,
+    observations: Vec<Observation>,
+}
+
+fn read_file(file_path: &str) -> PyResult<String> {
+    fs::read_to_string(file_path).map_err(|e| PyIOError::new_err(format!("Unable to read {}: {}", file_path, e)))
+}
+
+fn write_file(file_path: &str, contents: &str) -> PyResult<()> {
+    fs::write(file_path, contents).map_err(|e| PyIOError::new_err(format!("Unable to write {}: {}", file_path, e)))
+}
+
+#[pymethods]
+impl ObservationCatalog {
+    #[new]
+    pub fn new() -> Self {
+        ObservationCatalog {
+            name: None,
+            observations: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, observation: Observation) {
+        self.observations.push(observation);
+    }
+
+    pub fn clear(&mut self) {
+        self.observations.clear();
+    }
+
+    #[getter]
+    pub fn get_count(&self) -> usize {
+        self.observations.len()
+    }
+
+    #[getter]
+    pub fn get_observations(&self) -> Vec<Observation> {
+        self.observations.clone()
+    }
+
+    #[getter]
+    pub fn get_name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    #[setter]
+    pub fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    #[staticmethod]
+    pub fn from_b3_file(file_path: &str, observatories: Vec<Observatory>) -> PyResult<Self> {
+        let contents = read_file(file_path)?;
+        let observations = b3::read(&contents, &SiteLookup::new(&observatories)).map_err(PyValueError::new_err)?;
+        Ok(Self::from_file(file_path, observations))
+    }
+
+    #[staticmethod]
+    pub fn from_tdm_file(file_path: &str, observatories: Vec<Observatory>) -> PyResult<Self> {
+        let contents = read_file(file_path)?;
+        let observations = tdm::read(&contents, &SiteLookup::new(&observatories)).map_err(PyValueError::new_err)?;
+        Ok(Self::from_file(file_path, observations))
+    }
+
+    #[staticmethod]
+    pub fn from_crd_file(file_path: &str, observatories: Vec<Observatory>) -> PyResult<Self> {
+        let contents = read_file(file_path)?;
+        let observations = crd::read(&contents, &SiteLookup::new(&observatories)).map_err(PyValueError::new_err)?;
+        Ok(Self::from_file(file_path, observations))
+    }
+
+    pub fn to_b3_file(&self, file_path: &str) -> PyResult<()> {
+        let contents = b3::write(&self.observations).map_err(PyValueError::new_err)?;
+        write_file(file_path, &contents)
+    }
+
+    pub fn to_tdm_file(&self, file_path: &str) -> PyResult<()> {
+        let contents = tdm::write(&self.observations, TDM_ORIGINATOR).map_err(PyValueError::new_err)?;
+        write_file(file_path, &contents)
+    }
+}
+
+impl ObservationCatalog {
+    fn from_file(file_path: &str, observations: Vec<Observation>) -> Self {
+        Self {
+            name: Some(file_path.to_string()),
+            observations,
+        }
+    }
+}
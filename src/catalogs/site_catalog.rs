@@ -0,0 +1,231 @@
+use crate::bodies::{Observatory, Sensor};
+use crate::enums::MeasurementType;
+use pyo3::exceptions::{PyIOError, PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+
+const BIAS_KEYS: [(&str, MeasurementType); 6] = [
+    ("right_ascension", MeasurementType::RightAscension),
+    ("declination", MeasurementType::Declination),
+    ("range", MeasurementType::Range),
+    ("range_rate", MeasurementType::RangeRate),
+    ("azimuth", MeasurementType::Azimuth),
+    ("elevation", MeasurementType::Elevation),
+];
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SiteCatalog {
+    name: Option<String>,
+    map: HashMap<i32, Observatory>,
+}
+
+// Members set to null are treated as absent
+fn get_member<'a>(definition: &'a Value, key: &str) -> Option<&'a Value> {
+    definition.get(key).filter(|value| !value.is_null())
+}
+
+fn get_f64(definition: &Value, key: &str) -> Result<Option<f64>, String> {
+    match get_member(definition, key) {
+        Some(value) => value
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| format!("'{}' must be a number", key)),
+        None => Ok(None),
+    }
+}
+
+fn get_i32(definition: &Value, key: &str) -> Result<Option<i32>, String> {
+    match get_member(definition, key) {
+        Some(value) => value
+            .as_i64()
+            .and_then(|value| i32::try_from(value).ok())
+            .map(Some)
+            .ok_or_else(|| format!("'{}' must be an integer", key)),
+        None => Ok(None),
+    }
+}
+
+fn get_array<'a>(definition: &'a Value, key: &str) -> Result<&'a [Value], String> {
+    match get_member(definition, key) {
+        Some(value) => value
+            .as_array()
+            .map(|values| values.as_slice())
+            .ok_or_else(|| format!("'{}' must be an array", key)),
+        None => Ok(&[]),
+    }
+}
+
+fn require_f64(definition: &Value, key: &str) -> Result<f64, String> {
+    get_f64(definition, key)?.ok_or_else(|| format!("Missing '{}'", key))
+}
+
+fn require_str<'a>(definition: &'a Value, key: &str) -> Result<&'a str, String> {
+    match get_member(definition, key) {
+        Some(value) => value.as_str().ok_or_else(|| format!("'{}' must be a string", key)),
+        None => Err(format!("Missing '{}'", key)),
+    }
+}
+
+fn parse_sensor(definition: &Value) -> Result<Sensor, String> {
+    let name = require_str(definition, "name")?;
+    let mut sensor = Sensor::new(name.to_string(), require_f64(definition, "angular_noise")?);
+    let context = |e: String| format!("Sensor {}: {}", name, e);
+    if let Some(sensor_id) = get_i32(definition, "sensor_id").map_err(context)? {
+        sensor.set_sensor_id(sensor_id);
+    }
+    if let Some(range_noise) = get_f64(definition, "range_noise").map_err(context)? {
+        sensor.set_range_noise(range_noise);
+    }
+    if let Some(range_rate_noise) = get_f64(definition, "range_rate_noise").map_err(context)? {
+        sensor.set_range_rate_noise(range_rate_noise);
+    }
+    if let Some(angular_rate_noise) = get_f64(definition, "angular_rate_noise").map_err(context)? {
+        sensor.set_angular_rate_noise(angular_rate_noise);
+    }
+    sensor.set_min_elevation(get_f64(definition, "min_elevation").map_err(context)?);
+    sensor.set_max_range(get_f64(definition, "max_range").map_err(context)?);
+    if let Some(biases) = get_member(definition, "biases") {
+        for (key, measurement_type) in BIAS_KEYS {
+            if let Some(bias) = get_f64(biases, key).map_err(context)? {
+                sensor.set_bias(measurement_type, bias)?;
+            }
+        }
+    }
+    Ok(sensor)
+}
+
+fn parse_site(definition: &Value) -> Result<Observatory, String> {
+    let name = require_str(definition, "name")?;
+    let context = |e: String| format!("Site {}: {}", name, e);
+    let site_id = get_i32(definition, "site_id")
+        .map_err(context)?
+        .ok_or_else(|| context("Missing 'site_id'".to_string()))?;
+    let mut observatory = Observatory::new(
+        name.to_string(),
+        require_f64(definition, "latitude").map_err(context)?,
+        require_f64(definition, "longitude").map_err(context)?,
+        require_f64(definition, "altitude").map_err(context)?,
+    );
+    observatory.set_site_id(site_id);
+    for sensor in get_array(definition, "sensors").map_err(context)? {
+        observatory.add_sensor(parse_sensor(sensor).map_err(context)?);
+    }
+    Ok(observatory)
+}
+
+#[pymethods]
+impl SiteCatalog {
+    #[new]
+    pub fn new() -> Self {
+        SiteCatalog {
+            name: None,
+            map: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, observatory: Observatory) -> PyResult<()> {
+        match observatory.get_site_id() {
+            Some(site_id) => {
+                self.map.insert(site_id, observatory);
+                Ok(())
+            }
+            None => Err(PyErr::new::<PyValueError, _>(format!(
+                "Observatory {} has no site ID",
+                observatory.get_name()
+            ))),
+        }
+    }
+
+    pub fn keys(&self) -> Vec<i32> {
+        self.map.keys().cloned().collect()
+    }
+
+    pub fn get(&self, site_id: i32) -> Option<Observatory> {
+        self.map.get(&site_id).cloned()
+    }
+
+    pub fn get_sensor(&self, sensor_id: i32) -> Option<Sensor> {
+        self.map.values().find_map(|observatory| {
+            observatory
+                .get_sensors()
+                .into_iter()
+                .find(|sensor| sensor.get_sensor_id() == Some(sensor_id))
+        })
+    }
+
+    pub fn get_observatory_for_sensor(&self, sensor_id: i32) -> Option<Observatory> {
+        self.map
+            .values()
+            .find(|observatory| {
+                observatory
+                    .get_sensors()
+                    .iter()
+                    .any(|sensor| sensor.get_sensor_id() == Some(sensor_id))
+            })
+            .cloned()
+    }
+
+    pub fn remove(&mut self, site_id: i32) {
+        self.map.remove(&site_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    fn __getitem__(&self, site_id: i32) -> PyResult<Observatory> {
+        match self.map.get(&site_id) {
+            Some(observatory) => Ok(observatory.clone()),
+            None => Err(PyKeyError::new_err(format!("Invalid key: {}", site_id))),
+        }
+    }
+
+    #[getter]
+    pub fn get_count(&self) -> usize {
+        self.map.len()
+    }
+
+    #[getter]
+    pub fn get_observatories(&self) -> Vec<Observatory> {
+        let mut observatories: Vec<Observatory> = self.map.values().cloned().collect();
+        observatories.sort_by_key(|observatory| observatory.get_site_id());
+        observatories
+    }
+
+    #[getter]
+    pub fn get_name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    #[setter]
+    pub fn set_name(&mut self, name: String) {
+        self.name = Some(name);
+    }
+
+    #[staticmethod]
+    pub fn from_json_file(file_path: &str) -> PyResult<Self> {
+        let contents = fs::read_to_string(file_path)
+            .map_err(|e| PyIOError::new_err(format!("Unable to read {}: {}", file_path, e)))?;
+        let mut catalog = Self::from_json(&contents).map_err(PyValueError::new_err)?;
+        catalog.name = Some(file_path.to_string());
+        Ok(catalog)
+    }
+}
+
+impl SiteCatalog {
+    pub fn from_json(contents: &str) -> Result<Self, String> {
+        let root: Value = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+        let mut catalog = SiteCatalog::default();
+        for definition in get_array(&root, "sites")? {
+            let observatory = parse_site(definition)?;
+            let site_id = observatory.get_site_id().unwrap();
+            if catalog.map.insert(site_id, observatory).is_some() {
+                return Err(format!("Site ID {} is defined more than once", site_id));
+            }
+        }
+        Ok(catalog)
+    }
+}
@@ -0,0 +1,329 @@
+// CCSDS Tracking Data Messages in keyword-value (KVN) or XML notation
+use super::observation_catalog::SiteLookup;
+use crate::elements::{HorizonElements, TopocentricElements};
+use crate::enums::{EquinoxType, TimeSystem};
+use crate::estimation::Observation;
+use crate::saal::{astro_func_interface, time_func_interface};
+use crate::time::Epoch;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TDM_VERSION: &str = "2.0";
+const ANGLE_KEYWORDS: [&str; 2] = ["ANGLE_1", "ANGLE_2"];
+const RANGE_KEYWORD: &str = "RANGE";
+const RANGE_RATE_KEYWORD: &str = "DOPPLER_INSTANTANEOUS";
+const MICROSECONDS_PER_DAY: f64 = 86_400_000_000.0;
+
+#[derive(Default)]
+struct Segment {
+    metadata: HashMap<String, String>,
+    data: Vec<(String, String, f64)>,
+}
+
+// Epochs are written as calendar (YYYY-MM-DDThh:mm:ss) or ordinal (YYYY-DDDThh:mm:ss) dates
+fn parse_epoch(text: &str, time_system: TimeSystem) -> Result<Epoch, String> {
+    Epoch::parse_iso(text, time_system).map_err(|e| format!("Invalid epoch '{}': {}", text.trim(), e))
+}
+
+fn get_time_system(segment: &Segment) -> Result<TimeSystem, String> {
+    match segment.metadata.get("TIME_SYSTEM").map(|s| s.as_str()) {
+        Some("UTC") => Ok(TimeSystem::UTC),
+        Some("TAI") => Ok(TimeSystem::TAI),
+        Some("TT") => Ok(TimeSystem::TT),
+        Some("UT1") => Ok(TimeSystem::UT1),
+        Some("GPS") => Ok(TimeSystem::GPS),
+        Some("TDB") => Ok(TimeSystem::TDB),
+        Some("TCG") => Ok(TimeSystem::TCG),
+        Some(other) => Err(format!("Unsupported time system '{}'", other)),
+        None => Err("Missing TIME_SYSTEM".to_string()),
+    }
+}
+
+fn strip_comment(line: &str) -> Option<&str> {
+    let line = line.trim();
+    match line.is_empty() || line.starts_with("COMMENT") {
+        true => None,
+        false => Some(line),
+    }
+}
+
+fn parse_kvn(contents: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut segment = Segment::default();
+    let mut in_metadata = false;
+    let mut in_data = false;
+    for (i, line) in contents.lines().enumerate() {
+        let line = match strip_comment(line) {
+            Some(line) => line,
+            None => continue,
+        };
+        match line {
+            "META_START" => {
+                segment = Segment::default();
+                in_metadata = true;
+            }
+            "META_STOP" => in_metadata = false,
+            "DATA_START" => in_data = true,
+            "DATA_STOP" => {
+                in_data = false;
+                segments.push(std::mem::take(&mut segment));
+            }
+            _ => {
+                let (keyword, value) = line
+                    .split_once('=')
+                    .ok_or_else(|| format!("Line {}: Expected KEYWORD = VALUE", i + 1))?;
+                let keyword = keyword.trim().to_string();
+                let value = value.trim();
+                if in_metadata {
+                    segment.metadata.insert(keyword, value.to_string());
+                } else if in_data {
+                    let mut fields = value.split_whitespace();
+                    let (epoch, measurement) = match (fields.next(), fields.next()) {
+                        (Some(epoch), Some(measurement)) => (epoch, measurement),
+                        _ => return Err(format!("Line {}: Expected an epoch and a measurement", i + 1)),
+                    };
+                    let measurement: f64 = measurement
+                        .parse()
+                        .map_err(|_| format!("Line {}: Invalid measurement '{}'", i + 1, measurement))?;
+                    segment.data.push((keyword, epoch.to_string(), measurement));
+                }
+            }
+        }
+    }
+    Ok(segments)
+}
+
+// Walks the leaf elements of a TDM XML document, which carry every metadata and data value
+fn parse_xml(contents: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut segment = Segment::default();
+    let mut in_metadata = false;
+    let mut observation: HashMap<String, String> = HashMap::new();
+    let mut rest = contents;
+    while let Some(start) = rest.find('<') {
+        let end = rest[start..]
+            .find('>')
+            .map(|end| start + end)
+            .ok_or("Unterminated XML tag")?;
+        let tag = &rest[start + 1..end];
+        let text = rest[..start].trim();
+        rest = &rest[end + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') || tag.ends_with('/') {
+            continue;
+        }
+        let name = tag.split_whitespace().next().unwrap_or("");
+        let name = name.rsplit(':').next().unwrap_or(name);
+        match name {
+            "segment" => segment = Segment::default(),
+            "/segment" => segments.push(std::mem::take(&mut segment)),
+            "metadata" => in_metadata = true,
+            "/metadata" => in_metadata = false,
+            "observation" => observation.clear(),
+            "/observation" => {
+                let epoch = observation.remove("EPOCH").ok_or("Observation is missing EPOCH")?;
+                for (keyword, value) in observation.drain() {
+                    let measurement: f64 = value
+                        .parse()
+                        .map_err(|_| format!("Invalid measurement '{}' for {}", value, keyword))?;
+                    segment.data.push((keyword, epoch.clone(), measurement));
+                }
+            }
+            _ => {
+                if let Some(closed) = name.strip_prefix('/') {
+                    let text = text.to_string();
+                    if in_metadata {
+                        segment.metadata.insert(closed.to_string(), text);
+                    } else {
+                        observation.insert(closed.to_string(), text);
+                    }
+                }
+            }
+        }
+    }
+    Ok(segments)
+}
+
+fn get_observations(segment: &Segment, sites: &SiteLookup) -> Result<Vec<Observation>, String> {
+    let participants: Vec<&String> = (1..=5)
+        .filter_map(|i| segment.metadata.get(&format!("PARTICIPANT_{}", i)))
+        .collect();
+    let (site_index, (sensor, observatory)) = participants
+        .iter()
+        .enumerate()
+        .find_map(|(i, participant)| sites.find_by_name(participant).map(|site| (i, site)))
+        .ok_or_else(|| format!("No observatory matches participants {:?}", participants))?;
+
+    // The target is a participant other than the observatory, which may itself have a numeric name
+    let satellite_id = participants
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != site_index)
+        .find_map(|(_, participant)| participant.parse::<i32>().ok());
+    let time_system = get_time_system(segment)?;
+    if let Some(units) = segment.metadata.get("RANGE_UNITS") {
+        if units != "km" && segment.data.iter().any(|(keyword, _, _)| keyword == RANGE_KEYWORD) {
+            return Err(format!("Unsupported range units '{}'", units));
+        }
+    }
+    let angle_type = segment.metadata.get("ANGLE_TYPE").map(|s| s.as_str());
+    let frame = segment.metadata.get("REFERENCE_FRAME").map(|s| s.as_str());
+
+    // Measurements sharing an epoch belong to the same observation
+    let mut measurements: BTreeMap<i64, (Epoch, HashMap<&str, f64>)> = BTreeMap::new();
+    for (keyword, epoch, value) in segment.data.iter() {
+        let key = match keyword.as_str() {
+            "ANGLE_1" | "ANGLE_2" | RANGE_KEYWORD | RANGE_RATE_KEYWORD => keyword.as_str(),
+            _ => continue,
+        };
+        let epoch = parse_epoch(epoch, time_system)?
+            .to_system(TimeSystem::UTC)
+            .map_err(|e| e.to_string())?;
+        let order = (epoch.days_since_1950 * MICROSECONDS_PER_DAY).round() as i64;
+        measurements
+            .entry(order)
+            .or_insert_with(|| (epoch, HashMap::new()))
+            .1
+            .insert(key, *value);
+    }
+
+    let mut observations = Vec::new();
+    for (epoch, values) in measurements.into_values() {
+        let site_position = observatory.get_state_at_epoch(epoch).position;
+        let range = values.get(RANGE_KEYWORD).copied();
+        let range_rate = values.get(RANGE_RATE_KEYWORD).copied();
+        let mut ob = match (values.get("ANGLE_1"), values.get("ANGLE_2")) {
+            (Some(angle_1), Some(angle_2)) => match angle_type {
+                Some("RADEC") => {
+                    let mut topocentric = match frame {
+                        Some("EME2000") | Some("ICRF") => {
+                            let (ra, dec) = astro_func_interface::topo_equinox_to_date(
+                                EquinoxType::J2000.get_value(),
+                                epoch.days_since_1950,
+                                *angle_1,
+                                *angle_2,
+                            );
+                            TopocentricElements::new(ra, dec)
+                        }
+                        Some("TEME") => TopocentricElements::new(*angle_1, *angle_2),
+                        Some(other) => return Err(format!("Unsupported reference frame '{}'", other)),
+                        None => return Err("Missing REFERENCE_FRAME for RADEC angles".to_string()),
+                    };
+                    topocentric.set_range(range);
+                    topocentric.set_range_rate(range_rate);
+                    Observation::new(sensor.clone(), epoch, topocentric, site_position)
+                }
+                Some("AZEL") => {
+                    let mut horizon = HorizonElements::new(*angle_1, *angle_2);
+                    horizon.set_range(range);
+                    horizon.set_range_rate(range_rate);
+                    Observation::from_horizon(sensor.clone(), epoch, horizon, site_position)
+                }
+                Some(other) => return Err(format!("Unsupported angle type '{}'", other)),
+                None => return Err("Missing ANGLE_TYPE".to_string()),
+            },
+            (None, None) => match (range, range_rate) {
+                (Some(range), _) => Observation::from_range(sensor.clone(), epoch, range, site_position),
+                (None, Some(range_rate)) => {
+                    Observation::from_range_rate(sensor.clone(), epoch, range_rate, site_position)
+                }
+                (None, None) => continue,
+            },
+            _ => return Err(format!("Unpaired angle at {}", epoch.to_iso())),
+        };
+        if let Some(satellite_id) = satellite_id {
+            ob.set_observed_satellite_id(satellite_id);
+        }
+        observations.push(ob);
+    }
+    Ok(observations)
+}
+
+pub fn read(contents: &str, sites: &SiteLookup) -> Result<Vec<Observation>, String> {
+    let segments = match contents.trim_start().starts_with('<') {
+        true => parse_xml(contents)?,
+        false => parse_kvn(contents)?,
+    };
+    let mut observations = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let segment_observations = get_observations(segment, sites).map_err(|e| format!("Segment {}: {}", i + 1, e))?;
+        observations.extend(segment_observations);
+    }
+    Ok(observations)
+}
+
+fn format_data_line(keyword: &str, epoch: Epoch, value: f64) -> String {
+    format!("{} = {} {:.9}\n", keyword, epoch.to_iso(), value)
+}
+
+pub fn write(observations: &[Observation], originator: &str) -> Result<String, String> {
+    // Each sensor, target and angle type pairing is written as its own segment
+    let mut segments: BTreeMap<(String, Option<i32>, &str), Vec<&Observation>> = BTreeMap::new();
+    for ob in observations {
+        let angle_type = match (ob.get_azimuth(), ob.get_right_ascension()) {
+            (Some(_), _) => "AZEL",
+            (None, Some(_)) => "RADEC",
+            (None, None) => "",
+        };
+        segments
+            .entry((ob.get_sensor().get_name(), ob.get_observed_satellite_id(), angle_type))
+            .or_default()
+            .push(ob);
+    }
+
+    let unix_seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs_f64();
+    let now = Epoch::from_days_since_1950(
+        time_func_interface::ymd_components_to_ds50(1970, 1, 1, 0, 0, 0.0) + unix_seconds / 86400.0,
+        TimeSystem::UTC,
+    );
+    let mut contents = format!(
+        "CCSDS_TDM_VERS = {}\nCREATION_DATE = {}\nORIGINATOR = {}\n",
+        TDM_VERSION,
+        now.to_iso(),
+        originator
+    );
+    for ((sensor_name, satellite_id, angle_type), mut segment) in segments {
+        segment.sort_by(|a, b| a.get_epoch().partial_cmp(&b.get_epoch()).unwrap());
+        let satellite_id =
+            satellite_id.ok_or_else(|| format!("Observed satellite ID is not set for {}", sensor_name))?;
+        contents.push_str("\nMETA_START\nTIME_SYSTEM = UTC\n");
+        contents.push_str(&format!(
+            "PARTICIPANT_1 = {}\nPARTICIPANT_2 = {}\n",
+            sensor_name, satellite_id
+        ));
+        contents.push_str("MODE = SEQUENTIAL\nPATH = 1,2,1\n");
+        match angle_type {
+            "AZEL" => contents.push_str("ANGLE_TYPE = AZEL\n"),
+            "RADEC" => contents.push_str("ANGLE_TYPE = RADEC\nREFERENCE_FRAME = EME2000\n"),
+            _ => {}
+        }
+        contents.push_str("RANGE_UNITS = km\nMETA_STOP\n\nDATA_START\n");
+        for ob in segment {
+            let epoch = ob.get_epoch().to_system(TimeSystem::UTC).map_err(|e| e.to_string())?;
+            let angles = match angle_type {
+                "AZEL" => Some((ob.get_azimuth().unwrap(), ob.get_elevation().unwrap())),
+                "RADEC" => Some(astro_func_interface::topo_date_to_equinox(
+                    EquinoxType::J2000.get_value(),
+                    epoch.days_since_1950,
+                    ob.get_right_ascension().unwrap(),
+                    ob.get_declination().unwrap(),
+                )),
+                _ => None,
+            };
+            if let Some(angles) = angles {
+                contents.push_str(&format_data_line(ANGLE_KEYWORDS[0], epoch, angles.0));
+                contents.push_str(&format_data_line(ANGLE_KEYWORDS[1], epoch, angles.1));
+            }
+            if let Some(range) = ob.get_range() {
+                contents.push_str(&format_data_line(RANGE_KEYWORD, epoch, range));
+            }
+            if let Some(range_rate) = ob.get_range_rate() {
+                contents.push_str(&format_data_line(RANGE_RATE_KEYWORD, epoch, range_rate));
+            }
+        }
+        contents.push_str("DATA_STOP\n");
+    }
+    Ok(contents)
+}
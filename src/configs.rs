@@ -3,3 +3,4 @@ pub const MAX_NEWTON_ITERATIONS: usize = 10;
 pub const NEWTON_TOLERANCE: f64 = 1e-6;
 pub const DEFAULT_SRP_TERM: f64 = 0.03;
 pub const DEFAULT_DRAG_TERM: f64 = 0.01;
+pub const DEFAULT_CORRELATION_SIGNIFICANCE: f64 = 0.001;
@@ -3,6 +3,7 @@ mod cartesian_vector;
 mod ephemeris;
 mod equinoctial_elements;
 mod geodetic_position;
+mod horizon_elements;
 mod keplerian_elements;
 mod keplerian_state;
 mod spherical_vector;
@@ -14,12 +15,13 @@ pub use cartesian_vector::CartesianVector;
 pub use ephemeris::Ephemeris;
 pub use equinoctial_elements::EquinoctialElements;
 pub use geodetic_position::GeodeticPosition;
+pub use horizon_elements::HorizonElements;
 pub use keplerian_elements::KeplerianElements;
 pub use keplerian_state::KeplerianState;
 use pyo3::prelude::*;
 use pyo3::py_run;
 pub use spherical_vector::SphericalVector;
-pub use tle::TLE;
+pub use tle::{ForcePerturbations, TLE};
 pub use topocentric_elements::TopocentricElements;
 
 pub const B_STAR_TO_B_TERM: f64 = 12.741621;
@@ -34,6 +36,7 @@ pub fn register_elements(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     elements.add_class::<Ephemeris>()?;
     elements.add_class::<SphericalVector>()?;
     elements.add_class::<TopocentricElements>()?;
+    elements.add_class::<HorizonElements>()?;
     elements.add_class::<EquinoctialElements>()?;
     elements.add_class::<GeodeticPosition>()?;
     py_run!(
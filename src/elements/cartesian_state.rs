@@ -1,7 +1,8 @@
 use super::{CartesianVector, KeplerianElements, KeplerianState};
-use crate::enums::{KeplerianType, ReferenceFrame};
+use crate::enums::{EarthRotationModel, KeplerianType, ReferenceFrame};
 use crate::saal::astro_func_interface;
 use crate::time::Epoch;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
 #[pyclass]
@@ -37,44 +38,50 @@ impl CartesianState {
             _ => panic!("Invalid element index"),
         }
     }
-}
-
-#[pymethods]
-impl CartesianState {
-    #[new]
-    pub fn new(epoch: Epoch, position: CartesianVector, velocity: CartesianVector, frame: ReferenceFrame) -> Self {
-        Self {
-            epoch,
-            position,
-            velocity,
-            frame,
-        }
-    }
-
-    #[getter]
-    pub fn get_position(&self) -> CartesianVector {
-        self.position
-    }
-
-    #[getter]
-    pub fn get_velocity(&self) -> CartesianVector {
-        self.velocity
-    }
 
-    #[getter]
-    pub fn get_epoch(&self) -> Epoch {
-        self.epoch
-    }
-
-    pub fn get_frame(&self) -> ReferenceFrame {
-        self.frame
+    fn is_inertial(frame: ReferenceFrame) -> bool {
+        matches!(frame, ReferenceFrame::TEME | ReferenceFrame::J2000)
     }
 
-    pub fn to_keplerian(&self) -> KeplerianState {
-        let pos: [f64; 3] = [self.position[0], self.position[1], self.position[2]];
-        let vel: [f64; 3] = [self.velocity[0], self.velocity[1], self.velocity[2]];
-        let kep = KeplerianElements::from(astro_func_interface::cartesian_to_keplerian(&pos, &vel));
-        KeplerianState::new(self.epoch, kep, self.frame, KeplerianType::Osculating)
+    // Rotates between TEME and EFG by the Greenwich angle of the model when crossing between inertial and Earth-fixed.
+    // Apparent sidereal time and the Earth rotation angle are measured from the true equinox and the CIO, so they
+    // only apply from TOD and CIRS, and GMST06 from the IAU 2006 mean equinox.  TEME is defined by the IAU-82 mean
+    // sidereal time, so only the FK4, FK5 and GMST82 angles can be paired with it.
+    pub fn to_frame_with_rotation(
+        &self,
+        frame: ReferenceFrame,
+        rotation: EarthRotationModel,
+    ) -> Result<CartesianState, String> {
+        if !matches!(
+            rotation,
+            EarthRotationModel::FK4 | EarthRotationModel::FK5 | EarthRotationModel::GMST82
+        ) {
+            return Err(format!(
+                "{:?} does not rotate TEME to EFG; use FK4, FK5 or GMST82",
+                rotation
+            ));
+        }
+        if Self::is_inertial(self.frame) == Self::is_inertial(frame) {
+            return Ok(self.to_frame(frame));
+        }
+        let theta_g = self.epoch.to_greenwich_angle(rotation);
+        let (state, rotated_frame) = match Self::is_inertial(self.frame) {
+            true => (self.to_frame(ReferenceFrame::TEME), ReferenceFrame::EFG),
+            false => (self.to_frame(ReferenceFrame::EFG), ReferenceFrame::TEME),
+        };
+        let in_pos: [f64; 3] = [state.position[0], state.position[1], state.position[2]];
+        let in_vel: [f64; 3] = [state.velocity[0], state.velocity[1], state.velocity[2]];
+        let (out_pos, out_vel) = match rotated_frame {
+            ReferenceFrame::EFG => astro_func_interface::teme_to_efg_at_angle(theta_g, &in_pos, &in_vel),
+            _ => astro_func_interface::efg_to_teme_at_angle(theta_g, &in_pos, &in_vel),
+        };
+        Ok(CartesianState::new(
+            self.epoch,
+            CartesianVector::from(out_pos),
+            CartesianVector::from(out_vel),
+            rotated_frame,
+        )
+        .to_frame(frame))
     }
 
     pub fn to_frame(&self, frame: ReferenceFrame) -> CartesianState {
@@ -217,10 +224,59 @@ impl CartesianState {
     }
 }
 
+#[pymethods]
+impl CartesianState {
+    #[new]
+    pub fn new(epoch: Epoch, position: CartesianVector, velocity: CartesianVector, frame: ReferenceFrame) -> Self {
+        Self {
+            epoch,
+            position,
+            velocity,
+            frame,
+        }
+    }
+
+    #[getter]
+    pub fn get_position(&self) -> CartesianVector {
+        self.position
+    }
+
+    #[getter]
+    pub fn get_velocity(&self) -> CartesianVector {
+        self.velocity
+    }
+
+    #[getter]
+    pub fn get_epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    pub fn get_frame(&self) -> ReferenceFrame {
+        self.frame
+    }
+
+    pub fn to_keplerian(&self) -> KeplerianState {
+        let pos: [f64; 3] = [self.position[0], self.position[1], self.position[2]];
+        let vel: [f64; 3] = [self.velocity[0], self.velocity[1], self.velocity[2]];
+        let kep = KeplerianElements::from(astro_func_interface::cartesian_to_keplerian(&pos, &vel));
+        KeplerianState::new(self.epoch, kep, self.frame, KeplerianType::Osculating)
+    }
+
+    #[pyo3(name = "to_frame", signature = (frame, rotation = None))]
+    fn py_to_frame(&self, frame: ReferenceFrame, rotation: Option<EarthRotationModel>) -> PyResult<CartesianState> {
+        match rotation {
+            Some(rotation) => self
+                .to_frame_with_rotation(frame, rotation)
+                .map_err(PyValueError::new_err),
+            None => Ok(self.to_frame(frame)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{CartesianState, CartesianVector};
-    use crate::enums::{ReferenceFrame, TimeSystem};
+    use crate::enums::{EarthRotationModel, ReferenceFrame, TimeSystem};
     use crate::time::Epoch;
     use approx::assert_abs_diff_eq;
 
@@ -247,4 +303,54 @@ mod tests {
         assert_eq!(osc.get_frame(), ReferenceFrame::TEME);
         assert_eq!(osc.get_epoch(), state.epoch);
     }
+
+    #[test]
+    fn test_to_frame_with_rotation() {
+        let state = CartesianState::new(epoch_1(), geo_position(), geo_velocity(), ReferenceFrame::TEME);
+        let fk5 = state
+            .to_frame_with_rotation(ReferenceFrame::EFG, EarthRotationModel::FK5)
+            .unwrap();
+        let saal = state.to_frame(ReferenceFrame::EFG);
+        assert_abs_diff_eq!(fk5.position[0], saal.position[0], epsilon = 1e-6);
+        assert_abs_diff_eq!(fk5.position[1], saal.position[1], epsilon = 1e-6);
+
+        let efg = state
+            .to_frame_with_rotation(ReferenceFrame::EFG, EarthRotationModel::GMST82)
+            .unwrap();
+        assert_eq!(efg.get_frame(), ReferenceFrame::EFG);
+        let teme = efg
+            .to_frame_with_rotation(ReferenceFrame::TEME, EarthRotationModel::GMST82)
+            .unwrap();
+        assert_abs_diff_eq!(teme.position[0], state.position[0], epsilon = 1e-6);
+        assert_abs_diff_eq!(teme.position[1], state.position[1], epsilon = 1e-6);
+        assert_abs_diff_eq!(teme.velocity[1], state.velocity[1], epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_to_frame_with_rotation_reference() {
+        // Vallado, Fundamentals of Astrodynamics and Applications, Example 3-5: GMST of 152.578787886 degrees at
+        // 1992-08-20 12:14 UT1
+        let epoch = Epoch::from_days_since_1950(15573.509722222223, TimeSystem::UT1);
+        let theta_g = 152.578787886_f64.to_radians();
+        let state = CartesianState::new(epoch, geo_position(), geo_velocity(), ReferenceFrame::TEME);
+        let efg = state
+            .to_frame_with_rotation(ReferenceFrame::EFG, EarthRotationModel::GMST82)
+            .unwrap();
+        assert_abs_diff_eq!(efg.position[0], 42164.0 * theta_g.cos(), epsilon = 1e-4);
+        assert_abs_diff_eq!(efg.position[1], -42164.0 * theta_g.sin(), epsilon = 1e-4);
+        assert_abs_diff_eq!(efg.position[2], 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_to_frame_with_rotation_rejects_other_equinoxes() {
+        let state = CartesianState::new(epoch_1(), geo_position(), geo_velocity(), ReferenceFrame::TEME);
+        for rotation in [
+            EarthRotationModel::GAST82,
+            EarthRotationModel::GMST06,
+            EarthRotationModel::GAST06,
+            EarthRotationModel::ERA,
+        ] {
+            assert!(state.to_frame_with_rotation(ReferenceFrame::EFG, rotation).is_err());
+        }
+    }
 }
@@ -4,7 +4,7 @@ use crate::elements::{CartesianState, CartesianVector};
 use crate::enums::{ReferenceFrame, TimeSystem};
 use crate::events::CloseApproach;
 use crate::saal::ext_ephem_interface;
-use crate::time::{Epoch, TimeSpan};
+use crate::time::{Epoch, EpochRange, TimeSpan};
 use pyo3::prelude::*;
 
 #[pyclass]
@@ -81,13 +81,21 @@ impl Ephemeris {
         let (ds50_start, ds50_end) = ext_ephem_interface::get_ds50_utc_range(self.key).unwrap();
         let start_epoch = Epoch::from_days_since_1950(ds50_start, TimeSystem::UTC);
         let end_epoch = Epoch::from_days_since_1950(ds50_end, TimeSystem::UTC);
+        let epochs = EpochRange::new(start_epoch, end_epoch, TimeSpan::from_minutes(CONJUNCTION_STEP_MINUTES)).ok()?;
+        self.get_close_approach_over(other, &epochs, distance_threshold)
+    }
 
-        let mut closest_epoch = start_epoch;
+    pub fn get_close_approach_over(
+        &self,
+        other: &Ephemeris,
+        epochs: &EpochRange,
+        distance_threshold: f64,
+    ) -> Option<CloseApproach> {
+        let mut closest_epoch = epochs.get_start();
         let mut min_distance = f64::MAX;
-        let mut current_epoch = start_epoch;
-        let step = TimeSpan::from_minutes(CONJUNCTION_STEP_MINUTES);
+        let step = epochs.get_step();
 
-        while current_epoch <= end_epoch {
+        for current_epoch in epochs {
             let state_1 = self.get_state_at_epoch(current_epoch);
             let state_2 = other.get_state_at_epoch(current_epoch);
 
@@ -104,7 +112,6 @@ impl Ephemeris {
                     let t_max = current_epoch + step;
 
                     if t < t_min || t > t_max {
-                        current_epoch += step;
                         continue;
                     }
                     if let Some(ca) = refine_close_approach(self, other, t) {
@@ -118,8 +125,6 @@ impl Ephemeris {
                     break;
                 }
             }
-
-            current_epoch += step;
         }
         if min_distance < distance_threshold {
             Some(CloseApproach::new(
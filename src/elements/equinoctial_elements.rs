@@ -1,8 +1,14 @@
-use super::KeplerianElements;
+use super::{CartesianState, KeplerianElements};
 use crate::saal::astro_func_interface;
+use crate::time::DAYS_TO_SECONDS;
+use nalgebra::DMatrix;
 use pyo3::prelude::*;
+use std::f64::consts::{PI, TAU};
 use std::ops::{Index, IndexMut};
 
+const MAX_KEPLER_ITERATIONS: usize = 20;
+const KEPLER_TOLERANCE: f64 = 1e-14;
+
 #[pyclass]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct EquinoctialElements {
@@ -38,6 +44,136 @@ impl EquinoctialElements {
     }
 }
 
+impl EquinoctialElements {
+    fn get_eccentric_longitude(&self) -> f64 {
+        let mean_longitude = self.mean_longitude.to_radians();
+        let mut eccentric_longitude = mean_longitude;
+        for _ in 0..MAX_KEPLER_ITERATIONS {
+            let (sin_f, cos_f) = eccentric_longitude.sin_cos();
+            let error = eccentric_longitude + self.a_g * cos_f - self.a_f * sin_f - mean_longitude;
+            let step = error / (1.0 - self.a_g * sin_f - self.a_f * cos_f);
+            eccentric_longitude -= step;
+            if step.abs() < KEPLER_TOLERANCE {
+                break;
+            }
+        }
+        eccentric_longitude
+    }
+
+    // Two-body partials of the TEME position and velocity with respect to each element
+    pub fn get_cartesian_partials(&self) -> DMatrix<f64> {
+        let (k, h, p, q) = (self.a_f, self.a_g, self.chi, self.psi);
+        let n = self.mean_motion * TAU / DAYS_TO_SECONDS;
+        let a = astro_func_interface::mean_motion_to_sma(self.mean_motion);
+        let (sin_f, cos_f) = self.get_eccentric_longitude().sin_cos();
+        let s = (1.0 - k * k - h * h).sqrt();
+        let beta = 1.0 / (1.0 + s);
+        let d = 1.0 - k * cos_f - h * sin_f;
+
+        // In-plane coordinates scaled by the semi-major axis and their eccentric longitude derivatives
+        let x = (1.0 - h * h * beta) * cos_f + k * h * beta * sin_f - k;
+        let y = (1.0 - k * k * beta) * sin_f + k * h * beta * cos_f - h;
+        let x_f = -(1.0 - h * h * beta) * sin_f + k * h * beta * cos_f;
+        let y_f = (1.0 - k * k * beta) * cos_f - k * h * beta * sin_f;
+        let x_ff = -(1.0 - h * h * beta) * cos_f - k * h * beta * sin_f;
+        let y_ff = -(1.0 - k * k * beta) * sin_f - k * h * beta * cos_f;
+
+        // Derivatives of the eccentric longitude, beta and d with respect to af, ag and L (radians)
+        let df = [sin_f / d, -cos_f / d, 1.0 / d];
+        let dbeta = [k * beta * beta / s, h * beta * beta / s, 0.0];
+        let d_f = k * sin_f - h * cos_f;
+        let dd = [-cos_f + d_f * df[0], -sin_f + d_f * df[1], d_f * df[2]];
+
+        // Explicit derivatives of each in-plane term with respect to af, ag and beta
+        let x_k = [h * beta * sin_f - 1.0, -2.0 * h * beta * cos_f + k * beta * sin_f];
+        let y_k = [-2.0 * k * beta * sin_f + h * beta * cos_f, k * beta * cos_f - 1.0];
+        let x_f_k = [h * beta * cos_f, 2.0 * h * beta * sin_f + k * beta * cos_f];
+        let y_f_k = [-2.0 * k * beta * cos_f - h * beta * sin_f, -k * beta * sin_f];
+        let x_beta = -h * h * cos_f + k * h * sin_f;
+        let y_beta = -k * k * sin_f + k * h * cos_f;
+        let x_f_beta = h * h * sin_f + k * h * cos_f;
+        let y_f_beta = -k * k * cos_f - k * h * sin_f;
+
+        let g = 1.0 + p * p + q * q;
+        let f_hat = [(1.0 - p * p + q * q) / g, 2.0 * p * q / g, -2.0 * p / g];
+        let g_hat = [2.0 * p * q / g, (1.0 + p * p - q * q) / g, 2.0 * q / g];
+        let f_hat_p = [-2.0 * p / g, 2.0 * q / g, -2.0 / g];
+        let f_hat_q = [2.0 * q / g, 2.0 * p / g, 0.0];
+        let g_hat_p = [2.0 * q / g, 2.0 * p / g, 0.0];
+        let g_hat_q = [2.0 * p / g, -2.0 * q / g, 2.0 / g];
+
+        let velocity_scale = n * a / d;
+        let position = [a * x, a * y];
+        let velocity = [velocity_scale * x_f, velocity_scale * y_f];
+
+        let mut partials = DMatrix::zeros(6, 6);
+        let mut set_in_plane = |col: usize, d_position: [f64; 2], d_velocity: [f64; 2]| {
+            for i in 0..3 {
+                partials[(i, col)] = d_position[0] * f_hat[i] + d_position[1] * g_hat[i];
+                partials[(i + 3, col)] = d_velocity[0] * f_hat[i] + d_velocity[1] * g_hat[i];
+            }
+        };
+
+        // af, ag and mean longitude change the in-plane coordinates
+        for (col, element) in [
+            (astro_func_interface::XA_EQNX_AF, 0),
+            (astro_func_interface::XA_EQNX_AG, 1),
+            (astro_func_interface::XA_EQNX_L, 2),
+        ] {
+            let explicit = |terms: &[f64; 2]| if element < 2 { terms[element] } else { 0.0 };
+            let dx = explicit(&x_k) + x_f * df[element] + x_beta * dbeta[element];
+            let dy = explicit(&y_k) + y_f * df[element] + y_beta * dbeta[element];
+            let dx_f = explicit(&x_f_k) + x_ff * df[element] + x_f_beta * dbeta[element];
+            let dy_f = explicit(&y_f_k) + y_ff * df[element] + y_f_beta * dbeta[element];
+            let scale = if element < 2 { 1.0 } else { PI / 180.0 };
+            set_in_plane(
+                col,
+                [a * dx * scale, a * dy * scale],
+                [
+                    velocity_scale * (dx_f - x_f * dd[element] / d) * scale,
+                    velocity_scale * (dy_f - y_f * dd[element] / d) * scale,
+                ],
+            );
+        }
+
+        // Mean motion scales the orbit size at a fixed mean longitude
+        let dn = TAU / DAYS_TO_SECONDS;
+        set_in_plane(
+            astro_func_interface::XA_EQNX_N,
+            [-2.0 * position[0] / (3.0 * n) * dn, -2.0 * position[1] / (3.0 * n) * dn],
+            [velocity[0] / (3.0 * n) * dn, velocity[1] / (3.0 * n) * dn],
+        );
+
+        // chi and psi rotate the orbital plane
+        for (col, f_hat_e, g_hat_e) in [
+            (astro_func_interface::XA_EQNX_CHI, f_hat_p, g_hat_p),
+            (astro_func_interface::XA_EQNX_PSI, f_hat_q, g_hat_q),
+        ] {
+            let dg = if col == astro_func_interface::XA_EQNX_CHI {
+                2.0 * p
+            } else {
+                2.0 * q
+            };
+            for i in 0..3 {
+                let df_hat = f_hat_e[i] - f_hat[i] * dg / g;
+                let dg_hat = g_hat_e[i] - g_hat[i] * dg / g;
+                partials[(i, col)] = position[0] * df_hat + position[1] * dg_hat;
+                partials[(i + 3, col)] = velocity[0] * df_hat + velocity[1] * dg_hat;
+            }
+        }
+        partials
+    }
+}
+
+impl From<&CartesianState> for EquinoctialElements {
+    fn from(state: &CartesianState) -> Self {
+        Self::from(astro_func_interface::cartesian_to_equinoctial(
+            &state.position.into(),
+            &state.velocity.into(),
+        ))
+    }
+}
+
 impl From<&EquinoctialElements> for [f64; astro_func_interface::XA_EQNX_SIZE] {
     fn from(eqn: &EquinoctialElements) -> Self {
         let mut xa_eqnx = [0.0; astro_func_interface::XA_EQNX_SIZE];
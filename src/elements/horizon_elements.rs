@@ -0,0 +1,87 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HorizonElements {
+    range: Option<f64>,
+    range_rate: Option<f64>,
+    azimuth: f64,
+    elevation: f64,
+    azimuth_rate: Option<f64>,
+    elevation_rate: Option<f64>,
+}
+
+#[pymethods]
+impl HorizonElements {
+    #[new]
+    pub fn new(azimuth: f64, elevation: f64) -> Self {
+        Self {
+            range: None,
+            range_rate: None,
+            azimuth,
+            elevation,
+            azimuth_rate: None,
+            elevation_rate: None,
+        }
+    }
+
+    #[getter]
+    pub fn get_azimuth(&self) -> f64 {
+        self.azimuth
+    }
+
+    #[getter]
+    pub fn get_elevation(&self) -> f64 {
+        self.elevation
+    }
+
+    #[getter]
+    pub fn get_range(&self) -> Option<f64> {
+        self.range
+    }
+
+    #[getter]
+    pub fn get_range_rate(&self) -> Option<f64> {
+        self.range_rate
+    }
+
+    #[getter]
+    pub fn get_azimuth_rate(&self) -> Option<f64> {
+        self.azimuth_rate
+    }
+
+    #[getter]
+    pub fn get_elevation_rate(&self) -> Option<f64> {
+        self.elevation_rate
+    }
+
+    #[setter]
+    pub fn set_azimuth(&mut self, azimuth: f64) {
+        self.azimuth = azimuth;
+    }
+
+    #[setter]
+    pub fn set_elevation(&mut self, elevation: f64) {
+        self.elevation = elevation;
+    }
+
+    #[setter]
+    pub fn set_range(&mut self, range: Option<f64>) {
+        self.range = range;
+    }
+
+    #[setter]
+    pub fn set_range_rate(&mut self, range_rate: Option<f64>) {
+        self.range_rate = range_rate;
+    }
+
+    #[setter]
+    pub fn set_azimuth_rate(&mut self, azimuth_rate: Option<f64>) {
+        self.azimuth_rate = azimuth_rate;
+    }
+
+    #[setter]
+    pub fn set_elevation_rate(&mut self, elevation_rate: Option<f64>) {
+        self.elevation_rate = elevation_rate;
+    }
+}
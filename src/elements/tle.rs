@@ -1,15 +1,55 @@
-use super::{CartesianState, EquinoctialElements, KeplerianElements, KeplerianState};
-use crate::bodies::Satellite;
+use super::{CartesianState, CartesianVector, EquinoctialElements, KeplerianState};
 use crate::enums::{Classification, KeplerianType, ReferenceFrame};
-use crate::estimation::Observation;
 use crate::propagation::{ForceProperties, SGP4Output};
-use crate::saal::{sgp4_prop_interface, tle_interface, GetSetString};
-use crate::time::Epoch;
+use crate::saal::astro_func_interface::{XA_EQNX_AF, XA_EQNX_AG, XA_EQNX_CHI, XA_EQNX_L, XA_EQNX_N, XA_EQNX_PSI};
+use crate::saal::{astro_func_interface, env_const_interface, sgp4_prop_interface, tle_interface, GetSetString};
+use crate::time::{Epoch, TimeSpan};
 use nalgebra::{DMatrix, DVector};
 use pyo3::prelude::*;
+use std::f64::consts::TAU;
 use std::str::FromStr;
 
-const DEFAULT_EPSILONS: [f64; 8] = [1e-6, 1e-6, 1e-6, 1e-6, 1e-8, 1e-6, 1e-4, 1e-4];
+const DRAG_EPSILON: f64 = 1e-4;
+const SRP_EPSILON: f64 = 1e-4;
+
+// Copies of an element set with one force term nudged each, loaded for propagation for as long as they are held
+#[derive(Debug)]
+pub struct ForcePerturbations {
+    perturbations: Vec<(TLE, f64)>,
+}
+
+impl ForcePerturbations {
+    fn new(perturbations: Vec<(TLE, f64)>) -> Result<Self, String> {
+        let mut loaded = Self {
+            perturbations: Vec::with_capacity(perturbations.len()),
+        };
+        for (tle, epsilon) in perturbations {
+            sgp4_prop_interface::load_key(tle.get_key())?;
+            loaded.perturbations.push((tle, epsilon));
+        }
+        Ok(loaded)
+    }
+
+    pub fn len(&self) -> usize {
+        self.perturbations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.perturbations.is_empty()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &(TLE, f64)> {
+        self.perturbations.iter()
+    }
+}
+
+impl Drop for ForcePerturbations {
+    fn drop(&mut self) {
+        for (tle, _) in &self.perturbations {
+            sgp4_prop_interface::remove_key(tle.get_key()).unwrap();
+        }
+    }
+}
 
 #[pyclass]
 #[derive(Debug, PartialEq)]
@@ -97,98 +137,175 @@ impl TLE {
         }
     }
 
-    pub fn get_stm(&self, epoch: Epoch, use_drag: bool, use_srp: bool) -> Result<DMatrix<f64>, String> {
-        let mut n = 6;
+    pub fn get_cartesian_state_at_epoch(&self, epoch: Epoch) -> Result<CartesianState, String> {
+        let (pos, vel) = match sgp4_prop_interface::get_posvel_at_ds50(self.key, epoch.days_since_1950) {
+            Ok(posvel) => posvel,
+            Err(_) => {
+                sgp4_prop_interface::load_key(self.key)?;
+                let posvel = sgp4_prop_interface::get_posvel_at_ds50(self.key, epoch.days_since_1950);
+                sgp4_prop_interface::remove_key(self.key)?;
+                posvel?
+            }
+        };
+        Ok(CartesianState::new(
+            epoch,
+            CartesianVector::from(pos),
+            CartesianVector::from(vel),
+            ReferenceFrame::TEME,
+        ))
+    }
+
+    fn get_perturbed_tles(&self, use_drag: bool, use_srp: bool, use_mean_motion_dot: bool) -> Vec<(TLE, f64)> {
+        let mut perturbations = Vec::new();
         if use_drag {
-            n += 1;
+            let mut perturbed_forces = self.force_properties;
+            perturbed_forces.set_drag_coefficient(self.force_properties.get_drag_coefficient() + DRAG_EPSILON);
+            perturbations.push((self.new_with_force_properties(perturbed_forces), DRAG_EPSILON));
         }
+
+        // SGP4 element sets without an SRP term carry the mean motion derivative instead
         if use_srp {
-            n += 1;
+            let mut perturbed_forces = self.force_properties;
+            if use_mean_motion_dot
+                && self.get_type() != KeplerianType::MeanBrouwerXP
+                && self.get_type() != KeplerianType::Osculating
+            {
+                perturbed_forces.set_mean_motion_dot(self.force_properties.get_mean_motion_dot() + SRP_EPSILON);
+            } else {
+                perturbed_forces.set_srp_coefficient(self.force_properties.get_srp_coefficient() + SRP_EPSILON);
+            }
+            perturbations.push((self.new_with_force_properties(perturbed_forces), SRP_EPSILON));
         }
-        let mut stm: DMatrix<f64> = DMatrix::zeros(n, n);
+        perturbations
+    }
 
-        let state_0 = self.get_keplerian_state();
-        let elements_0 = self.get_equinoctial_elements_at_epoch(self.get_epoch());
-        let forces_0 = self.get_force_properties();
-        let tle_0 = self.clone();
-        let reference_elements = tle_0.get_equinoctial_elements_at_epoch(epoch);
+    // Built once per iteration so every observation shares the same loaded element sets
+    pub fn get_force_perturbations(&self, use_drag: bool, use_srp: bool) -> Result<ForcePerturbations, String> {
+        ForcePerturbations::new(self.get_perturbed_tles(use_drag, use_srp, false))
+    }
 
-        // Perturb orbital elements
-        for i in 0..6 {
-            let mut xa_eqnx = elements_0;
-            let epsilon = DEFAULT_EPSILONS[i];
-            xa_eqnx[i] += epsilon;
-            let perturbed_elements = KeplerianElements::from(&xa_eqnx);
-            let perturbed_state = KeplerianState::new(
-                self.get_epoch(),
-                perturbed_elements,
-                ReferenceFrame::TEME,
-                self.get_type(),
-            );
-            let tle = TLE::new(
-                self.satellite_id,
-                self.name.clone(),
-                self.classification,
-                self.designator.clone(),
-                perturbed_state,
-                forces_0,
-            );
-
-            let perturbed_els = tle.get_equinoctial_elements_at_epoch(epoch);
-            for j in 0..6 {
-                stm[(j, i)] = (perturbed_els[j] - reference_elements[j]) / epsilon;
-            }
-        }
+    fn new_with_force_properties(&self, force_properties: ForceProperties) -> Self {
+        TLE::new(
+            self.satellite_id,
+            self.name.clone(),
+            self.classification,
+            self.designator.clone(),
+            self.keplerian_state,
+            force_properties,
+        )
+    }
 
-        let mut current_col = 6;
-        // Perturb drag
-        if use_drag {
-            let mut perturbed_forces = forces_0;
-            let epsilon = DEFAULT_EPSILONS[6];
-            perturbed_forces.set_drag_coefficient(forces_0.get_drag_coefficient() + epsilon);
-            let tle = TLE::new(
-                self.satellite_id,
-                self.name.clone(),
-                self.classification,
-                self.designator.clone(),
-                state_0,
-                perturbed_forces,
-            );
-
-            let perturbed_els = tle.get_equinoctial_elements_at_epoch(epoch);
-            for j in 0..6 {
-                stm[(j, current_col)] = (perturbed_els[j] - reference_elements[j]) / epsilon;
+    pub fn get_stm(&self, epoch: Epoch, use_drag: bool, use_srp: bool) -> Result<DMatrix<f64>, String> {
+        let perturbations = self.get_perturbed_tles(use_drag, use_srp, true);
+        let n = 6 + perturbations.len();
+        let mut stm: DMatrix<f64> = DMatrix::identity(n, n);
+        stm.view_mut((0, 0), (6, 6))
+            .copy_from(&self.get_mean_element_stm(epoch - self.get_epoch()));
+
+        // Force model terms have no closed form under SGP4 and are differenced instead
+        if !perturbations.is_empty() {
+            let reference_elements = self.get_equinoctial_elements_at_epoch(epoch);
+            for (col, (tle, epsilon)) in perturbations.iter().enumerate() {
+                let perturbed_els = tle.get_equinoctial_elements_at_epoch(epoch);
+                for j in 0..6 {
+                    stm[(j, 6 + col)] = (perturbed_els[j] - reference_elements[j]) / epsilon;
+                }
             }
-            stm[(current_col, current_col)] = 1.0;
-            current_col += 1;
         }
+        Ok(stm)
+    }
 
-        //Perturb SRP or mean motion dot
-        if use_srp {
-            let mut perturbed_forces = forces_0;
-            let epsilon = DEFAULT_EPSILONS[7];
-            if self.get_type() == KeplerianType::MeanBrouwerXP || self.get_type() == KeplerianType::Osculating {
-                perturbed_forces.set_srp_coefficient(forces_0.get_srp_term() + epsilon);
-            } else {
-                perturbed_forces.set_mean_motion_dot(forces_0.get_mean_motion_dot() + epsilon);
+    // Partials of a propagated TEME state with respect to the estimated elements and force terms
+    pub fn get_state_partials(
+        &self,
+        state: &CartesianState,
+        perturbations: &ForcePerturbations,
+    ) -> Result<DMatrix<f64>, String> {
+        let mut partials = DMatrix::zeros(6, 6 + perturbations.len());
+        let cartesian_partials = EquinoctialElements::from(state).get_cartesian_partials();
+        partials
+            .view_mut((0, 0), (6, 6))
+            .copy_from(&(cartesian_partials * self.get_mean_element_stm(state.epoch - self.get_epoch())));
+
+        for (col, (tle, epsilon)) in perturbations.iter().enumerate() {
+            let perturbed_state = tle.get_cartesian_state_at_epoch(state.epoch)?;
+            for i in 0..3 {
+                partials[(i, 6 + col)] = (perturbed_state.position[i] - state.position[i]) / epsilon;
+                partials[(i + 3, 6 + col)] = (perturbed_state.velocity[i] - state.velocity[i]) / epsilon;
             }
+        }
+        Ok(partials)
+    }
+
+    // Mean elements drift with the mean motion and the J2 secular rates of the node, perigee and mean anomaly, which
+    // rotate (af, ag) by the rate of the longitude of perigee and (chi, psi) by the rate of the node
+    fn get_mean_element_stm(&self, elapsed: TimeSpan) -> DMatrix<f64> {
+        let elements = self.get_equinoctial_elements_at_epoch(self.get_epoch());
+        let (a_f, a_g, chi, psi) = (
+            elements[XA_EQNX_AF],
+            elements[XA_EQNX_AG],
+            elements[XA_EQNX_CHI],
+            elements[XA_EQNX_PSI],
+        );
+        let mean_motion = elements[XA_EQNX_N];
+        let days = elapsed.in_days();
+
+        let eta_squared = 1.0 - a_f * a_f - a_g * a_g;
+        let semi_latus_rectum = astro_func_interface::mean_motion_to_sma(mean_motion) * eta_squared;
+        let radius_ratio = env_const_interface::get_earth_radius() / semi_latus_rectum;
+        let rate_scale = 0.75 * env_const_interface::get_j2() * radius_ratio * radius_ratio * mean_motion * TAU;
+        let tan_squared = chi * chi + psi * psi;
+        let cos_i = (1.0 - tan_squared) / (1.0 + tan_squared);
+        let d_cos_i = -4.0 / ((1.0 + tan_squared) * (1.0 + tan_squared));
+
+        // Rates in radians per day, which scale as N^(7/3) and as a power of 1 - e^2, and their partials with
+        // respect to (af, ag, chi, psi, L, N)
+        let get_rate_partials = |rate: f64, d_rate_d_cos_i: f64, eta_power: f64| {
+            let mut partials = [0.0; 6];
+            partials[XA_EQNX_AF] = -rate * eta_power * a_f / eta_squared;
+            partials[XA_EQNX_AG] = -rate * eta_power * a_g / eta_squared;
+            partials[XA_EQNX_CHI] = d_rate_d_cos_i * d_cos_i * chi;
+            partials[XA_EQNX_PSI] = d_rate_d_cos_i * d_cos_i * psi;
+            partials[XA_EQNX_N] = rate * 7.0 / (3.0 * mean_motion);
+            partials
+        };
+        let node_rate = -2.0 * rate_scale * cos_i;
+        let node_partials = get_rate_partials(node_rate, -2.0 * rate_scale, -4.0);
+        let perigee_longitude_rate = rate_scale * (5.0 * cos_i * cos_i - 2.0 * cos_i - 1.0);
+        let perigee_longitude_partials =
+            get_rate_partials(perigee_longitude_rate, rate_scale * (10.0 * cos_i - 2.0), -4.0);
+        let eta = eta_squared.sqrt();
+        let anomaly_partials = get_rate_partials(
+            rate_scale * eta * (3.0 * cos_i * cos_i - 1.0),
+            rate_scale * eta * 6.0 * cos_i,
+            -3.0,
+        );
 
-            let tle = TLE::new(
-                self.satellite_id,
-                self.name.clone(),
-                self.classification,
-                self.designator.clone(),
-                state_0,
-                perturbed_forces,
-            );
-
-            let perturbed_els = tle.get_equinoctial_elements_at_epoch(epoch);
-            for j in 0..6 {
-                stm[(j, current_col)] = (perturbed_els[j] - reference_elements[j]) / epsilon;
-            }
-            stm[(current_col, current_col)] = 1.0;
+        let (sin_perigee, cos_perigee) = (perigee_longitude_rate * days).sin_cos();
+        let (sin_node, cos_node) = (node_rate * days).sin_cos();
+        let a_f_t = a_f * cos_perigee - a_g * sin_perigee;
+        let a_g_t = a_g * cos_perigee + a_f * sin_perigee;
+        let chi_t = chi * cos_node + psi * sin_node;
+        let psi_t = psi * cos_node - chi * sin_node;
+
+        let mut stm = DMatrix::identity(6, 6);
+        stm[(XA_EQNX_AF, XA_EQNX_AF)] = cos_perigee;
+        stm[(XA_EQNX_AF, XA_EQNX_AG)] = -sin_perigee;
+        stm[(XA_EQNX_AG, XA_EQNX_AF)] = sin_perigee;
+        stm[(XA_EQNX_AG, XA_EQNX_AG)] = cos_perigee;
+        stm[(XA_EQNX_CHI, XA_EQNX_CHI)] = cos_node;
+        stm[(XA_EQNX_CHI, XA_EQNX_PSI)] = sin_node;
+        stm[(XA_EQNX_PSI, XA_EQNX_CHI)] = -sin_node;
+        stm[(XA_EQNX_PSI, XA_EQNX_PSI)] = cos_node;
+        stm[(XA_EQNX_L, XA_EQNX_N)] = 360.0 * days;
+        for j in 0..6 {
+            stm[(XA_EQNX_AF, j)] -= a_g_t * days * perigee_longitude_partials[j];
+            stm[(XA_EQNX_AG, j)] += a_f_t * days * perigee_longitude_partials[j];
+            stm[(XA_EQNX_CHI, j)] += psi_t * days * node_partials[j];
+            stm[(XA_EQNX_PSI, j)] -= chi_t * days * node_partials[j];
+            stm[(XA_EQNX_L, j)] += days * (perigee_longitude_partials[j] + anomaly_partials[j]).to_degrees();
         }
-        Ok(stm)
+        stm
     }
 
     pub fn new_with_delta_x(&self, delta_x: &DVector<f64>, use_drag: bool, use_srp: bool) -> Self {
@@ -221,104 +338,6 @@ impl TLE {
         )
     }
 
-    pub fn get_jacobian(&self, ob: &Observation, use_drag: bool, use_srp: bool) -> Result<DMatrix<f64>, String> {
-        // Build the reference satellite
-        let ref_sat = Satellite::from_tle(self.clone());
-
-        // Get the predicted measurements for the reference satellite
-        let h_ref = ob.get_predicted_vector(&ref_sat)?;
-
-        let m = h_ref.len();
-        let mut n = 6;
-        if use_drag {
-            n += 1;
-        }
-        if use_srp {
-            n += 1;
-        }
-        let mut jac = DMatrix::<f64>::zeros(m, n);
-
-        // Get the reference keplerian elements as an array
-        let ref_state = self.get_keplerian_state();
-        let ref_elements = self.get_equinoctial_elements_at_epoch(self.get_epoch());
-
-        for j in 0..6 {
-            let mut perturbed_elements = ref_elements;
-            let epsilon = DEFAULT_EPSILONS[j];
-            perturbed_elements[j] += epsilon;
-            let perturbed_kep = KeplerianElements::from(&perturbed_elements);
-            let perturbed_state = KeplerianState::new(
-                ref_state.get_epoch(),
-                perturbed_kep,
-                ref_state.get_frame(),
-                ref_state.get_type(),
-            );
-            let perturbed_tle = TLE::new(
-                self.satellite_id,
-                self.name.clone(),
-                self.classification,
-                self.designator.clone(),
-                perturbed_state,
-                self.force_properties,
-            );
-            let perturbed_sat = Satellite::from_tle(perturbed_tle);
-            let h_p = ob.get_predicted_vector(&perturbed_sat)?;
-
-            // Compute the j-th column as (h_p - h_ref) / epsilon
-            for i in 0..m {
-                jac[(i, j)] = (h_p[i] - h_ref[i]) / epsilon;
-            }
-        }
-
-        let mut next_row = 6;
-        // drag
-        if use_drag {
-            let mut perturbed_forces = self.force_properties;
-            let epsilon = DEFAULT_EPSILONS[6];
-            perturbed_forces.set_drag_coefficient(perturbed_forces.get_drag_coefficient() + epsilon);
-            let perturbed_tle = TLE::new(
-                self.satellite_id,
-                self.name.clone(),
-                self.classification,
-                self.designator.clone(),
-                ref_state,
-                perturbed_forces,
-            );
-            let perturbed_sat = Satellite::from_tle(perturbed_tle);
-            let h_p = ob.get_predicted_vector(&perturbed_sat)?;
-
-            // Compute the j-th column as (h_p - h_ref) / epsilon
-            for i in 0..m {
-                jac[(i, next_row)] = (h_p[i] - h_ref[i]) / epsilon;
-            }
-            next_row += 1;
-        }
-
-        //srp or mean motion dot
-        if use_srp {
-            let mut perturbed_forces = self.force_properties;
-            let epsilon = DEFAULT_EPSILONS[7];
-            perturbed_forces.set_srp_coefficient(perturbed_forces.get_srp_coefficient() + epsilon);
-            let perturbed_tle = TLE::new(
-                self.satellite_id,
-                self.name.clone(),
-                self.classification,
-                self.designator.clone(),
-                ref_state,
-                perturbed_forces,
-            );
-            let perturbed_sat = Satellite::from_tle(perturbed_tle);
-            let h_p = ob.get_predicted_vector(&perturbed_sat)?;
-
-            // Compute the j-th column as (h_p - h_ref) / epsilon
-            for i in 0..m {
-                jac[(i, next_row)] = (h_p[i] - h_ref[i]) / epsilon;
-            }
-        }
-
-        Ok(jac)
-    }
-
     pub fn get_xa_tle(&self) -> [f64; tle_interface::XA_TLE_SIZE] {
         let mut xa_tle = [0.0; tle_interface::XA_TLE_SIZE];
         xa_tle[tle_interface::XA_TLE_SATNUM] = self.satellite_id as f64;
@@ -514,8 +533,10 @@ mod tests {
     use crate::enums::{Classification, KeplerianType, ReferenceFrame, TimeSystem};
     use crate::propagation::ForceProperties;
     use crate::saal::astro_func_interface;
-    use crate::time::Epoch;
+    use crate::saal::astro_func_interface::{XA_EQNX_CHI, XA_EQNX_L, XA_EQNX_N, XA_EQNX_PSI};
+    use crate::time::{Epoch, TimeSpan};
     use approx::assert_abs_diff_eq;
+    use nalgebra::DVector;
 
     const SGP_LINE_1: &str = "1 25544U 98067A   20200.51605324 +.00000884  00000 0  22898-4 0 0999";
     const SGP_LINE_2: &str = "2 25544  51.6443  93.0000 0001400  84.0000 276.0000 15.4930007023660";
@@ -655,4 +676,23 @@ mod tests {
         assert_ne!(tle.get_key(), 0);
         tle.remove_from_memory();
     }
+    #[test]
+    fn test_mean_element_stm() {
+        // Compare the analytic J2 drift against SGP4 propagation of nudged elements over three days
+        let tle = sgp_tle_from_lines();
+        let epoch = tle.get_epoch() + TimeSpan::from_days(3.0);
+        let stm = tle.get_stm(epoch, false, false).unwrap();
+        let reference = tle.get_equinoctial_elements_at_epoch(epoch);
+        for (j, step) in [(XA_EQNX_CHI, 1e-6), (XA_EQNX_PSI, 1e-6), (XA_EQNX_N, 1e-7)] {
+            let mut delta_x = DVector::zeros(6);
+            delta_x[j] = step;
+            let perturbed = tle
+                .new_with_delta_x(&delta_x, false, false)
+                .get_equinoctial_elements_at_epoch(epoch);
+            for i in [XA_EQNX_CHI, XA_EQNX_PSI, XA_EQNX_L] {
+                let differenced = (perturbed[i] - reference[i]) / step;
+                assert_abs_diff_eq!(stm[(i, j)], differenced, epsilon = 1e-2 * differenced.abs().max(1.0));
+            }
+        }
+    }
 }
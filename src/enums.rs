@@ -1,19 +1,25 @@
 mod classification;
 mod covariance_type;
+mod earth_rotation_model;
 mod equinox_type;
 mod geodetic_model;
 mod keplerian_type;
+mod measurement_type;
 mod reference_frame;
 mod saal_key_mode;
+mod solver_type;
 mod time_system;
 
 pub use classification::Classification;
 pub use covariance_type::CovarianceType;
+pub use earth_rotation_model::EarthRotationModel;
 pub use equinox_type::EquinoxType;
 pub use geodetic_model::GeodeticModel;
 pub use keplerian_type::KeplerianType;
+pub use measurement_type::MeasurementType;
 pub use reference_frame::ReferenceFrame;
 pub use saal_key_mode::SAALKeyMode;
+pub use solver_type::SolverType;
 pub use time_system::TimeSystem;
 
 use pyo3::prelude::*;
@@ -29,6 +35,9 @@ pub fn register_enums(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     enums.add_class::<EquinoxType>()?;
     enums.add_class::<GeodeticModel>()?;
     enums.add_class::<CovarianceType>()?;
+    enums.add_class::<SolverType>()?;
+    enums.add_class::<MeasurementType>()?;
+    enums.add_class::<EarthRotationModel>()?;
     py_run!(
         parent_module.py(),
         enums,
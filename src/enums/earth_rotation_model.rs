@@ -0,0 +1,48 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EarthRotationModel {
+    FK4,
+    FK5,
+    GMST82,
+    GAST82,
+    GMST06,
+    GAST06,
+    ERA,
+}
+
+#[pymethods]
+impl EarthRotationModel {
+    #[getter]
+    fn value(&self) -> &str {
+        match self {
+            EarthRotationModel::FK4 => "FK4",
+            EarthRotationModel::FK5 => "FK5",
+            EarthRotationModel::GMST82 => "GMST82",
+            EarthRotationModel::GAST82 => "GAST82",
+            EarthRotationModel::GMST06 => "GMST06",
+            EarthRotationModel::GAST06 => "GAST06",
+            EarthRotationModel::ERA => "ERA",
+        }
+    }
+
+    fn __repr__(&self) -> &str {
+        match self {
+            EarthRotationModel::FK4 => "EarthRotationModel.FK4",
+            EarthRotationModel::FK5 => "EarthRotationModel.FK5",
+            EarthRotationModel::GMST82 => "EarthRotationModel.GMST82",
+            EarthRotationModel::GAST82 => "EarthRotationModel.GAST82",
+            EarthRotationModel::GMST06 => "EarthRotationModel.GMST06",
+            EarthRotationModel::GAST06 => "EarthRotationModel.GAST06",
+            EarthRotationModel::ERA => "EarthRotationModel.ERA",
+        }
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+    fn __ne__(&self, other: &Self) -> bool {
+        self != other
+    }
+}
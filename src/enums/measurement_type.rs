@@ -0,0 +1,58 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeasurementType {
+    RightAscension,
+    Declination,
+    Range,
+    RangeRate,
+    RightAscensionRate,
+    DeclinationRate,
+    Azimuth,
+    Elevation,
+    AzimuthRate,
+    ElevationRate,
+}
+
+#[pymethods]
+impl MeasurementType {
+    #[getter]
+    fn get_value(&self) -> &str {
+        match self {
+            MeasurementType::RightAscension => "Right Ascension",
+            MeasurementType::Declination => "Declination",
+            MeasurementType::Range => "Range",
+            MeasurementType::RangeRate => "Range Rate",
+            MeasurementType::RightAscensionRate => "Right Ascension Rate",
+            MeasurementType::DeclinationRate => "Declination Rate",
+            MeasurementType::Azimuth => "Azimuth",
+            MeasurementType::Elevation => "Elevation",
+            MeasurementType::AzimuthRate => "Azimuth Rate",
+            MeasurementType::ElevationRate => "Elevation Rate",
+        }
+    }
+
+    fn __repr__(&self) -> &str {
+        match self {
+            MeasurementType::RightAscension => "MeasurementType.RightAscension",
+            MeasurementType::Declination => "MeasurementType.Declination",
+            MeasurementType::Range => "MeasurementType.Range",
+            MeasurementType::RangeRate => "MeasurementType.RangeRate",
+            MeasurementType::RightAscensionRate => "MeasurementType.RightAscensionRate",
+            MeasurementType::DeclinationRate => "MeasurementType.DeclinationRate",
+            MeasurementType::Azimuth => "MeasurementType.Azimuth",
+            MeasurementType::Elevation => "MeasurementType.Elevation",
+            MeasurementType::AzimuthRate => "MeasurementType.AzimuthRate",
+            MeasurementType::ElevationRate => "MeasurementType.ElevationRate",
+        }
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __ne__(&self, other: &Self) -> bool {
+        self != other
+    }
+}
@@ -0,0 +1,40 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolverType {
+    GaussNewton,
+    LevenbergMarquardt,
+    QR,
+    SVD,
+}
+
+#[pymethods]
+impl SolverType {
+    #[getter]
+    fn get_value(&self) -> &str {
+        match self {
+            SolverType::GaussNewton => "Gauss-Newton",
+            SolverType::LevenbergMarquardt => "Levenberg-Marquardt",
+            SolverType::QR => "QR",
+            SolverType::SVD => "SVD",
+        }
+    }
+
+    fn __repr__(&self) -> &str {
+        match self {
+            SolverType::GaussNewton => "SolverType.GaussNewton",
+            SolverType::LevenbergMarquardt => "SolverType.LevenbergMarquardt",
+            SolverType::QR => "SolverType.QR",
+            SolverType::SVD => "SolverType.SVD",
+        }
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __ne__(&self, other: &Self) -> bool {
+        self != other
+    }
+}
@@ -8,6 +8,9 @@ pub enum TimeSystem {
     TAI,
     UT1,
     TT,
+    GPS,
+    TDB,
+    TCG,
 }
 
 impl Display for TimeSystem {
@@ -17,6 +20,9 @@ impl Display for TimeSystem {
             TimeSystem::TAI => write!(f, "TAI"),
             TimeSystem::UT1 => write!(f, "UT1"),
             TimeSystem::TT => write!(f, "TT"),
+            TimeSystem::GPS => write!(f, "GPS"),
+            TimeSystem::TDB => write!(f, "TDB"),
+            TimeSystem::TCG => write!(f, "TCG"),
         }
     }
 }
@@ -30,6 +36,9 @@ impl TimeSystem {
             TimeSystem::TAI => "TAI",
             TimeSystem::UT1 => "UT1",
             TimeSystem::TT => "TT",
+            TimeSystem::GPS => "GPS",
+            TimeSystem::TDB => "TDB",
+            TimeSystem::TCG => "TCG",
         }
     }
 
@@ -39,6 +48,9 @@ impl TimeSystem {
             TimeSystem::TAI => "TimeSystem.TAI",
             TimeSystem::UT1 => "TimeSystem.UT1",
             TimeSystem::TT => "TimeSystem.TT",
+            TimeSystem::GPS => "TimeSystem.GPS",
+            TimeSystem::TDB => "TimeSystem.TDB",
+            TimeSystem::TCG => "TimeSystem.TCG",
         }
     }
     fn __eq__(&self, other: &Self) -> bool {
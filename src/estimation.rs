@@ -1,12 +1,28 @@
 mod batch_least_squares;
+mod correlation_candidate;
+mod correlation_report;
 mod covariance;
+mod maneuver_detector;
 mod observation;
 mod observation_residual;
+mod observation_simulator;
+mod sensor_bias;
+mod tle_fitter;
+mod tracklet;
+mod tracklet_builder;
 
 pub use batch_least_squares::BatchLeastSquares;
+pub use correlation_candidate::CorrelationCandidate;
+pub use correlation_report::CorrelationReport;
 pub use covariance::Covariance;
+pub use maneuver_detector::ManeuverDetector;
 pub use observation::Observation;
 pub use observation_residual::ObservationResidual;
+pub use observation_simulator::ObservationSimulator;
+pub use sensor_bias::SensorBias;
+pub use tle_fitter::TLEFitter;
+pub use tracklet::Tracklet;
+pub use tracklet_builder::TrackletBuilder;
 
 use pyo3::prelude::*;
 use pyo3::py_run;
@@ -17,6 +33,14 @@ pub fn register_estimation(parent_module: &Bound<'_, PyModule>) -> PyResult<()>
     estimation.add_class::<ObservationResidual>()?;
     estimation.add_class::<BatchLeastSquares>()?;
     estimation.add_class::<Covariance>()?;
+    estimation.add_class::<SensorBias>()?;
+    estimation.add_class::<CorrelationCandidate>()?;
+    estimation.add_class::<CorrelationReport>()?;
+    estimation.add_class::<Tracklet>()?;
+    estimation.add_class::<TrackletBuilder>()?;
+    estimation.add_class::<ObservationSimulator>()?;
+    estimation.add_class::<ManeuverDetector>()?;
+    estimation.add_class::<TLEFitter>()?;
     py_run!(
         parent_module.py(),
         estimation,
@@ -1,17 +1,103 @@
-use super::{Covariance, Observation, ObservationResidual};
+use super::observation::SensorKey;
+use super::{Covariance, Observation, ObservationResidual, SensorBias};
 use crate::bodies::Satellite;
 use crate::configs;
-use crate::enums::{CovarianceType, KeplerianType};
-use crate::time::Epoch;
+use crate::elements::{CartesianVector, ForcePerturbations};
+use crate::enums::{CovarianceType, KeplerianType, MeasurementType, SolverType};
+use crate::events::ManeuverEvent;
+use crate::saal::astro_func_interface::{XA_EQNX_AF, XA_EQNX_AG, XA_EQNX_L, XA_EQNX_N};
+use crate::time::{Epoch, TimeSpan};
 use nalgebra::{DMatrix, DVector};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use rayon::prelude::*;
 
 pub const DEFAULT_MAX_ITERATIONS: usize = 20;
+pub const DEFAULT_FIRST_PASS_EDIT_MULTIPLIER: f64 = 3.0;
+pub const DEFAULT_RMS_TOLERANCE: f64 = 1e-3;
+pub const DEFAULT_MAX_DIVERGENT_ITERATIONS: usize = 3;
+pub const DEFAULT_INITIAL_DAMPING: f64 = 1e-3;
+const DAMPING_FACTOR: f64 = 10.0;
+const MIN_DAMPING: f64 = 1e-12;
+const MAX_DAMPING: f64 = 1e10;
+const SVD_RELATIVE_TOLERANCE: f64 = 1e-12;
+const MANEUVER_EPOCH_STEP_SECONDS: f64 = 1.0;
+const BIAS_MEASUREMENT_TYPES: [MeasurementType; 6] = [
+    MeasurementType::RightAscension,
+    MeasurementType::Declination,
+    MeasurementType::Azimuth,
+    MeasurementType::Elevation,
+    MeasurementType::Range,
+    MeasurementType::RangeRate,
+];
+
+type BiasParameter = (SensorKey, MeasurementType);
+type MeasurementsAndWeights = (DVector<f64>, DVector<f64>, Vec<usize>);
+type ImpulsiveManeuver = (Epoch, CartesianVector);
+type CorrectedEstimate = (Satellite, Vec<Observation>, Option<ImpulsiveManeuver>);
+
+// Trajectory followed before the maneuver, recovered by removing the impulse from the post-maneuver estimate
+fn get_pre_maneuver_estimate(
+    satellite: &Satellite,
+    maneuver: Option<&ImpulsiveManeuver>,
+) -> Result<Option<(Epoch, Satellite)>, String> {
+    match maneuver {
+        Some((epoch, delta_v)) => Ok(Some((*epoch, satellite.new_with_impulse(*epoch, *delta_v * -1.0)?))),
+        None => Ok(None),
+    }
+}
+
+fn get_trajectory<'a>(
+    satellite: &'a Satellite,
+    pre_maneuver: &'a Option<(Epoch, Satellite)>,
+    epoch: Epoch,
+) -> &'a Satellite {
+    match pre_maneuver {
+        Some((maneuver_epoch, pre_maneuver)) if epoch < *maneuver_epoch => pre_maneuver,
+        _ => satellite,
+    }
+}
+
+// Linearization of the pre-maneuver arc about the post-maneuver estimate
+struct PreManeuverPartials {
+    epoch: Epoch,
+    satellite: Satellite,
+    force_perturbations: ForcePerturbations,
+    shifted_satellite: Option<Satellite>,
+    element_stm: DMatrix<f64>,
+    impulse_partials: DMatrix<f64>,
+}
+
+impl PreManeuverPartials {
+    fn get_jacobians(&self, ob: &Observation) -> Result<(DMatrix<f64>, DMatrix<f64>), String> {
+        // Observations before the maneuver see the elements at the maneuver epoch less the impulse
+        let mut state_jacobian = self.satellite.get_jacobian(ob, &self.force_perturbations)?;
+        let orbit_jacobian = state_jacobian.columns(0, 6).into_owned();
+        state_jacobian
+            .columns_mut(0, 6)
+            .copy_from(&(&orbit_jacobian * &self.element_stm));
+
+        let dim = state_jacobian.nrows();
+        let mut maneuver_jacobian = DMatrix::zeros(dim, 3 + self.shifted_satellite.is_some() as usize);
+        maneuver_jacobian
+            .columns_mut(0, 3)
+            .copy_from(&(-&orbit_jacobian * &self.impulse_partials));
+        if let Some(shifted_satellite) = self.shifted_satellite.as_ref() {
+            let predicted = ob.get_predicted_vector(&self.satellite)?;
+            let shifted = ob.get_predicted_vector(shifted_satellite)?;
+            for (i, difference) in ob.get_measurement_differences(&shifted, &predicted).iter().enumerate() {
+                maneuver_jacobian[(i, 3)] = difference / MANEUVER_EPOCH_STEP_SECONDS;
+            }
+        }
+        Ok((state_jacobian, maneuver_jacobian))
+    }
+}
 
 #[pyclass]
 #[derive(Debug, Clone, PartialEq)]
 pub struct BatchLeastSquares {
     obs: Vec<Observation>,
+    a_priori_obs: Vec<Observation>,
     a_priori: Satellite,
     use_drag: bool,
     use_srp: bool,
@@ -22,6 +108,26 @@ pub struct BatchLeastSquares {
     weighted_rms: Option<f64>,
     converged: bool,
     output_keplerian_type: KeplerianType,
+    a_priori_covariance: Option<Covariance>,
+    a_priori_information: Option<(DVector<f64>, DMatrix<f64>)>,
+    consider_drag_sigma: Option<f64>,
+    consider_srp_sigma: Option<f64>,
+    formal_covariance: Option<DMatrix<f64>>,
+    edit_sigma: Option<f64>,
+    first_pass_edit_multiplier: f64,
+    accepted: Vec<bool>,
+    rejection_history: Vec<Vec<usize>>,
+    solver_type: SolverType,
+    rms_tolerance: f64,
+    correction_tolerance: Option<f64>,
+    max_divergent_iterations: usize,
+    divergent_iterations: usize,
+    initial_damping: f64,
+    damping: f64,
+    use_biases: bool,
+    a_priori_maneuver: Option<ImpulsiveManeuver>,
+    maneuver: Option<ImpulsiveManeuver>,
+    estimate_maneuver_epoch: bool,
 }
 
 #[pymethods]
@@ -31,7 +137,9 @@ impl BatchLeastSquares {
         let output_keplerian_type = a_priori.get_keplerian_state().unwrap().get_type();
         let a_priori = a_priori.clone();
         let current_estimate = a_priori.clone();
+        let accepted = vec![true; obs.len()];
         Self {
+            a_priori_obs: obs.clone(),
             obs,
             a_priori,
             use_drag: false,
@@ -43,6 +151,26 @@ impl BatchLeastSquares {
             weighted_rms: None,
             converged: false,
             output_keplerian_type,
+            a_priori_covariance: None,
+            a_priori_information: None,
+            consider_drag_sigma: None,
+            consider_srp_sigma: None,
+            formal_covariance: None,
+            edit_sigma: None,
+            first_pass_edit_multiplier: DEFAULT_FIRST_PASS_EDIT_MULTIPLIER,
+            accepted,
+            rejection_history: Vec::new(),
+            solver_type: SolverType::GaussNewton,
+            rms_tolerance: DEFAULT_RMS_TOLERANCE,
+            correction_tolerance: None,
+            max_divergent_iterations: DEFAULT_MAX_DIVERGENT_ITERATIONS,
+            divergent_iterations: 0,
+            initial_damping: DEFAULT_INITIAL_DAMPING,
+            damping: DEFAULT_INITIAL_DAMPING,
+            use_biases: false,
+            a_priori_maneuver: None,
+            maneuver: None,
+            estimate_maneuver_epoch: false,
         }
     }
 
@@ -52,14 +180,10 @@ impl BatchLeastSquares {
             Ok(_) => {}
             Err(e) => return Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
         }
-        self.current_estimate =
-            match self
-                .current_estimate
-                .new_with_delta_x(self.delta_x.as_ref().unwrap(), self.use_drag, self.use_srp)
-            {
-                Ok(new_estimate) => new_estimate,
-                Err(e) => return Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
-            };
+        (self.current_estimate, self.obs, self.maneuver) = match self.apply_correction(self.delta_x.as_ref().unwrap()) {
+            Ok(corrected) => corrected,
+            Err(e) => return Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
+        };
 
         Ok(())
     }
@@ -76,12 +200,12 @@ impl BatchLeastSquares {
     }
 
     #[getter]
-    fn get_converged(&self) -> bool {
+    pub fn get_converged(&self) -> bool {
         self.converged
     }
 
     #[getter]
-    fn get_current_estimate(&self) -> Satellite {
+    pub fn get_current_estimate(&self) -> Satellite {
         self.current_estimate.clone()
     }
 
@@ -95,7 +219,21 @@ impl BatchLeastSquares {
         self.converged = false;
         self.delta_x = None;
         self.weighted_rms = None;
+        self.a_priori_information = None;
+        self.formal_covariance = None;
+        self.accepted = vec![true; self.obs.len()];
+        self.rejection_history.clear();
+        self.divergent_iterations = 0;
+        self.damping = self.initial_damping;
         let last_epoch = self.obs.iter().map(|o| o.get_epoch()).max().unwrap();
+        if let Some((maneuver_epoch, _)) = self.maneuver {
+            let first_epoch = self.obs.iter().map(|o| o.get_epoch()).min().unwrap();
+            if maneuver_epoch <= first_epoch || maneuver_epoch > last_epoch {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Maneuver epoch must fall after the first observation and no later than the last observation",
+                ));
+            }
+        }
         self.current_estimate = match self.current_estimate.clone_at_epoch(last_epoch) {
             Ok(satellite) => satellite,
             Err(e) => return Err(pyo3::exceptions::PyRuntimeError::new_err(e)),
@@ -121,12 +259,14 @@ impl BatchLeastSquares {
     #[getter]
     pub fn get_rms(&self) -> Option<f64> {
         let mut range_errors: Vec<f64> = Vec::new();
-        for ob in self.obs.iter() {
-            match ob.get_residual(&self.current_estimate) {
-                Some(residual) => range_errors.push(residual.get_range()),
-                None => return None,
+        for (residual, accepted) in self.get_observation_residuals()?.iter().zip(self.accepted.iter()) {
+            if *accepted {
+                range_errors.push(residual.get_range());
             }
         }
+        if range_errors.is_empty() {
+            return None;
+        }
         let r = DVector::from_vec(range_errors);
         let m = r.len() as f64;
         let rss = (r.transpose() * &r)[(0, 0)];
@@ -141,6 +281,7 @@ impl BatchLeastSquares {
 
     #[setter]
     pub fn set_observations(&mut self, obs: Vec<Observation>) {
+        self.a_priori_obs = obs.clone();
         self.obs = obs;
         self.reset();
     }
@@ -152,16 +293,19 @@ impl BatchLeastSquares {
 
     #[getter]
     pub fn get_residuals(&self) -> Vec<(Epoch, ObservationResidual)> {
-        let mut residuals: Vec<(Epoch, ObservationResidual)> = Vec::new();
-        for ob in self.obs.iter() {
-            match ob.get_residual(&self.current_estimate) {
-                Some(residual) => {
-                    residuals.push((ob.get_epoch(), residual));
-                }
-                None => return Vec::new(),
-            }
+        match self.get_observation_residuals() {
+            Some(residuals) => self
+                .obs
+                .iter()
+                .zip(residuals)
+                .zip(self.accepted.iter())
+                .map(|((ob, mut residual), accepted)| {
+                    residual.set_accepted(*accepted);
+                    (ob.get_epoch(), residual)
+                })
+                .collect(),
+            None => Vec::new(),
         }
-        residuals
     }
 
     #[setter]
@@ -192,33 +336,28 @@ impl BatchLeastSquares {
     }
 
     fn reset(&mut self) {
-        self.current_estimate = Satellite::new(self.a_priori.get_satellite_id());
-        if let Some(name) = self.a_priori.get_name() {
-            self.current_estimate.set_name(name);
+        self.current_estimate = self.get_seeded_estimate();
+        self.maneuver = self.a_priori_maneuver;
+
+        // The estimate is the post-maneuver orbit, so any a priori delta-v is applied to the seed
+        if let Some((maneuver_epoch, delta_v)) = self.a_priori_maneuver {
+            if delta_v.get_magnitude() > 0.0 {
+                if let Ok(post_maneuver) = self.current_estimate.new_with_impulse(maneuver_epoch, delta_v) {
+                    self.current_estimate = post_maneuver;
+                }
+            }
         }
+        self.obs = self.a_priori_obs.clone();
         self.iteration_count = 0;
         self.converged = false;
         self.delta_x = None;
         self.weighted_rms = None;
-
-        let mut force_properties = self.a_priori.get_force_properties();
-
-        // Seed SRP if not already set
-        if self.get_estimate_srp() && force_properties.get_srp_coefficient() == 0.0 {
-            force_properties.set_srp_coefficient(configs::DEFAULT_SRP_TERM);
-            force_properties.set_srp_area(1.0);
-        }
-
-        // Seed drag if not already set
-        if self.get_estimate_drag() && force_properties.get_drag_coefficient() == 0.0 {
-            force_properties.set_drag_coefficient(configs::DEFAULT_DRAG_TERM);
-        }
-        self.current_estimate.set_force_properties(force_properties);
-
-        // Seed orbit state
-        let mut kep_state = self.a_priori.get_keplerian_state().unwrap();
-        kep_state.set_type(self.output_keplerian_type);
-        self.current_estimate.set_keplerian_state(kep_state).unwrap();
+        self.a_priori_information = None;
+        self.formal_covariance = None;
+        self.accepted = vec![true; self.obs.len()];
+        self.rejection_history.clear();
+        self.divergent_iterations = 0;
+        self.damping = self.initial_damping;
 
         // Disable SRP estimation if output type is incompatible
         if self.use_srp
@@ -234,9 +373,207 @@ impl BatchLeastSquares {
         self.use_srp
     }
 
+    #[getter]
+    pub fn get_a_priori_covariance(&self) -> Option<Covariance> {
+        self.a_priori_covariance
+    }
+
+    #[setter]
+    pub fn set_a_priori_covariance(&mut self, a_priori_covariance: Option<Covariance>) -> PyResult<()> {
+        if let Some(cov) = a_priori_covariance {
+            if cov.get_covariance_type() != CovarianceType::Equinoctial {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "A priori covariance must be of type Equinoctial".to_string(),
+                ));
+            }
+        }
+        self.a_priori_covariance = a_priori_covariance;
+        self.a_priori_information = None;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_consider_drag_sigma(&self) -> Option<f64> {
+        self.consider_drag_sigma
+    }
+
+    #[setter]
+    pub fn set_consider_drag_sigma(&mut self, consider_drag_sigma: Option<f64>) {
+        self.consider_drag_sigma = consider_drag_sigma;
+    }
+
+    #[getter]
+    pub fn get_consider_srp_sigma(&self) -> Option<f64> {
+        self.consider_srp_sigma
+    }
+
+    #[setter]
+    pub fn set_consider_srp_sigma(&mut self, consider_srp_sigma: Option<f64>) {
+        self.consider_srp_sigma = consider_srp_sigma;
+    }
+
+    #[getter]
+    pub fn get_formal_covariance(&self) -> Option<Covariance> {
+        self.formal_covariance
+            .as_ref()
+            .map(|p| Covariance::from((p.clone(), CovarianceType::Equinoctial)))
+    }
+
+    #[getter]
+    pub fn get_edit_sigma(&self) -> Option<f64> {
+        self.edit_sigma
+    }
+
+    #[setter]
+    pub fn set_edit_sigma(&mut self, edit_sigma: Option<f64>) {
+        self.edit_sigma = edit_sigma;
+    }
+
+    #[getter]
+    pub fn get_first_pass_edit_multiplier(&self) -> f64 {
+        self.first_pass_edit_multiplier
+    }
+
+    #[setter]
+    pub fn set_first_pass_edit_multiplier(&mut self, first_pass_edit_multiplier: f64) {
+        self.first_pass_edit_multiplier = first_pass_edit_multiplier;
+    }
+
+    #[getter]
+    pub fn get_rejection_history(&self) -> Vec<Vec<usize>> {
+        self.rejection_history.clone()
+    }
+
+    #[getter]
+    pub fn get_solver_type(&self) -> SolverType {
+        self.solver_type
+    }
+
+    #[setter]
+    pub fn set_solver_type(&mut self, solver_type: SolverType) {
+        self.solver_type = solver_type;
+    }
+
+    #[getter]
+    pub fn get_rms_tolerance(&self) -> f64 {
+        self.rms_tolerance
+    }
+
+    #[setter]
+    pub fn set_rms_tolerance(&mut self, rms_tolerance: f64) {
+        self.rms_tolerance = rms_tolerance;
+    }
+
+    #[getter]
+    pub fn get_correction_tolerance(&self) -> Option<f64> {
+        self.correction_tolerance
+    }
+
+    #[setter]
+    pub fn set_correction_tolerance(&mut self, correction_tolerance: Option<f64>) {
+        self.correction_tolerance = correction_tolerance;
+    }
+
+    #[getter]
+    pub fn get_max_divergent_iterations(&self) -> usize {
+        self.max_divergent_iterations
+    }
+
+    #[setter]
+    pub fn set_max_divergent_iterations(&mut self, max_divergent_iterations: usize) {
+        self.max_divergent_iterations = max_divergent_iterations;
+    }
+
+    #[getter]
+    pub fn get_initial_damping(&self) -> f64 {
+        self.initial_damping
+    }
+
+    #[setter]
+    pub fn set_initial_damping(&mut self, initial_damping: f64) {
+        self.initial_damping = initial_damping;
+        self.damping = initial_damping;
+    }
+
+    #[getter]
+    pub fn get_estimate_biases(&self) -> bool {
+        self.use_biases
+    }
+
+    #[setter]
+    pub fn set_estimate_biases(&mut self, use_biases: bool) {
+        self.use_biases = use_biases;
+        self.reset();
+    }
+
+    #[getter]
+    pub fn get_maneuver(&self) -> Option<ManeuverEvent> {
+        self.maneuver
+            .map(|(epoch, delta_v)| ManeuverEvent::new(self.a_priori.get_satellite_id(), epoch, Some(delta_v)))
+    }
+
+    #[setter]
+    pub fn set_maneuver(&mut self, maneuver: Option<ManeuverEvent>) {
+        self.a_priori_maneuver = maneuver.map(|maneuver| {
+            (
+                maneuver.get_epoch(),
+                maneuver.get_delta_v().unwrap_or(CartesianVector::new(0.0, 0.0, 0.0)),
+            )
+        });
+        self.reset();
+    }
+
+    #[getter]
+    pub fn get_estimate_maneuver_epoch(&self) -> bool {
+        self.estimate_maneuver_epoch
+    }
+
+    #[setter]
+    pub fn set_estimate_maneuver_epoch(&mut self, estimate_maneuver_epoch: bool) {
+        self.estimate_maneuver_epoch = estimate_maneuver_epoch;
+        self.reset();
+    }
+
+    #[getter]
+    pub fn get_maneuver_sigmas(&self) -> Option<Vec<f64>> {
+        let maneuver_col = self.get_state_size() + self.get_bias_parameters().len();
+        let count = self.get_maneuver_parameter_count();
+        self.formal_covariance
+            .as_ref()
+            .filter(|p| count > 0 && p.nrows() >= maneuver_col + count)
+            .map(|p| (maneuver_col..maneuver_col + count).map(|i| p[(i, i)].sqrt()).collect())
+    }
+
+    #[getter]
+    pub fn get_sensor_biases(&self) -> Vec<SensorBias> {
+        let state_size = self.get_state_size();
+        self.get_bias_parameters()
+            .into_iter()
+            .enumerate()
+            .map(|(i, ((sensor_id, sensor_name), measurement_type))| {
+                let value = self
+                    .obs
+                    .iter()
+                    .find(|ob| ob.get_sensor_key() == (sensor_id, sensor_name.clone()))
+                    .map(|ob| ob.get_sensor().get_bias(measurement_type))
+                    .unwrap();
+                let sigma = self
+                    .formal_covariance
+                    .as_ref()
+                    .filter(|p| p.nrows() > state_size + i)
+                    .map(|p| p[(state_size + i, state_size + i)].sqrt());
+                SensorBias::new(sensor_id, sensor_name, measurement_type, value, sigma)
+            })
+            .collect()
+    }
+
     #[getter]
     pub fn get_covariance(&self) -> Option<Covariance> {
-        let residuals = self.get_residuals();
+        let residuals: Vec<(Epoch, ObservationResidual)> = self
+            .get_residuals()
+            .into_iter()
+            .filter(|(_, residual)| residual.get_accepted())
+            .collect();
         let mut residual_matrix = DMatrix::zeros(residuals.len(), 6);
         for (i, (_, residual)) in residuals.iter().enumerate() {
             for j in 0..6 {
@@ -264,69 +601,587 @@ impl BatchLeastSquares {
 }
 
 impl BatchLeastSquares {
-    fn get_measurements_and_weights(&self) -> (DVector<f64>, DMatrix<f64>) {
+    fn get_measurements_and_weights(&self) -> Result<MeasurementsAndWeights, String> {
         let mut measurement_vec = Vec::new();
         let mut weight_diag = Vec::new();
-        self.obs.iter().for_each(|ob| {
-            let (m_vec, w_vec) = ob.get_measurement_and_weight_vector();
+        let mut dims = Vec::new();
+        for ob in self.obs.iter() {
+            let (m_vec, w_vec) = ob.get_measurement_and_weight_vector()?;
+            dims.push(m_vec.len());
             measurement_vec.extend(m_vec);
             weight_diag.extend(w_vec);
-        });
-        let measurement_vector = DVector::from_vec(measurement_vec);
-        let weight_matrix = DMatrix::from_diagonal(&DVector::from_vec(weight_diag));
-        (measurement_vector, weight_matrix)
+        }
+        Ok((DVector::from_vec(measurement_vec), DVector::from_vec(weight_diag), dims))
     }
 
-    fn get_predicted_measurements(&self) -> Result<DVector<f64>, String> {
-        let mut predicted_measurements = Vec::new();
-        for ob in self.obs.iter() {
-            match ob.get_predicted_vector(&self.current_estimate) {
-                Ok(predicted) => predicted_measurements.extend(predicted),
-                Err(e) => Err(e)?,
+    fn get_state_size(&self) -> usize {
+        let mut n = 6;
+        if self.use_drag {
+            n += 1;
+        }
+        if self.use_srp {
+            n += 1;
+        }
+        n
+    }
+
+    fn edit_observations(
+        &mut self,
+        edit_sigma: f64,
+        r: &DVector<f64>,
+        w: &DVector<f64>,
+        dims: &[usize],
+    ) -> Result<bool, String> {
+        // Normalized residual of each observation across all of its measurement components
+        let mut normalized = Vec::with_capacity(dims.len());
+        let mut row = 0;
+        for dim in dims {
+            let wrss: f64 = (row..row + dim).map(|i| w[i] * r[i] * r[i]).sum();
+            normalized.push((wrss / *dim as f64).sqrt());
+            row += dim;
+        }
+
+        // The first pass has no converged RMS to edit against, so use the median as a robust reference
+        let threshold = match self.weighted_rms {
+            Some(weighted_rms) => edit_sigma * weighted_rms.max(1.0),
+            None => {
+                let mut sorted = normalized.clone();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                edit_sigma * sorted[sorted.len() / 2].max(1.0) * self.first_pass_edit_multiplier
+            }
+        };
+
+        let accepted: Vec<bool> = normalized.iter().map(|rho| *rho <= threshold).collect();
+        let accepted_rows: usize = accepted
+            .iter()
+            .zip(dims.iter())
+            .filter(|(accepted, _)| **accepted)
+            .map(|(_, dim)| dim)
+            .sum();
+        if accepted_rows
+            < self.get_state_size() + self.get_bias_parameters().len() + self.get_maneuver_parameter_count()
+        {
+            return Err("Residual editing rejected too many observations".to_string());
+        }
+
+        let changed = accepted != self.accepted;
+        self.rejection_history.push(
+            accepted
+                .iter()
+                .enumerate()
+                .filter(|(_, accepted)| !**accepted)
+                .map(|(i, _)| i)
+                .collect(),
+        );
+        self.accepted = accepted;
+        Ok(changed)
+    }
+
+    fn get_bias_parameters(&self) -> Vec<BiasParameter> {
+        let mut bias_parameters = Vec::new();
+        if self.use_biases {
+            for ob in self.obs.iter() {
+                let sensor_key = ob.get_sensor_key();
+                for measurement_type in ob.get_measurement_types() {
+                    let parameter = (sensor_key.clone(), measurement_type);
+                    if BIAS_MEASUREMENT_TYPES.contains(&measurement_type) && !bias_parameters.contains(&parameter) {
+                        bias_parameters.push(parameter);
+                    }
+                }
+            }
+        }
+        bias_parameters
+    }
+
+    fn get_maneuver_parameter_count(&self) -> usize {
+        // The epoch has no effect on the measurements until the delta-v is nonzero
+        match self.maneuver {
+            None => 0,
+            Some((_, delta_v)) if self.estimate_maneuver_epoch && delta_v.get_magnitude() > 0.0 => 4,
+            Some(_) => 3,
+        }
+    }
+
+    fn apply_correction(&self, delta_x: &DVector<f64>) -> Result<CorrectedEstimate, String> {
+        let state_size = self.get_state_size();
+        let satellite = self.current_estimate.new_with_delta_x(
+            &delta_x.rows(0, state_size).into_owned(),
+            self.use_drag,
+            self.use_srp,
+        )?;
+
+        // Write the bias corrections back onto every observation made by the same sensor
+        let mut obs = self.obs.clone();
+        for (i, (sensor_key, measurement_type)) in self.get_bias_parameters().into_iter().enumerate() {
+            for ob in obs.iter_mut().filter(|ob| ob.get_sensor_key() == sensor_key) {
+                let bias = ob.get_sensor().get_bias(measurement_type) + delta_x[state_size + i];
+                ob.set_sensor_bias(measurement_type, bias)?;
+            }
+        }
+
+        let maneuver_col = state_size + self.get_bias_parameters().len();
+        let maneuver = match self.maneuver {
+            Some((maneuver_epoch, delta_v)) => {
+                let delta_v = delta_v
+                    + CartesianVector::new(
+                        delta_x[maneuver_col],
+                        delta_x[maneuver_col + 1],
+                        delta_x[maneuver_col + 2],
+                    );
+                let maneuver_epoch = match self.get_maneuver_parameter_count() > 3 {
+                    true => self
+                        .get_bounded_maneuver_epoch(maneuver_epoch + TimeSpan::from_seconds(delta_x[maneuver_col + 3])),
+                    false => maneuver_epoch,
+                };
+                Some((maneuver_epoch, delta_v))
             }
+            None => None,
+        };
+        Ok((satellite, obs, maneuver))
+    }
+
+    // Keeps an estimated maneuver between the first and last observations so both arcs stay observed
+    fn get_bounded_maneuver_epoch(&self, maneuver_epoch: Epoch) -> Epoch {
+        let first_epoch = self.obs.iter().map(|o| o.get_epoch()).min().unwrap();
+        let last_epoch = self.obs.iter().map(|o| o.get_epoch()).max().unwrap();
+        maneuver_epoch
+            .max(first_epoch + TimeSpan::from_seconds(MANEUVER_EPOCH_STEP_SECONDS))
+            .min(last_epoch)
+    }
+
+    fn get_residual_vector(
+        &self,
+        satellite: &Satellite,
+        maneuver: Option<&ImpulsiveManeuver>,
+        obs: &[Observation],
+        y: &DVector<f64>,
+    ) -> Result<DVector<f64>, String> {
+        // Predictions are evaluated in parallel and collected in observation order
+        let pre_maneuver = get_pre_maneuver_estimate(satellite, maneuver)?;
+        let predictions = obs
+            .par_iter()
+            .map(|ob| ob.get_predicted_vector(get_trajectory(satellite, &pre_maneuver, ob.get_epoch())))
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut residuals = Vec::with_capacity(y.len());
+        for (ob, predicted) in obs.iter().zip(predictions) {
+            let row = residuals.len();
+            let observed = y.rows(row, predicted.len());
+            residuals.extend(ob.get_measurement_differences(observed.as_slice(), &predicted));
         }
-        Ok(DVector::from_vec(predicted_measurements))
+        Ok(DVector::from_vec(residuals))
+    }
+
+    fn get_observation_residuals(&self) -> Option<Vec<ObservationResidual>> {
+        let pre_maneuver = get_pre_maneuver_estimate(&self.current_estimate, self.maneuver.as_ref()).ok()?;
+        self.obs
+            .par_iter()
+            .map(|ob| ob.get_residual(get_trajectory(&self.current_estimate, &pre_maneuver, ob.get_epoch())))
+            .collect()
     }
 
-    fn get_jacobians(&self) -> Result<DMatrix<f64>, String> {
-        let m = self.get_predicted_measurements()?.len();
+    fn get_seeded_estimate(&self) -> Satellite {
+        let mut seeded = Satellite::new(self.a_priori.get_satellite_id());
+        if let Some(name) = self.a_priori.get_name() {
+            seeded.set_name(name);
+        }
+
+        let mut force_properties = self.a_priori.get_force_properties();
+
+        // Seed SRP if not already set
+        if self.get_estimate_srp() && force_properties.get_srp_coefficient() == 0.0 {
+            force_properties.set_srp_coefficient(configs::DEFAULT_SRP_TERM);
+            force_properties.set_srp_area(1.0);
+        }
+
+        // Seed drag if not already set
+        if self.get_estimate_drag() && force_properties.get_drag_coefficient() == 0.0 {
+            force_properties.set_drag_coefficient(configs::DEFAULT_DRAG_TERM);
+        }
+        seeded.set_force_properties(force_properties);
+
+        // Seed orbit state
+        let mut kep_state = self.a_priori.get_keplerian_state().unwrap();
+        kep_state.set_type(self.output_keplerian_type);
+        seeded.set_keplerian_state(kep_state).unwrap();
+        seeded
+    }
+
+    fn get_consider_flags(&self) -> (bool, bool) {
+        let consider_drag = self.consider_drag_sigma.is_some() && !self.use_drag;
+        let consider_srp = self.consider_srp_sigma.is_some() && !self.use_srp;
+        (consider_drag, consider_srp)
+    }
+
+    fn get_jacobians(
+        &self,
+        use_drag: bool,
+        use_srp: bool,
+        bias_parameters: &[BiasParameter],
+    ) -> Result<DMatrix<f64>, String> {
+        let m = self.obs.iter().map(|ob| ob.get_measurement_types().len()).sum();
         let mut n = 6;
         let mut row = 0;
-        if self.use_drag {
+        if use_drag {
             n += 1;
         }
-        if self.use_srp {
+        if use_srp {
             n += 1;
         }
-        let mut jacobian = DMatrix::zeros(m, n);
-        for ob in self.obs.iter() {
-            let ob_jacobian = self.current_estimate.get_jacobian(ob, self.use_drag, self.use_srp)?;
+        let maneuver_col = n + bias_parameters.len();
+        let pre_maneuver_partials = self.get_pre_maneuver_partials(use_drag, use_srp)?;
+        let force_perturbations = self.current_estimate.get_force_perturbations(use_drag, use_srp)?;
+        let ob_jacobians = self
+            .obs
+            .par_iter()
+            .map(|ob| match pre_maneuver_partials.as_ref() {
+                Some(partials) if ob.get_epoch() < partials.epoch => {
+                    partials.get_jacobians(ob).map(|(h, h_maneuver)| (h, Some(h_maneuver)))
+                }
+                _ => self
+                    .current_estimate
+                    .get_jacobian(ob, &force_perturbations)
+                    .map(|h| (h, None)),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut jacobian = DMatrix::zeros(m, maneuver_col + self.get_maneuver_parameter_count());
+        for (ob, (ob_jacobian, maneuver_jacobian)) in self.obs.iter().zip(ob_jacobians) {
             let dim = ob_jacobian.nrows();
             jacobian.view_mut((row, 0), (dim, n)).copy_from(&ob_jacobian);
+            if let Some(maneuver_jacobian) = maneuver_jacobian {
+                jacobian
+                    .view_mut((row, maneuver_col), maneuver_jacobian.shape())
+                    .copy_from(&maneuver_jacobian);
+            }
+
+            // Biases add directly to the predicted measurement
+            let sensor_key = ob.get_sensor_key();
+            for (i, measurement_type) in ob.get_measurement_types().into_iter().enumerate() {
+                if let Some(col) = bias_parameters
+                    .iter()
+                    .position(|(key, bias_type)| *key == sensor_key && *bias_type == measurement_type)
+                {
+                    jacobian[(row + i, n + col)] = 1.0;
+                }
+            }
             row += dim;
         }
         Ok(jacobian)
     }
 
+    fn get_pre_maneuver_partials(&self, use_drag: bool, use_srp: bool) -> Result<Option<PreManeuverPartials>, String> {
+        let (epoch, delta_v) = match self.maneuver {
+            Some(maneuver) => maneuver,
+            None => return Ok(None),
+        };
+        let shifted_satellite = match self.get_maneuver_parameter_count() > 3 {
+            true => Some(self.current_estimate.new_with_impulse(
+                epoch + TimeSpan::from_seconds(MANEUVER_EPOCH_STEP_SECONDS),
+                delta_v * -1.0,
+            )?),
+            false => None,
+        };
+        let satellite = self.current_estimate.new_with_impulse(epoch, delta_v * -1.0)?;
+        Ok(Some(PreManeuverPartials {
+            epoch,
+            force_perturbations: satellite.get_force_perturbations(use_drag, use_srp)?,
+            satellite,
+            shifted_satellite,
+            element_stm: self.current_estimate.get_stm(epoch, false, false)?,
+            impulse_partials: self.current_estimate.get_impulse_partials(epoch)?,
+        }))
+    }
+
+    fn get_a_priori_information(&self, epoch: Epoch) -> Result<(DVector<f64>, DMatrix<f64>), String> {
+        let p_0: DMatrix<f64> = self.a_priori_covariance.unwrap().into();
+        let seeded = self.get_seeded_estimate();
+        let phi = seeded.get_stm(epoch, false, false)?;
+        let p_epoch = &phi * p_0 * phi.transpose();
+        let information = match p_epoch.try_inverse() {
+            Some(information) => information,
+            None => return Err("A priori covariance is singular".to_string()),
+        };
+        let a_priori_elements = seeded.get_equinoctial_elements_at_epoch(epoch)?;
+        let x_0 = DVector::from_fn(6, |i, _| a_priori_elements[i]);
+        Ok((x_0, information))
+    }
+
+    fn get_a_priori_residual(
+        &self,
+        satellite: &Satellite,
+        maneuver: Option<&ImpulsiveManeuver>,
+    ) -> Result<DVector<f64>, String> {
+        // The a priori state describes the orbit before any maneuver
+        let (x_0, _) = self.a_priori_information.as_ref().unwrap();
+        let epoch = satellite.get_keplerian_state().unwrap().get_epoch();
+        let current_elements = match get_pre_maneuver_estimate(satellite, maneuver)? {
+            Some((_, pre_maneuver)) => pre_maneuver.get_equinoctial_elements_at_epoch(epoch)?,
+            None => satellite.get_equinoctial_elements_at_epoch(epoch)?,
+        };
+        let mut dx_0 = DVector::from_fn(6, |i, _| x_0[i] - current_elements[i]);
+        dx_0[XA_EQNX_L] = (dx_0[XA_EQNX_L] + 540.0).rem_euclid(360.0) - 180.0;
+        Ok(dx_0)
+    }
+
+    fn get_weighted_rss(
+        &self,
+        satellite: &Satellite,
+        maneuver: Option<&ImpulsiveManeuver>,
+        obs: &[Observation],
+        y: &DVector<f64>,
+        w: &DVector<f64>,
+    ) -> Result<f64, String> {
+        let r = self.get_residual_vector(satellite, maneuver, obs, y)?;
+        let mut wrss = r.component_mul(&r).dot(w);
+        if let Some((_, information)) = self.a_priori_information.as_ref() {
+            let dx_0 = self.get_a_priori_residual(satellite, maneuver)?;
+            wrss += (dx_0.transpose() * information * &dx_0)[(0, 0)];
+        }
+        Ok(wrss)
+    }
+
+    fn is_valid_correction(&self, delta_x: &DVector<f64>) -> Result<bool, String> {
+        let epoch = self.current_estimate.get_keplerian_state().unwrap().get_epoch();
+        let elements = self.current_estimate.get_equinoctial_elements_at_epoch(epoch)?;
+        let a_f = elements[XA_EQNX_AF] + delta_x[XA_EQNX_AF];
+        let a_g = elements[XA_EQNX_AG] + delta_x[XA_EQNX_AG];
+        let mean_motion = elements[XA_EQNX_N] + delta_x[XA_EQNX_N];
+        Ok(a_f.hypot(a_g) < 1.0 && mean_motion > 0.0 && delta_x.iter().all(|dx| dx.is_finite()))
+    }
+
+    fn get_damped_delta_x(
+        &mut self,
+        n: &DMatrix<f64>,
+        b: &DVector<f64>,
+        y: &DVector<f64>,
+        w: &DVector<f64>,
+        cost: f64,
+    ) -> Result<DVector<f64>, String> {
+        loop {
+            let mut n_damped = n.clone();
+            for i in 0..n.nrows() {
+                n_damped[(i, i)] += self.damping * n[(i, i)];
+            }
+            let delta_x = match n_damped.lu().solve(b) {
+                Some(delta_x) => delta_x,
+                None => return Err("Damped normal equations are singular".to_string()),
+            };
+
+            // Accept the step only if it reduces the cost, otherwise fall back toward gradient descent
+            if self.is_valid_correction(&delta_x)? {
+                let (trial, trial_obs, trial_maneuver) = self.apply_correction(&delta_x)?;
+                if let Ok(trial_cost) = self.get_weighted_rss(&trial, trial_maneuver.as_ref(), &trial_obs, y, w) {
+                    if trial_cost < cost {
+                        self.damping = (self.damping / DAMPING_FACTOR).max(MIN_DAMPING);
+                        return Ok(delta_x);
+                    }
+                }
+            }
+            self.damping *= DAMPING_FACTOR;
+
+            // No damped step reduces the cost, which is a stall rather than a converged solution
+            if self.damping > MAX_DAMPING {
+                self.damping = self.initial_damping;
+                return Err("Solution stalled: no damped step reduces the weighted residuals".to_string());
+            }
+        }
+    }
+
     fn get_delta_x(&mut self) -> Result<(), String> {
-        let (y, w) = self.get_measurements_and_weights();
-        let y_hat = self.get_predicted_measurements()?;
-        let r = &y - &y_hat;
-        let h = self.get_jacobians()?;
-        let h_transpose_w = &h.transpose() * &w;
-        let n = &h_transpose_w * &h;
-        let b = &h_transpose_w * &r;
+        let (y, mut w_diag, dims) = self.get_measurements_and_weights()?;
+        let r = self.get_residual_vector(&self.current_estimate, self.maneuver.as_ref(), &self.obs, &y)?;
+
+        // Remove observations that fail the residual edit from the normal equations
+        let mut edits_changed = false;
+        if let Some(edit_sigma) = self.edit_sigma {
+            edits_changed = self.edit_observations(edit_sigma, &r, &w_diag, &dims)?;
+        }
+        let mut row = 0;
+        let mut accepted_rows = 0;
+        for (accepted, dim) in self.accepted.iter().zip(dims.iter()) {
+            match accepted {
+                true => accepted_rows += dim,
+                false => w_diag.rows_mut(row, *dim).fill(0.0),
+            }
+            row += dim;
+        }
+        let sqrt_w = w_diag.map(f64::sqrt);
+
+        // Build the full partials and split the estimated columns from the consider columns
+        let (consider_drag, consider_srp) = self.get_consider_flags();
+        let bias_parameters = self.get_bias_parameters();
+        let h_full = self.get_jacobians(
+            self.use_drag || consider_drag,
+            self.use_srp || consider_srp,
+            &bias_parameters,
+        )?;
+        let mut estimated_columns: Vec<usize> = (0..6).collect();
+        let mut consider_columns = Vec::new();
+        let mut consider_variances = Vec::new();
+        let mut next_col = 6;
+        if self.use_drag || consider_drag {
+            match consider_drag {
+                true => {
+                    consider_columns.push(next_col);
+                    consider_variances.push(self.consider_drag_sigma.unwrap().powi(2));
+                }
+                false => estimated_columns.push(next_col),
+            }
+            next_col += 1;
+        }
+        if self.use_srp || consider_srp {
+            match consider_srp {
+                true => {
+                    consider_columns.push(next_col);
+                    consider_variances.push(self.consider_srp_sigma.unwrap().powi(2));
+                }
+                false => estimated_columns.push(next_col),
+            }
+            next_col += 1;
+        }
+        let maneuver_count = self.get_maneuver_parameter_count();
+        estimated_columns.extend(next_col..next_col + bias_parameters.len() + maneuver_count);
+
+        // Whiten the measurement equations so every solver works from the same square root information system
+        let mut a_meas = h_full.select_columns(&estimated_columns);
+        for (i, mut h_row) in a_meas.row_iter_mut().enumerate() {
+            h_row *= sqrt_w[i];
+        }
+        let z_meas = r.component_mul(&sqrt_w);
+        let (a, z) = match self.a_priori_covariance.is_some() {
+            false => (a_meas.clone(), z_meas.clone()),
+            true => {
+                // Add the a priori state as information on the orbit elements
+                if self.a_priori_information.is_none() {
+                    let epoch = self.current_estimate.get_keplerian_state().unwrap().get_epoch();
+                    self.a_priori_information = Some(self.get_a_priori_information(epoch)?);
+                }
+                let dx_0 = self.get_a_priori_residual(&self.current_estimate, self.maneuver.as_ref())?;
+                let (_, information) = self.a_priori_information.as_ref().unwrap();
+                let r_0 = match information.clone().cholesky() {
+                    Some(cholesky) => cholesky.l().transpose(),
+                    None => return Err("A priori covariance is not positive definite".to_string()),
+                };
+                let (m, n) = a_meas.shape();
+                let mut a = DMatrix::zeros(m + 6, n);
+                a.rows_mut(0, m).copy_from(&a_meas);
+                a.view_mut((m, 0), (6, 6)).copy_from(&r_0);
+                if let Some((maneuver_epoch, delta_v)) = self.maneuver {
+                    // Map the impulse at the maneuver epoch back onto the pre-maneuver elements at the solution epoch
+                    let epoch = self.current_estimate.get_keplerian_state().unwrap().get_epoch();
+                    let pre_maneuver = self.current_estimate.new_with_impulse(maneuver_epoch, delta_v * -1.0)?;
+                    let element_stm = pre_maneuver.get_stm(epoch, false, false)?;
+                    let impulse_partials = self.current_estimate.get_impulse_partials(maneuver_epoch)?;
+                    a.view_mut((m, n - maneuver_count), (6, 3))
+                        .copy_from(&(-&r_0 * element_stm * impulse_partials));
+                }
+                let mut z = DVector::zeros(m + 6);
+                z.rows_mut(0, m).copy_from(&z_meas);
+                z.rows_mut(m, 6).copy_from(&(&r_0 * dx_0));
+                (a, z)
+            }
+        };
+        let n = a.transpose() * &a;
+        let b = a.transpose() * &z;
+
+        // A heavily damped step is small and barely changes the RMS even far from the minimum, so
+        // Levenberg-Marquardt convergence is judged on the undamped Gauss-Newton step instead
+        let undamped_delta_x = match self.solver_type {
+            SolverType::LevenbergMarquardt => n.clone().lu().solve(&b),
+            _ => None,
+        };
 
         // Compute weighted RMS for convergence testing and noise balancing
-        let m = r.len() as f64;
-        let wrss = (r.transpose() * &w * &r)[(0, 0)];
+        let m = accepted_rows as f64;
+        let wrss = z_meas.dot(&z_meas);
         let current_weighted_rms = (wrss / m).sqrt();
-        if self.weighted_rms.is_some() && (current_weighted_rms - self.weighted_rms.unwrap()).abs() < 1e-3 {
-            self.converged = true;
+        if !current_weighted_rms.is_finite() {
+            return Err("Weighted RMS is not finite".to_string());
+        }
+        if let Some(previous_weighted_rms) = self.weighted_rms {
+            let change = current_weighted_rms - previous_weighted_rms;
+            if change.abs() <= self.rms_tolerance * previous_weighted_rms && !edits_changed {
+                self.converged = match (self.solver_type, undamped_delta_x.as_ref()) {
+                    (SolverType::LevenbergMarquardt, Some(undamped_delta_x)) => {
+                        // The linearized cost after the full step must also sit on the plateau
+                        let cost = z.dot(&z);
+                        let predicted_cost = (cost - b.dot(undamped_delta_x)).max(0.0);
+                        cost == 0.0 || 1.0 - (predicted_cost / cost).sqrt() <= self.rms_tolerance
+                    }
+                    (SolverType::LevenbergMarquardt, None) => false,
+                    _ => true,
+                };
+            } else if change > self.rms_tolerance * previous_weighted_rms && !edits_changed {
+                self.divergent_iterations += 1;
+            } else {
+                self.divergent_iterations = 0;
+            }
+            if !self.converged && self.divergent_iterations >= self.max_divergent_iterations {
+                return Err(format!(
+                    "Solution diverged after {} consecutive increases in weighted RMS",
+                    self.divergent_iterations
+                ));
+            }
         }
 
+        // Inflate the formal covariance by the uncertainty of the consider parameters
+        self.formal_covariance = n.clone().try_inverse().map(|p| match consider_columns.is_empty() {
+            true => p,
+            false => {
+                let mut a_c = h_full.select_columns(&consider_columns);
+                for (i, mut h_row) in a_c.row_iter_mut().enumerate() {
+                    h_row *= sqrt_w[i];
+                }
+                let c = DMatrix::from_diagonal(&DVector::from_vec(consider_variances));
+                let s = -&p * a_meas.transpose() * a_c;
+                &p + &s * c * s.transpose()
+            }
+        });
+
         self.weighted_rms = Some(current_weighted_rms);
-        self.delta_x = n.lu().solve(&b);
+        let delta_x = match self.solver_type {
+            SolverType::GaussNewton => n.clone().lu().solve(&b),
+            // Only a plateau in the weighted RMS after accepted steps marks convergence, so no further step is taken
+            SolverType::LevenbergMarquardt => match self.converged {
+                true => Some(DVector::zeros(n.nrows())),
+                false => Some(self.get_damped_delta_x(&n, &b, &y, &w_diag, z.dot(&z))?),
+            },
+            SolverType::QR => {
+                let qr = a.qr();
+                qr.r().solve_upper_triangular(&(qr.q().transpose() * &z))
+            }
+            SolverType::SVD => {
+                let svd = a.svd(true, true);
+                let tolerance = SVD_RELATIVE_TOLERANCE * svd.singular_values.max();
+                svd.solve(&z, tolerance).ok()
+            }
+        };
+        let delta_x = match delta_x {
+            Some(delta_x) => delta_x,
+            None => return Err("Normal equations are singular".to_string()),
+        };
+        if !self.is_valid_correction(&delta_x)? {
+            return Err("Solution diverged: state correction produced an invalid orbit".to_string());
+        }
+
+        // Optionally converge on the size of the correction relative to the formal uncertainty
+        let correction = match self.solver_type {
+            SolverType::LevenbergMarquardt => undamped_delta_x.as_ref(),
+            _ => Some(&delta_x),
+        };
+        if let (Some(tolerance), Some(p), Some(correction)) =
+            (self.correction_tolerance, self.formal_covariance.as_ref(), correction)
+        {
+            if !edits_changed
+                && correction
+                    .iter()
+                    .enumerate()
+                    .all(|(i, dx)| dx.abs() <= tolerance * p[(i, i)].sqrt())
+            {
+                self.converged = true;
+            }
+        }
+
+        self.delta_x = Some(delta_x);
         Ok(())
     }
 }
@@ -0,0 +1,140 @@
+use pyo3::prelude::*;
+
+const MAX_GAMMA_ITERATIONS: usize = 200;
+const GAMMA_TOLERANCE: f64 = 1e-14;
+const LANCZOS_COEFFICIENTS: [f64; 6] = [
+    76.18009172947146,
+    -86.50532032941677,
+    24.01409824083091,
+    -1.231739572450155,
+    0.1208650973866179e-2,
+    -0.5395239384953e-5,
+];
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationCandidate {
+    satellite_id: i32,
+    chi_square: f64,
+    degrees_of_freedom: usize,
+    p_value: f64,
+    correlated: bool,
+}
+
+impl CorrelationCandidate {
+    pub fn new(satellite_id: i32, chi_square: f64, degrees_of_freedom: usize, significance: f64) -> Self {
+        let p_value = get_chi_square_p_value(chi_square, degrees_of_freedom);
+        Self {
+            satellite_id,
+            chi_square,
+            degrees_of_freedom,
+            p_value,
+            correlated: p_value >= significance,
+        }
+    }
+}
+
+#[pymethods]
+impl CorrelationCandidate {
+    #[getter]
+    pub fn get_satellite_id(&self) -> i32 {
+        self.satellite_id
+    }
+
+    #[getter]
+    pub fn get_chi_square(&self) -> f64 {
+        self.chi_square
+    }
+
+    #[getter]
+    pub fn get_degrees_of_freedom(&self) -> usize {
+        self.degrees_of_freedom
+    }
+
+    #[getter]
+    pub fn get_p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    #[getter]
+    pub fn get_correlated(&self) -> bool {
+        self.correlated
+    }
+}
+
+fn get_ln_gamma(x: f64) -> f64 {
+    let mut denominator = x;
+    let mut series = 1.000000000190015;
+    for coefficient in LANCZOS_COEFFICIENTS {
+        denominator += 1.0;
+        series += coefficient / denominator;
+    }
+    let t = x + 5.5;
+    -t + (x + 0.5) * t.ln() + (2.5066282746310005 * series / x).ln()
+}
+
+// Upper tail probability of the chi-square distribution from the regularized incomplete gamma function
+fn get_chi_square_p_value(chi_square: f64, degrees_of_freedom: usize) -> f64 {
+    if chi_square <= 0.0 || degrees_of_freedom == 0 {
+        return 1.0;
+    }
+    let a = degrees_of_freedom as f64 / 2.0;
+    let x = chi_square / 2.0;
+    let log_prefactor = -x + a * x.ln() - get_ln_gamma(a);
+
+    if x < a + 1.0 {
+        // Series expansion of the lower incomplete gamma function
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_GAMMA_ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * GAMMA_TOLERANCE {
+                break;
+            }
+        }
+        (1.0 - sum * log_prefactor.exp()).max(0.0)
+    } else {
+        // Lentz continued fraction of the upper incomplete gamma function
+        let tiny = f64::MIN_POSITIVE / GAMMA_TOLERANCE;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..=MAX_GAMMA_ITERATIONS {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < GAMMA_TOLERANCE {
+                break;
+            }
+        }
+        (h * log_prefactor.exp()).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chi_square_p_value() {
+        assert!((get_chi_square_p_value(3.841458820694124, 1) - 0.05).abs() < 1e-9);
+        assert!((get_chi_square_p_value(5.991464547107979, 2) - 0.05).abs() < 1e-9);
+        assert!((get_chi_square_p_value(2.0, 4) - 0.7357588823428847).abs() < 1e-9);
+        assert!((get_chi_square_p_value(23.209251158954356, 10) - 0.01).abs() < 1e-9);
+        assert_eq!(get_chi_square_p_value(0.0, 3), 1.0);
+    }
+}
@@ -0,0 +1,54 @@
+use super::CorrelationCandidate;
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationReport {
+    significance: f64,
+    candidates: Vec<CorrelationCandidate>,
+}
+
+#[pymethods]
+impl CorrelationReport {
+    #[new]
+    pub fn new(significance: f64) -> Self {
+        Self {
+            significance,
+            candidates: Vec::new(),
+        }
+    }
+
+    #[getter]
+    pub fn get_significance(&self) -> f64 {
+        self.significance
+    }
+
+    #[getter]
+    pub fn get_candidates(&self) -> Vec<CorrelationCandidate> {
+        self.candidates.clone()
+    }
+
+    #[setter]
+    pub fn set_candidates(&mut self, mut candidates: Vec<CorrelationCandidate>) {
+        // Rank by chi-square with the satellite ID breaking ties so reports are repeatable
+        candidates.sort_by(|a, b| {
+            a.get_chi_square()
+                .total_cmp(&b.get_chi_square())
+                .then(a.get_satellite_id().cmp(&b.get_satellite_id()))
+        });
+        self.candidates = candidates;
+    }
+
+    #[getter]
+    pub fn get_best_candidate(&self) -> Option<CorrelationCandidate> {
+        self.candidates
+            .iter()
+            .find(|candidate| candidate.get_correlated())
+            .cloned()
+    }
+
+    #[getter]
+    pub fn get_uncorrelated(&self) -> bool {
+        self.candidates.iter().all(|candidate| !candidate.get_correlated())
+    }
+}
@@ -11,6 +11,14 @@ pub struct Covariance {
 
 #[pymethods]
 impl Covariance {
+    #[new]
+    pub fn new(covariance_type: CovarianceType) -> Self {
+        Self {
+            elements: Matrix6::zeros(),
+            covariance_type,
+        }
+    }
+
     pub fn get_element(&self, i: usize, j: usize) -> f64 {
         self.elements[(i, j)]
     }
@@ -46,6 +54,12 @@ impl From<Covariance> for [[f64; 6]; 6] {
     }
 }
 
+impl From<Covariance> for DMatrix<f64> {
+    fn from(cov: Covariance) -> Self {
+        DMatrix::from_fn(6, 6, |i, j| cov.elements[(i, j)])
+    }
+}
+
 impl From<([[f64; 6]; 6], CovarianceType)> for Covariance {
     fn from(input: ([[f64; 6]; 6], CovarianceType)) -> Self {
         let (elements, covariance_type) = input;
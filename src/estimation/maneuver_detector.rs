@@ -0,0 +1,274 @@
+use super::{BatchLeastSquares, Observation};
+use crate::bodies::Satellite;
+use crate::elements::{CartesianState, CartesianVector, TLE};
+use crate::events::ManeuverEvent;
+use crate::saal::sat_state_interface;
+use crate::time::{Epoch, TimeSpan};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+pub const DEFAULT_MANEUVER_RESIDUAL_THRESHOLD: f64 = 5.0;
+pub const DEFAULT_MIN_MANEUVER_OBSERVATIONS: usize = 3;
+pub const DEFAULT_MANEUVER_FIT_SPAN_DAYS: f64 = 2.0;
+pub const DEFAULT_DELTA_V_THRESHOLD: f64 = 0.001;
+const MANEUVER_SEARCH_STEP_SECONDS: f64 = 60.0;
+const MANEUVER_SEARCH_TOLERANCE_SECONDS: f64 = 0.1;
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618_033_988_749_895;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManeuverDetector {
+    residual_threshold: f64,
+    min_observations: usize,
+    fit_span: TimeSpan,
+    delta_v_threshold: f64,
+}
+
+impl Default for ManeuverDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn get_state(satellite: &Satellite, epoch: Epoch) -> Result<CartesianState, String> {
+    satellite.get_state_at_epoch(epoch).ok_or(format!(
+        "Error propagating satellite {} to {}",
+        satellite.get_satellite_id(),
+        epoch.to_iso()
+    ))
+}
+
+fn get_separation(pre: &Satellite, post: &Satellite, epoch: Epoch) -> Result<f64, String> {
+    Ok((get_state(post, epoch)?.position - get_state(pre, epoch)?.position).get_magnitude())
+}
+
+// Locates the epoch where the two trajectories meet and returns the RIC velocity change at that point
+fn get_impulse(
+    pre: &Satellite,
+    post: &Satellite,
+    start: Epoch,
+    end: Epoch,
+) -> Result<(Epoch, CartesianVector), String> {
+    let span = (end - start).in_seconds().max(0.0);
+    let steps = (span / MANEUVER_SEARCH_STEP_SECONDS).ceil().max(1.0) as usize;
+    let step = span / steps as f64;
+    let mut best_offset = 0.0;
+    let mut best_separation = f64::INFINITY;
+    for i in 0..=steps {
+        let offset = i as f64 * step;
+        let separation = get_separation(pre, post, start + TimeSpan::from_seconds(offset))?;
+        if separation < best_separation {
+            best_separation = separation;
+            best_offset = offset;
+        }
+    }
+
+    // Golden-section refinement within the bracketing search steps
+    let mut lower = (best_offset - step).max(0.0);
+    let mut upper = (best_offset + step).min(span);
+    while upper - lower > MANEUVER_SEARCH_TOLERANCE_SECONDS {
+        let left = upper - GOLDEN_RATIO_CONJUGATE * (upper - lower);
+        let right = lower + GOLDEN_RATIO_CONJUGATE * (upper - lower);
+        if get_separation(pre, post, start + TimeSpan::from_seconds(left))?
+            < get_separation(pre, post, start + TimeSpan::from_seconds(right))?
+        {
+            upper = right;
+        } else {
+            lower = left;
+        }
+    }
+    let epoch = start + TimeSpan::from_seconds(0.5 * (lower + upper));
+
+    let pre_state = get_state(pre, epoch)?;
+    let post_state = get_state(post, epoch)?;
+    let xa_delta = sat_state_interface::get_relative_state(
+        &get_posvel(&pre_state),
+        &get_posvel(&post_state),
+        epoch.days_since_1950,
+    );
+    let delta_v = CartesianVector::new(
+        xa_delta[sat_state_interface::XA_DELTA_VRADIAL],
+        xa_delta[sat_state_interface::XA_DELTA_VINTRCK],
+        xa_delta[sat_state_interface::XA_DELTA_VCRSSTRCK],
+    );
+    Ok((epoch, delta_v))
+}
+
+fn get_posvel(state: &CartesianState) -> [f64; 6] {
+    [
+        state.position[0],
+        state.position[1],
+        state.position[2],
+        state.velocity[0],
+        state.velocity[1],
+        state.velocity[2],
+    ]
+}
+
+// Root mean square of the noise-normalized measurement residuals
+fn get_normalized_residual(ob: &Observation, satellite: &Satellite) -> Result<f64, String> {
+    let (m_vec, w_vec) = ob.get_measurement_and_weight_vector()?;
+    let predicted = ob.get_predicted_vector(satellite)?;
+    let differences = ob.get_measurement_differences(&m_vec, &predicted);
+    let chi_square = differences
+        .iter()
+        .zip(w_vec.iter())
+        .map(|(r, w)| w * r * r)
+        .sum::<f64>();
+    Ok((chi_square / differences.len() as f64).sqrt())
+}
+
+impl ManeuverDetector {
+    fn get_post_maneuver_fit(&self, obs: &[Observation], prior: &Satellite) -> Option<Satellite> {
+        let end = obs[0].get_epoch() + self.fit_span;
+        let fit_obs: Vec<Observation> = obs.iter().filter(|ob| ob.get_epoch() <= end).cloned().collect();
+        let mut bls = BatchLeastSquares::new(fit_obs, prior);
+        match bls.solve() {
+            Ok(_) if bls.get_converged() => Some(bls.get_current_estimate()),
+            _ => None,
+        }
+    }
+
+    fn get_observation_events(
+        &self,
+        prior: &Satellite,
+        mut obs: Vec<Observation>,
+    ) -> Result<Vec<ManeuverEvent>, String> {
+        obs.sort_by_key(|ob| ob.get_epoch());
+        let satellite_id = prior.get_satellite_id();
+        let mut prior = prior.clone();
+        let mut last_nominal = match prior.get_keplerian_state() {
+            Some(keplerian_state) => keplerian_state.get_epoch(),
+            None => return Err(format!("Satellite {} has no orbit state", satellite_id)),
+        };
+        let mut events = Vec::new();
+        let mut run_start: Option<usize> = None;
+        let mut i = 0;
+        while i < obs.len() {
+            if get_normalized_residual(&obs[i], &prior)? <= self.residual_threshold {
+                run_start = None;
+                last_nominal = obs[i].get_epoch();
+                i += 1;
+                continue;
+            }
+
+            // A maneuver is only declared once several consecutive observations disagree with the prior
+            let start = *run_start.get_or_insert(i);
+            if i + 1 - start < self.min_observations {
+                i += 1;
+                continue;
+            }
+            let first_outlier = obs[start].get_epoch();
+            let search_start = last_nominal.min(first_outlier);
+            match self.get_post_maneuver_fit(&obs[start..], &prior) {
+                Some(post) => {
+                    let (epoch, delta_v) = get_impulse(&prior, &post, search_start, first_outlier)?;
+                    events.push(ManeuverEvent::new(satellite_id, epoch, Some(delta_v)));
+                    prior = post;
+                    last_nominal = obs[i].get_epoch();
+                    run_start = None;
+                    i += 1;
+                }
+                None => {
+                    // Without a post-maneuver orbit later residuals cannot be interpreted
+                    events.push(ManeuverEvent::new(satellite_id, first_outlier, None));
+                    break;
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    fn get_tle_events(&self, tles: Vec<TLE>) -> Result<Vec<ManeuverEvent>, String> {
+        let mut histories: BTreeMap<i32, Vec<TLE>> = BTreeMap::new();
+        for tle in tles {
+            histories.entry(tle.get_satellite_id()).or_default().push(tle);
+        }
+
+        let mut events = Vec::new();
+        for (satellite_id, mut history) in histories {
+            history.sort_by_key(|tle| tle.get_epoch());
+            for pair in history.windows(2) {
+                let (start, end) = (pair[0].get_epoch(), pair[1].get_epoch());
+                if end <= start {
+                    continue;
+                }
+                let pre = Satellite::from_tle(pair[0].clone());
+                let post = Satellite::from_tle(pair[1].clone());
+                let (epoch, delta_v) = get_impulse(&pre, &post, start, end)?;
+                if delta_v.get_magnitude() > self.delta_v_threshold {
+                    events.push(ManeuverEvent::new(satellite_id, epoch, Some(delta_v)));
+                }
+            }
+        }
+        Ok(events)
+    }
+}
+
+#[pymethods]
+impl ManeuverDetector {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            residual_threshold: DEFAULT_MANEUVER_RESIDUAL_THRESHOLD,
+            min_observations: DEFAULT_MIN_MANEUVER_OBSERVATIONS,
+            fit_span: TimeSpan::from_days(DEFAULT_MANEUVER_FIT_SPAN_DAYS),
+            delta_v_threshold: DEFAULT_DELTA_V_THRESHOLD,
+        }
+    }
+
+    #[getter]
+    pub fn get_residual_threshold(&self) -> f64 {
+        self.residual_threshold
+    }
+
+    #[setter]
+    pub fn set_residual_threshold(&mut self, residual_threshold: f64) {
+        self.residual_threshold = residual_threshold;
+    }
+
+    #[getter]
+    pub fn get_min_observations(&self) -> usize {
+        self.min_observations
+    }
+
+    #[setter]
+    pub fn set_min_observations(&mut self, min_observations: usize) -> PyResult<()> {
+        if min_observations == 0 {
+            return Err(PyErr::new::<PyValueError, _>(
+                "At least one observation is required to detect a maneuver",
+            ));
+        }
+        self.min_observations = min_observations;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_fit_span(&self) -> TimeSpan {
+        self.fit_span
+    }
+
+    #[setter]
+    pub fn set_fit_span(&mut self, fit_span: TimeSpan) {
+        self.fit_span = fit_span;
+    }
+
+    #[getter]
+    pub fn get_delta_v_threshold(&self) -> f64 {
+        self.delta_v_threshold
+    }
+
+    #[setter]
+    pub fn set_delta_v_threshold(&mut self, delta_v_threshold: f64) {
+        self.delta_v_threshold = delta_v_threshold;
+    }
+
+    pub fn detect_from_observations(&self, prior: &Satellite, obs: Vec<Observation>) -> PyResult<Vec<ManeuverEvent>> {
+        self.get_observation_events(prior, obs).map_err(PyRuntimeError::new_err)
+    }
+
+    pub fn detect_from_tles(&self, tles: Vec<TLE>) -> PyResult<Vec<ManeuverEvent>> {
+        self.get_tle_events(tles).map_err(PyRuntimeError::new_err)
+    }
+}
@@ -1,77 +1,594 @@
-use std::vec;
-
 use super::ObservationResidual;
 use crate::bodies::{Satellite, Sensor};
-use crate::elements::{CartesianVector, TopocentricElements};
+use crate::elements::{CartesianState, CartesianVector, HorizonElements, SphericalVector, TopocentricElements};
+use crate::enums::{MeasurementType, TimeSystem};
 use crate::saal::{astro_func_interface, sat_state_interface};
 
-use crate::time::Epoch;
+use crate::time::{Epoch, TimeSpan};
+use nalgebra::DMatrix;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
+use std::f64::consts::PI;
+
+const SPEED_OF_LIGHT: f64 = 299792.458;
+const MAX_LIGHT_TIME_ITERATIONS: usize = 5;
+const LIGHT_TIME_TOLERANCE: f64 = 1e-9;
+const MIN_REFRACTION_ELEVATION: f64 = -1.0;
+const MAX_REFRACTION_ITERATIONS: usize = 10;
+const EARTH_ROTATION_RATE: f64 = 7.292115147e-5;
+const POSITION_DIFFERENCE_STEP: f64 = 1e-3;
+const VELOCITY_DIFFERENCE_STEP: f64 = 1e-6;
+const ANGLE_DIFFERENCE_STEP: f64 = 1e-4;
+
+// Sensors are identified by ID and name so distinct sensors sharing a name are kept apart
+pub type SensorKey = (Option<i32>, String);
+
+#[derive(Debug, Clone, PartialEq)]
+enum ObservedElements {
+    Topocentric(TopocentricElements),
+    Horizon(HorizonElements),
+    Range(f64),
+    RangeRate(f64),
+}
+
+impl ObservedElements {
+    fn get_required_types(&self) -> Vec<MeasurementType> {
+        match self {
+            ObservedElements::Topocentric(_) => vec![MeasurementType::RightAscension, MeasurementType::Declination],
+            ObservedElements::Horizon(_) => vec![MeasurementType::Azimuth, MeasurementType::Elevation],
+            ObservedElements::Range(_) => vec![MeasurementType::Range],
+            ObservedElements::RangeRate(_) => vec![MeasurementType::RangeRate],
+        }
+    }
+
+    fn get_optional_types(&self) -> Vec<MeasurementType> {
+        match self {
+            ObservedElements::Topocentric(_) => vec![
+                MeasurementType::Range,
+                MeasurementType::RangeRate,
+                MeasurementType::RightAscensionRate,
+                MeasurementType::DeclinationRate,
+            ],
+            ObservedElements::Horizon(_) => vec![
+                MeasurementType::Range,
+                MeasurementType::RangeRate,
+                MeasurementType::AzimuthRate,
+                MeasurementType::ElevationRate,
+            ],
+            ObservedElements::Range(_) | ObservedElements::RangeRate(_) => Vec::new(),
+        }
+    }
+
+    fn get_measurement(&self, measurement_type: MeasurementType) -> Option<f64> {
+        match (self, measurement_type) {
+            (ObservedElements::Topocentric(topo), MeasurementType::RightAscension) => Some(topo.get_right_ascension()),
+            (ObservedElements::Topocentric(topo), MeasurementType::Declination) => Some(topo.get_declination()),
+            (ObservedElements::Topocentric(topo), MeasurementType::Range) => topo.get_range(),
+            (ObservedElements::Topocentric(topo), MeasurementType::RangeRate) => topo.get_range_rate(),
+            (ObservedElements::Topocentric(topo), MeasurementType::RightAscensionRate) => {
+                topo.get_right_ascension_rate()
+            }
+            (ObservedElements::Topocentric(topo), MeasurementType::DeclinationRate) => topo.get_declination_rate(),
+            (ObservedElements::Horizon(horizon), MeasurementType::Azimuth) => Some(horizon.get_azimuth()),
+            (ObservedElements::Horizon(horizon), MeasurementType::Elevation) => Some(horizon.get_elevation()),
+            (ObservedElements::Horizon(horizon), MeasurementType::Range) => horizon.get_range(),
+            (ObservedElements::Horizon(horizon), MeasurementType::RangeRate) => horizon.get_range_rate(),
+            (ObservedElements::Horizon(horizon), MeasurementType::AzimuthRate) => horizon.get_azimuth_rate(),
+            (ObservedElements::Horizon(horizon), MeasurementType::ElevationRate) => horizon.get_elevation_rate(),
+            (ObservedElements::Range(range), MeasurementType::Range) => Some(*range),
+            (ObservedElements::RangeRate(range_rate), MeasurementType::RangeRate) => Some(*range_rate),
+            _ => None,
+        }
+    }
+}
 
 #[pyclass]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Observation {
     sensor: Sensor,
     epoch: Epoch,
-    observed_teme_topocentric: TopocentricElements,
+    observed_elements: ObservedElements,
     observer_teme_position: CartesianVector,
+    observer_teme_velocity: Option<CartesianVector>,
+    space_based: bool,
     observed_satellite_id: Option<i32>,
+    light_time_correction: bool,
+    aberration_correction: bool,
+    refraction_correction: bool,
 }
 
 impl Observation {
-    pub fn get_measurement_and_weight_vector(&self) -> (Vec<f64>, Vec<f64>) {
-        let mut m_vec = vec![self.get_right_ascension(), self.get_declination()];
-        let mut w_vec = vec![
-            1.0 / self.sensor.get_angular_noise().powi(2),
-            1.0 / self.sensor.get_angular_noise().powi(2),
-        ];
-        if self.get_range().is_some() && self.sensor.get_range_noise().is_some() {
-            m_vec.push(self.get_range().unwrap());
-            w_vec.push(1.0 / self.sensor.get_range_noise().unwrap().powi(2));
+    fn from_observed_elements(
+        sensor: Sensor,
+        epoch: Epoch,
+        observed_elements: ObservedElements,
+        observer_teme_position: CartesianVector,
+    ) -> Self {
+        Self {
+            sensor,
+            epoch,
+            observed_elements,
+            observer_teme_position,
+            observer_teme_velocity: None,
+            space_based: false,
+            observed_satellite_id: None,
+            light_time_correction: false,
+            aberration_correction: false,
+            refraction_correction: false,
+        }
+    }
+
+    // Space-based angles whose observer velocity is unknown, such as B3 type 9 records
+    pub fn from_space_position(
+        sensor: Sensor,
+        epoch: Epoch,
+        observed_teme_topocentric: TopocentricElements,
+        observer_teme_position: CartesianVector,
+    ) -> Self {
+        let mut ob = Self::from_observed_elements(
+            sensor,
+            epoch,
+            ObservedElements::Topocentric(observed_teme_topocentric),
+            observer_teme_position,
+        );
+        ob.space_based = true;
+        ob
+    }
+
+    // A space-based observer without a known velocity is treated as momentarily at rest
+    fn get_observer_velocity(&self) -> CartesianVector {
+        match (self.observer_teme_velocity, self.space_based) {
+            (Some(observer_teme_velocity), _) => observer_teme_velocity,
+            (None, true) => CartesianVector::new(0.0, 0.0, 0.0),
+            (None, false) => CartesianVector::new(
+                -EARTH_ROTATION_RATE * self.observer_teme_position[1],
+                EARTH_ROTATION_RATE * self.observer_teme_position[0],
+                0.0,
+            ),
+        }
+    }
+
+    pub fn get_sensor_key(&self) -> SensorKey {
+        (self.sensor.get_sensor_id(), self.sensor.get_name())
+    }
+
+    pub fn get_measurement_types(&self) -> Vec<MeasurementType> {
+        let mut measurement_types = self.observed_elements.get_required_types();
+        for measurement_type in self.observed_elements.get_optional_types() {
+            if self.observed_elements.get_measurement(measurement_type).is_some()
+                && self.sensor.get_noise(measurement_type).is_some()
+            {
+                measurement_types.push(measurement_type);
+            }
+        }
+        measurement_types
+    }
+
+    // Azimuth and elevation are converted so every angle observation has a right ascension and declination
+    pub fn get_right_ascension(&self) -> Option<f64> {
+        match &self.observed_elements {
+            ObservedElements::Horizon(horizon) => Some(
+                astro_func_interface::horizon_to_topocentric(
+                    self.epoch,
+                    self.observer_teme_position,
+                    horizon.get_azimuth(),
+                    horizon.get_elevation(),
+                )
+                .0,
+            ),
+            _ => self.observed_elements.get_measurement(MeasurementType::RightAscension),
+        }
+    }
+
+    pub fn get_declination(&self) -> Option<f64> {
+        match &self.observed_elements {
+            ObservedElements::Horizon(horizon) => Some(
+                astro_func_interface::horizon_to_topocentric(
+                    self.epoch,
+                    self.observer_teme_position,
+                    horizon.get_azimuth(),
+                    horizon.get_elevation(),
+                )
+                .1,
+            ),
+            _ => self.observed_elements.get_measurement(MeasurementType::Declination),
+        }
+    }
+
+    pub fn get_measurement_and_weight_vector(&self) -> Result<(Vec<f64>, Vec<f64>), String> {
+        let mut m_vec = Vec::new();
+        let mut w_vec = Vec::new();
+        for measurement_type in self.get_measurement_types() {
+            let noise = match self.sensor.get_noise(measurement_type) {
+                Some(noise) => noise,
+                None => {
+                    return Err(format!(
+                        "Sensor {} has no noise defined for {:?} measurements",
+                        self.sensor.get_name(),
+                        measurement_type
+                    ))
+                }
+            };
+            m_vec.push(self.observed_elements.get_measurement(measurement_type).unwrap());
+            w_vec.push(1.0 / noise.powi(2));
+        }
+        Ok((m_vec, w_vec))
+    }
+
+    fn get_satellite_state(&self, satellite: &Satellite, epoch: Epoch) -> Result<CartesianState, String> {
+        match satellite.get_state_at_epoch(epoch) {
+            Some(satellite_state) => Ok(satellite_state),
+            None => Err(format!(
+                "Error propagating satellite {} to {}",
+                satellite.get_satellite_id(),
+                epoch.to_iso()
+            )),
+        }
+    }
+
+    fn get_emission_state(&self, satellite: &Satellite) -> Result<CartesianState, String> {
+        let mut satellite_state = self.get_satellite_state(satellite, self.epoch)?;
+        if self.light_time_correction {
+            // Evaluate the satellite when the signal left it rather than when it was received
+            let mut light_time = 0.0;
+            for _ in 0..MAX_LIGHT_TIME_ITERATIONS {
+                let next_light_time =
+                    (satellite_state.position - self.observer_teme_position).get_magnitude() / SPEED_OF_LIGHT;
+                if (next_light_time - light_time).abs() < LIGHT_TIME_TOLERANCE {
+                    break;
+                }
+                light_time = next_light_time;
+                satellite_state =
+                    self.get_satellite_state(satellite, self.epoch - TimeSpan::from_seconds(light_time))?;
+            }
+        }
+        Ok(satellite_state)
+    }
+
+    fn get_aberration(&self, ra: f64, dec: f64) -> (f64, f64) {
+        // SAAL takes UTC days since 1950, which is the epoch and its residual rounded to a single double
+        let ds50utc = self.epoch.to_system(TimeSystem::UTC).unwrap().days_since_1950;
+        let (annual_ra, annual_dec) = astro_func_interface::annual_aberration(ds50utc, ra, dec);
+        let (observer_ra, observer_dec) = match self.space_based {
+            true => get_velocity_aberration(self.get_observer_velocity(), ra, dec),
+            false => astro_func_interface::diurnal_aberration(ds50utc, self.observer_teme_position, ra, dec),
+        };
+        (annual_ra + observer_ra, annual_dec + observer_dec)
+    }
+
+    fn has_apparent_angle_corrections(&self) -> bool {
+        self.aberration_correction || (self.refraction_correction && !self.space_based)
+    }
+
+    // Geometric right ascension and declination mapped to the apparent direction the sensor reports
+    fn get_apparent_angles(&self, ra: f64, dec: f64) -> (f64, f64) {
+        let (mut ra, mut dec) = (ra, dec);
+        if self.aberration_correction {
+            let (ra_delta, dec_delta) = self.get_aberration(ra, dec);
+            ra = (ra + ra_delta).rem_euclid(360.0);
+            dec += dec_delta;
+        }
+        if self.refraction_correction && !self.space_based {
+            (ra, dec) = self.get_refracted_direction(ra, dec, get_refraction_from_true_elevation);
         }
-        if self.get_range_rate().is_some() && self.sensor.get_range_rate_noise().is_some() {
-            m_vec.push(self.get_range_rate().unwrap());
-            w_vec.push(1.0 / self.sensor.get_range_rate_noise().unwrap().powi(2));
+        (ra, dec)
+    }
+
+    // Rows are the apparent right ascension and declination, columns the geometric right ascension and declination
+    fn get_apparent_angle_jacobian(&self, ra: f64, dec: f64) -> [[f64; 2]; 2] {
+        let mut jacobian = [[0.0; 2]; 2];
+        for (column, (d_ra, d_dec)) in [(ANGLE_DIFFERENCE_STEP, 0.0), (0.0, ANGLE_DIFFERENCE_STEP)]
+            .into_iter()
+            .enumerate()
+        {
+            let (ra_plus, dec_plus) = self.get_apparent_angles(ra + d_ra, dec + d_dec);
+            let (ra_minus, dec_minus) = self.get_apparent_angles(ra - d_ra, dec - d_dec);
+            jacobian[0][column] =
+                ((ra_plus - ra_minus + 540.0).rem_euclid(360.0) - 180.0) / (2.0 * ANGLE_DIFFERENCE_STEP);
+            jacobian[1][column] = (dec_plus - dec_minus) / (2.0 * ANGLE_DIFFERENCE_STEP);
         }
-        if self.get_right_ascension_rate().is_some() && self.sensor.get_angular_rate_noise().is_some() {
-            m_vec.push(self.get_right_ascension_rate().unwrap());
-            w_vec.push(1.0 / self.sensor.get_angular_rate_noise().unwrap().powi(2));
+        jacobian
+    }
+
+    fn apply_apparent_corrections(&self, xa_topo: &mut [f64; astro_func_interface::XA_TOPO_SIZE]) {
+        let (ra, dec) = self.get_apparent_angles(
+            xa_topo[astro_func_interface::XA_TOPO_RA],
+            xa_topo[astro_func_interface::XA_TOPO_DEC],
+        );
+        xa_topo[astro_func_interface::XA_TOPO_RA] = ra;
+        xa_topo[astro_func_interface::XA_TOPO_DEC] = dec;
+        if self.refraction_correction && !self.space_based {
+            let elevation = xa_topo[astro_func_interface::XA_TOPO_EL];
+            xa_topo[astro_func_interface::XA_TOPO_EL] = elevation + get_refraction_from_true_elevation(elevation);
         }
-        if self.get_declination_rate().is_some() && self.sensor.get_angular_rate_noise().is_some() {
-            m_vec.push(self.get_declination_rate().unwrap());
-            w_vec.push(1.0 / self.sensor.get_angular_rate_noise().unwrap().powi(2));
+    }
+
+    // Lifts the line of sight toward the local zenith by the refraction at its elevation, keeping the azimuth, so
+    // right ascension and declination carry the same shift as elevation
+    fn get_refracted_direction(&self, ra: f64, dec: f64, get_refraction: fn(f64) -> f64) -> (f64, f64) {
+        let [east, north, up] = astro_func_interface::get_topocentric_basis(self.epoch, self.observer_teme_position);
+        let direction = SphericalVector::new(1.0, ra, dec).to_cartesian();
+        let (e, n, u) = (east.dot(&direction), north.dot(&direction), up.dot(&direction));
+        let elevation = u.atan2((e * e + n * n).sqrt()).to_degrees();
+        let azimuth = e.atan2(n).to_degrees();
+        astro_func_interface::horizon_to_topocentric(
+            self.epoch,
+            self.observer_teme_position,
+            azimuth,
+            elevation + get_refraction(elevation),
+        )
+    }
+
+    fn get_geometric_components(&self, satellite_state: CartesianState) -> [f64; astro_func_interface::XA_TOPO_SIZE] {
+        match self.space_based {
+            true => get_relative_topocentric_components(
+                self.observer_teme_position,
+                self.get_observer_velocity(),
+                satellite_state,
+            ),
+            false => astro_func_interface::teme_to_topocentric_components(self.observer_teme_position, satellite_state),
         }
-        (m_vec, w_vec)
     }
 
     pub fn get_predicted_vector(&self, satellite: &Satellite) -> Result<Vec<f64>, String> {
-        match satellite.get_state_at_epoch(self.get_epoch()) {
-            Some(satellite_state) => {
-                let topo = astro_func_interface::teme_to_topocentric(self.observer_teme_position, satellite_state);
-                let mut predicted = vec![topo.get_right_ascension(), topo.get_declination()];
-                if self.get_range().is_some() {
-                    predicted.push(topo.get_range().unwrap());
+        Ok(self.get_predicted_vector_at_state(self.get_emission_state(satellite)?))
+    }
+
+    fn get_predicted_vector_at_state(&self, satellite_state: CartesianState) -> Vec<f64> {
+        let mut xa_topo = self.get_geometric_components(satellite_state);
+        self.apply_apparent_corrections(&mut xa_topo);
+        self.get_measurement_types()
+            .into_iter()
+            .map(|measurement_type| {
+                get_topocentric_component(&xa_topo, measurement_type) + self.sensor.get_bias(measurement_type)
+            })
+            .collect()
+    }
+
+    // Partials of each measurement with respect to the TEME position and velocity at the emission epoch
+    pub fn get_state_partials(&self, satellite: &Satellite) -> Result<(CartesianState, DMatrix<f64>), String> {
+        let satellite_state = self.get_emission_state(satellite)?;
+        Ok((satellite_state, self.get_state_partials_at_state(satellite_state)))
+    }
+
+    fn get_state_partials_at_state(&self, satellite_state: CartesianState) -> DMatrix<f64> {
+        let observer_velocity = self.get_observer_velocity();
+        let rho = satellite_state.position - self.observer_teme_position;
+        let rho_dot = satellite_state.velocity - observer_velocity;
+        let range = rho.get_magnitude();
+        let line_of_sight = rho * (1.0 / range);
+        let xy_squared = rho[0] * rho[0] + rho[1] * rho[1];
+        let zero = CartesianVector::new(0.0, 0.0, 0.0);
+
+        // Geometric angle partials, chained through aberration and refraction when the predictions apply them
+        let mut ra_partial = CartesianVector::new(-rho[1] / xy_squared, rho[0] / xy_squared, 0.0) * (180.0 / PI);
+        let mut dec_partial = CartesianVector::new(-rho[0] * rho[2], -rho[1] * rho[2], xy_squared)
+            * (180.0 / (PI * range * range * xy_squared.sqrt()));
+        if self.has_apparent_angle_corrections() {
+            let xa_topo = self.get_geometric_components(satellite_state);
+            let jacobian = self.get_apparent_angle_jacobian(
+                xa_topo[astro_func_interface::XA_TOPO_RA],
+                xa_topo[astro_func_interface::XA_TOPO_DEC],
+            );
+            (ra_partial, dec_partial) = (
+                ra_partial * jacobian[0][0] + dec_partial * jacobian[0][1],
+                ra_partial * jacobian[1][0] + dec_partial * jacobian[1][1],
+            );
+        }
+
+        let measurement_types = self.get_measurement_types();
+        let mut partials = DMatrix::zeros(measurement_types.len(), 6);
+        for (row, measurement_type) in measurement_types.into_iter().enumerate() {
+            let (d_position, d_velocity) = match measurement_type {
+                MeasurementType::RightAscension => (ra_partial, zero),
+                MeasurementType::Declination => (dec_partial, zero),
+                MeasurementType::Range => (line_of_sight, zero),
+                MeasurementType::RangeRate => {
+                    let range_rate = line_of_sight.dot(&rho_dot);
+                    ((rho_dot - line_of_sight * range_rate) * (1.0 / range), line_of_sight)
                 }
-                if self.get_range_rate().is_some() {
-                    predicted.push(topo.get_range_rate().unwrap());
+                MeasurementType::Azimuth | MeasurementType::Elevation => {
+                    let [east, north, up] =
+                        astro_func_interface::get_topocentric_basis(self.epoch, self.observer_teme_position);
+                    let (e, n, u) = (east.dot(&rho), north.dot(&rho), up.dot(&rho));
+                    let horizontal_squared = e * e + n * n;
+                    if measurement_type == MeasurementType::Azimuth {
+                        ((east * n - north * e) * (180.0 / (PI * horizontal_squared)), zero)
+                    } else {
+                        let mut scale = 180.0 / (PI * horizontal_squared.sqrt());
+                        if self.refraction_correction {
+                            scale *= 1.0 + get_refraction_slope(u.atan2(horizontal_squared.sqrt()).to_degrees());
+                        }
+                        ((up - line_of_sight * (u / range)) * scale, zero)
+                    }
                 }
-                if self.get_right_ascension_rate().is_some() {
-                    predicted.push(topo.get_right_ascension_rate().unwrap());
+                // Angular rates are differenced through the geometry, which needs no propagation
+                _ => self.get_differenced_partials(satellite_state, measurement_type),
+            };
+            for i in 0..3 {
+                partials[(row, i)] = d_position[i];
+                partials[(row, i + 3)] = d_velocity[i];
+            }
+        }
+        partials
+    }
+
+    fn get_differenced_partials(
+        &self,
+        satellite_state: CartesianState,
+        measurement_type: MeasurementType,
+    ) -> (CartesianVector, CartesianVector) {
+        let mut d_position = CartesianVector::new(0.0, 0.0, 0.0);
+        let mut d_velocity = CartesianVector::new(0.0, 0.0, 0.0);
+        for i in 0..6 {
+            let step = if i < 3 {
+                POSITION_DIFFERENCE_STEP
+            } else {
+                VELOCITY_DIFFERENCE_STEP
+            };
+            let mut plus = satellite_state;
+            let mut minus = satellite_state;
+            if i < 3 {
+                plus.position[i] += step;
+                minus.position[i] -= step;
+            } else {
+                plus.velocity[i - 3] += step;
+                minus.velocity[i - 3] -= step;
+            }
+            let difference = (get_topocentric_component(&self.get_geometric_components(plus), measurement_type)
+                - get_topocentric_component(&self.get_geometric_components(minus), measurement_type))
+                / (2.0 * step);
+            if i < 3 {
+                d_position[i] = difference;
+            } else {
+                d_velocity[i - 3] = difference;
+            }
+        }
+        (d_position, d_velocity)
+    }
+
+    pub fn get_measurement_differences(&self, minuend: &[f64], subtrahend: &[f64]) -> Vec<f64> {
+        self.get_measurement_types()
+            .iter()
+            .zip(minuend.iter().zip(subtrahend.iter()))
+            .map(|(measurement_type, (a, b))| match measurement_type {
+                // Wrap angles that are measured around the full circle
+                MeasurementType::RightAscension | MeasurementType::Azimuth => (a - b + 540.0).rem_euclid(360.0) - 180.0,
+                _ => a - b,
+            })
+            .collect()
+    }
+
+    pub fn set_sensor_bias(&mut self, measurement_type: MeasurementType, bias: f64) -> Result<(), String> {
+        self.sensor.set_bias(measurement_type, bias)
+    }
+
+    // Observed measurement with the current sensor bias removed, matching the biased predictions used in the fit
+    fn get_debiased_measurement(&self, measurement_type: MeasurementType) -> f64 {
+        self.observed_elements.get_measurement(measurement_type).unwrap() - self.sensor.get_bias(measurement_type)
+    }
+
+    fn get_observed_position(&self, satellite_position: CartesianVector) -> CartesianVector {
+        let sensor_to_satellite = satellite_position - self.observer_teme_position;
+        let predicted_range = sensor_to_satellite.get_magnitude();
+        match &self.observed_elements {
+            ObservedElements::Topocentric(_) => {
+                let mut ra = self.get_debiased_measurement(MeasurementType::RightAscension);
+                let mut dec = self.get_debiased_measurement(MeasurementType::Declination);
+                if self.refraction_correction && !self.space_based {
+                    (ra, dec) = self.get_refracted_direction(ra, dec, |elevation| {
+                        -get_refraction_from_apparent_elevation(elevation)
+                    });
                 }
-                if self.get_declination_rate().is_some() {
-                    predicted.push(topo.get_declination_rate().unwrap());
+                if self.aberration_correction {
+                    // Remove the aberration from the apparent direction before placing the satellite
+                    let (ra_delta, dec_delta) = self.get_aberration(ra, dec);
+                    ra -= ra_delta;
+                    dec -= dec_delta;
                 }
-                Ok(predicted)
+                let direction = SphericalVector::new(1.0, ra, dec).to_cartesian();
+                self.observer_teme_position + (direction * predicted_range)
             }
-            None => Err(format!(
-                "Error propagating satellite {} to {}",
-                satellite.get_satellite_id(),
-                self.get_epoch().to_iso()
-            )),
+            ObservedElements::Horizon(_) => {
+                let mut elevation = self.get_debiased_measurement(MeasurementType::Elevation);
+                if self.refraction_correction {
+                    elevation -= get_refraction_from_apparent_elevation(elevation);
+                }
+                let (ra, dec) = astro_func_interface::horizon_to_topocentric(
+                    self.epoch,
+                    self.observer_teme_position,
+                    self.get_debiased_measurement(MeasurementType::Azimuth),
+                    elevation,
+                );
+                self.observer_teme_position + (SphericalVector::new(1.0, ra, dec).to_cartesian() * predicted_range)
+            }
+            ObservedElements::Range(_) => {
+                let range = self.get_debiased_measurement(MeasurementType::Range);
+                self.observer_teme_position + (sensor_to_satellite * (range / predicted_range))
+            }
+            // A range rate carries no position information
+            ObservedElements::RangeRate(_) => satellite_position,
         }
     }
 }
 
+fn get_relative_topocentric_components(
+    observer_position: CartesianVector,
+    observer_velocity: CartesianVector,
+    observed_teme: CartesianState,
+) -> [f64; astro_func_interface::XA_TOPO_SIZE] {
+    // A moving observer has no local horizon, so only the inertial components are defined
+    let mut xa_topo = [f64::NAN; astro_func_interface::XA_TOPO_SIZE];
+    let rho = observed_teme.position - observer_position;
+    let rho_dot = observed_teme.velocity - observer_velocity;
+    let range = rho.get_magnitude();
+    let range_rate = (rho[0] * rho_dot[0] + rho[1] * rho_dot[1] + rho[2] * rho_dot[2]) / range;
+    let xy_squared = rho[0] * rho[0] + rho[1] * rho[1];
+    let spherical = rho.to_spherical();
+    xa_topo[astro_func_interface::XA_TOPO_RA] = spherical.get_right_ascension().rem_euclid(360.0);
+    xa_topo[astro_func_interface::XA_TOPO_DEC] = spherical.get_declination();
+    xa_topo[astro_func_interface::XA_TOPO_RANGE] = range;
+    xa_topo[astro_func_interface::XA_TOPO_RANGEDOT] = range_rate;
+    xa_topo[astro_func_interface::XA_TOPO_RADOT] =
+        ((rho[0] * rho_dot[1] - rho[1] * rho_dot[0]) / xy_squared).to_degrees();
+    xa_topo[astro_func_interface::XA_TOPO_DECDOT] =
+        ((rho_dot[2] - range_rate * rho[2] / range) / xy_squared.sqrt()).to_degrees();
+    xa_topo
+}
+
+fn get_velocity_aberration(observer_velocity: CartesianVector, ra: f64, dec: f64) -> (f64, f64) {
+    // First-order shift of the line of sight toward the direction of observer motion
+    let direction = SphericalVector::new(1.0, ra, dec).to_cartesian();
+    let beta = observer_velocity * (1.0 / SPEED_OF_LIGHT);
+    let cos_angle = direction[0] * beta[0] + direction[1] * beta[1] + direction[2] * beta[2];
+    let apparent = (direction + beta - direction * cos_angle).to_spherical();
+    (
+        (apparent.get_right_ascension() - ra + 540.0).rem_euclid(360.0) - 180.0,
+        apparent.get_declination() - dec,
+    )
+}
+
+fn get_refraction_from_true_elevation(elevation: f64) -> f64 {
+    // Saemundsson's formula for standard pressure and temperature, converted from arcminutes
+    if elevation < MIN_REFRACTION_ELEVATION {
+        return 0.0;
+    }
+    1.02 / (elevation + 10.3 / (elevation + 5.11)).to_radians().tan() / 60.0
+}
+
+fn get_refraction_slope(elevation: f64) -> f64 {
+    (get_refraction_from_true_elevation(elevation + ANGLE_DIFFERENCE_STEP)
+        - get_refraction_from_true_elevation(elevation - ANGLE_DIFFERENCE_STEP))
+        / (2.0 * ANGLE_DIFFERENCE_STEP)
+}
+
+fn get_refraction_from_apparent_elevation(elevation: f64) -> f64 {
+    // Invert the true elevation formula so predictions and residuals remain consistent
+    let mut true_elevation = elevation;
+    for _ in 0..MAX_REFRACTION_ITERATIONS {
+        true_elevation = elevation - get_refraction_from_true_elevation(true_elevation);
+    }
+    elevation - true_elevation
+}
+
+fn get_topocentric_component(
+    xa_topo: &[f64; astro_func_interface::XA_TOPO_SIZE],
+    measurement_type: MeasurementType,
+) -> f64 {
+    match measurement_type {
+        MeasurementType::RightAscension => xa_topo[astro_func_interface::XA_TOPO_RA],
+        MeasurementType::Declination => xa_topo[astro_func_interface::XA_TOPO_DEC],
+        MeasurementType::Range => xa_topo[astro_func_interface::XA_TOPO_RANGE],
+        MeasurementType::RangeRate => xa_topo[astro_func_interface::XA_TOPO_RANGEDOT],
+        MeasurementType::RightAscensionRate => xa_topo[astro_func_interface::XA_TOPO_RADOT],
+        MeasurementType::DeclinationRate => xa_topo[astro_func_interface::XA_TOPO_DECDOT],
+        MeasurementType::Azimuth => xa_topo[astro_func_interface::XA_TOPO_AZ],
+        MeasurementType::Elevation => xa_topo[astro_func_interface::XA_TOPO_EL],
+        MeasurementType::AzimuthRate => xa_topo[astro_func_interface::XA_TOPO_AZDOT],
+        MeasurementType::ElevationRate => xa_topo[astro_func_interface::XA_TOPO_ELDOT],
+    }
+}
+
 #[pymethods]
 impl Observation {
     #[new]
@@ -81,13 +598,75 @@ impl Observation {
         observed_teme_topocentric: TopocentricElements,
         observer_teme_position: CartesianVector,
     ) -> Self {
-        Self {
+        Self::from_observed_elements(
             sensor,
             epoch,
-            observed_teme_topocentric,
+            ObservedElements::Topocentric(observed_teme_topocentric),
             observer_teme_position,
-            observed_satellite_id: None,
-        }
+        )
+    }
+
+    #[staticmethod]
+    pub fn from_horizon(
+        sensor: Sensor,
+        epoch: Epoch,
+        observed_horizon: HorizonElements,
+        observer_teme_position: CartesianVector,
+    ) -> Self {
+        Self::from_observed_elements(
+            sensor,
+            epoch,
+            ObservedElements::Horizon(observed_horizon),
+            observer_teme_position,
+        )
+    }
+
+    #[staticmethod]
+    pub fn from_range(sensor: Sensor, epoch: Epoch, range: f64, observer_teme_position: CartesianVector) -> Self {
+        Self::from_observed_elements(sensor, epoch, ObservedElements::Range(range), observer_teme_position)
+    }
+
+    #[staticmethod]
+    pub fn from_range_rate(
+        sensor: Sensor,
+        epoch: Epoch,
+        range_rate: f64,
+        observer_teme_position: CartesianVector,
+    ) -> Self {
+        Self::from_observed_elements(
+            sensor,
+            epoch,
+            ObservedElements::RangeRate(range_rate),
+            observer_teme_position,
+        )
+    }
+
+    #[staticmethod]
+    pub fn from_space_observer(
+        sensor: Sensor,
+        epoch: Epoch,
+        observed_teme_topocentric: TopocentricElements,
+        observer: &Satellite,
+    ) -> PyResult<Self> {
+        let observer_state = match observer.get_state_at_epoch(epoch) {
+            Some(observer_state) => observer_state,
+            None => {
+                return Err(PyErr::new::<PyRuntimeError, _>(format!(
+                    "Error propagating observer {} to {}",
+                    observer.get_satellite_id(),
+                    epoch.to_iso()
+                )))
+            }
+        };
+        let mut ob = Self::from_observed_elements(
+            sensor,
+            epoch,
+            ObservedElements::Topocentric(observed_teme_topocentric),
+            observer_state.position,
+        );
+        ob.observer_teme_velocity = Some(observer_state.velocity);
+        ob.space_based = true;
+        Ok(ob)
     }
 
     #[getter]
@@ -101,33 +680,106 @@ impl Observation {
     }
 
     #[getter]
-    pub fn get_range(&self) -> Option<f64> {
-        self.observed_teme_topocentric.get_range()
+    pub fn get_observer_teme_position(&self) -> CartesianVector {
+        self.observer_teme_position
     }
 
     #[getter]
-    pub fn get_range_rate(&self) -> Option<f64> {
-        self.observed_teme_topocentric.get_range_rate()
+    pub fn get_observer_teme_velocity(&self) -> Option<CartesianVector> {
+        self.observer_teme_velocity
+    }
+
+    #[getter]
+    pub fn get_space_based(&self) -> bool {
+        self.space_based
     }
 
     #[getter]
-    pub fn get_right_ascension(&self) -> f64 {
-        self.observed_teme_topocentric.get_right_ascension()
+    pub fn get_light_time_correction(&self) -> bool {
+        self.light_time_correction
+    }
+
+    #[setter]
+    pub fn set_light_time_correction(&mut self, light_time_correction: bool) {
+        self.light_time_correction = light_time_correction;
     }
 
     #[getter]
-    pub fn get_declination(&self) -> f64 {
-        self.observed_teme_topocentric.get_declination()
+    pub fn get_aberration_correction(&self) -> bool {
+        self.aberration_correction
+    }
+
+    #[setter]
+    pub fn set_aberration_correction(&mut self, aberration_correction: bool) {
+        self.aberration_correction = aberration_correction;
+    }
+
+    #[getter]
+    pub fn get_refraction_correction(&self) -> bool {
+        self.refraction_correction
+    }
+
+    #[setter]
+    pub fn set_refraction_correction(&mut self, refraction_correction: bool) {
+        self.refraction_correction = refraction_correction;
+    }
+
+    #[getter(measurement_types)]
+    pub fn get_measurement_types_py(&self) -> Vec<MeasurementType> {
+        self.get_measurement_types()
+    }
+
+    #[getter]
+    pub fn get_range(&self) -> Option<f64> {
+        self.observed_elements.get_measurement(MeasurementType::Range)
+    }
+
+    #[getter]
+    pub fn get_range_rate(&self) -> Option<f64> {
+        self.observed_elements.get_measurement(MeasurementType::RangeRate)
+    }
+
+    #[getter(right_ascension)]
+    pub fn get_right_ascension_py(&self) -> PyResult<f64> {
+        self.get_right_ascension()
+            .ok_or_else(|| PyErr::new::<PyValueError, _>("Observation does not contain a right ascension"))
+    }
+
+    #[getter(declination)]
+    pub fn get_declination_py(&self) -> PyResult<f64> {
+        self.get_declination()
+            .ok_or_else(|| PyErr::new::<PyValueError, _>("Observation does not contain a declination"))
     }
 
     #[getter]
     pub fn get_right_ascension_rate(&self) -> Option<f64> {
-        self.observed_teme_topocentric.get_right_ascension_rate()
+        self.observed_elements
+            .get_measurement(MeasurementType::RightAscensionRate)
     }
 
     #[getter]
     pub fn get_declination_rate(&self) -> Option<f64> {
-        self.observed_teme_topocentric.get_declination_rate()
+        self.observed_elements.get_measurement(MeasurementType::DeclinationRate)
+    }
+
+    #[getter]
+    pub fn get_azimuth(&self) -> Option<f64> {
+        self.observed_elements.get_measurement(MeasurementType::Azimuth)
+    }
+
+    #[getter]
+    pub fn get_elevation(&self) -> Option<f64> {
+        self.observed_elements.get_measurement(MeasurementType::Elevation)
+    }
+
+    #[getter]
+    pub fn get_azimuth_rate(&self) -> Option<f64> {
+        self.observed_elements.get_measurement(MeasurementType::AzimuthRate)
+    }
+
+    #[getter]
+    pub fn get_elevation_rate(&self) -> Option<f64> {
+        self.observed_elements.get_measurement(MeasurementType::ElevationRate)
     }
 
     #[getter]
@@ -136,23 +788,125 @@ impl Observation {
     }
 
     #[setter]
-    pub fn set_range(&mut self, range: Option<f64>) {
-        self.observed_teme_topocentric.set_range(range);
+    pub fn set_range(&mut self, range: Option<f64>) -> PyResult<()> {
+        match (&mut self.observed_elements, range) {
+            (ObservedElements::Topocentric(topo), _) => topo.set_range(range),
+            (ObservedElements::Horizon(horizon), _) => horizon.set_range(range),
+            (ObservedElements::Range(observed_range), Some(range)) => *observed_range = range,
+            _ => return Err(PyErr::new::<PyValueError, _>("Observation does not support a range")),
+        }
+        Ok(())
+    }
+
+    #[setter]
+    pub fn set_range_rate(&mut self, range_rate: Option<f64>) -> PyResult<()> {
+        match (&mut self.observed_elements, range_rate) {
+            (ObservedElements::Topocentric(topo), _) => topo.set_range_rate(range_rate),
+            (ObservedElements::Horizon(horizon), _) => horizon.set_range_rate(range_rate),
+            (ObservedElements::RangeRate(observed_range_rate), Some(range_rate)) => *observed_range_rate = range_rate,
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not support a range rate",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    #[setter]
+    pub fn set_right_ascension(&mut self, right_ascension: f64) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Topocentric(topo) => topo.set_right_ascension(right_ascension),
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not contain a right ascension",
+                ))
+            }
+        }
+        Ok(())
     }
 
     #[setter]
-    pub fn set_range_rate(&mut self, range_rate: Option<f64>) {
-        self.observed_teme_topocentric.set_range_rate(range_rate);
+    pub fn set_declination(&mut self, declination: f64) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Topocentric(topo) => topo.set_declination(declination),
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not contain a declination",
+                ))
+            }
+        }
+        Ok(())
     }
 
     #[setter]
-    pub fn set_right_ascension(&mut self, right_ascension: f64) {
-        self.observed_teme_topocentric.set_right_ascension(right_ascension);
+    pub fn set_right_ascension_rate(&mut self, right_ascension_rate: Option<f64>) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Topocentric(topo) => topo.set_right_ascension_rate(right_ascension_rate),
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not contain a right ascension",
+                ))
+            }
+        }
+        Ok(())
     }
 
     #[setter]
-    pub fn set_declination(&mut self, declination: f64) {
-        self.observed_teme_topocentric.set_declination(declination);
+    pub fn set_declination_rate(&mut self, declination_rate: Option<f64>) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Topocentric(topo) => topo.set_declination_rate(declination_rate),
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not contain a declination",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    #[setter]
+    pub fn set_azimuth(&mut self, azimuth: f64) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Horizon(horizon) => horizon.set_azimuth(azimuth),
+            _ => return Err(PyErr::new::<PyValueError, _>("Observation does not contain an azimuth")),
+        }
+        Ok(())
+    }
+
+    #[setter]
+    pub fn set_elevation(&mut self, elevation: f64) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Horizon(horizon) => horizon.set_elevation(elevation),
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not contain an elevation",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    #[setter]
+    pub fn set_azimuth_rate(&mut self, azimuth_rate: Option<f64>) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Horizon(horizon) => horizon.set_azimuth_rate(azimuth_rate),
+            _ => return Err(PyErr::new::<PyValueError, _>("Observation does not contain an azimuth")),
+        }
+        Ok(())
+    }
+
+    #[setter]
+    pub fn set_elevation_rate(&mut self, elevation_rate: Option<f64>) -> PyResult<()> {
+        match &mut self.observed_elements {
+            ObservedElements::Horizon(horizon) => horizon.set_elevation_rate(elevation_rate),
+            _ => {
+                return Err(PyErr::new::<PyValueError, _>(
+                    "Observation does not contain an elevation",
+                ))
+            }
+        }
+        Ok(())
     }
 
     #[setter]
@@ -161,11 +915,9 @@ impl Observation {
     }
 
     pub fn get_residual(&self, satellite: &Satellite) -> Option<ObservationResidual> {
-        match satellite.get_state_at_epoch(self.epoch) {
-            Some(satellite_state) => {
-                let sensor_to_satellite = satellite_state.position - self.observer_teme_position;
-                let teme_estimate = self.observer_teme_position
-                    + (self.observed_teme_topocentric.get_observed_direction() * sensor_to_satellite.get_magnitude());
+        match self.get_emission_state(satellite) {
+            Ok(satellite_state) => {
+                let teme_estimate = self.get_observed_position(satellite_state.position);
 
                 let posvel_1 = [
                     satellite_state.position[0],
@@ -190,7 +942,59 @@ impl Observation {
                     self.epoch.days_since_1950,
                 )))
             }
-            None => None,
+            Err(_) => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::ReferenceFrame;
+
+    #[test]
+    fn test_apparent_angle_partials() {
+        let epoch = Epoch::from_days_since_1950(25000.5, TimeSystem::UTC);
+        let observer_teme_position = CartesianVector::new(6378.137, 0.0, 0.0);
+        let mut ob = Observation::new(
+            Sensor::new("SENSOR".to_string(), 0.001),
+            epoch,
+            TopocentricElements::new(0.0, 0.0),
+            observer_teme_position,
+        );
+        ob.set_refraction_correction(true);
+        ob.set_aberration_correction(true);
+        let satellite_state = CartesianState::new(
+            epoch,
+            observer_teme_position + CartesianVector::new(300.0, 1500.0, 800.0),
+            CartesianVector::new(0.0, 7.5, 0.0),
+            ReferenceFrame::TEME,
+        );
+
+        let partials = ob.get_state_partials_at_state(satellite_state);
+        for i in 0..3 {
+            let mut plus = satellite_state;
+            let mut minus = satellite_state;
+            plus.position[i] += POSITION_DIFFERENCE_STEP;
+            minus.position[i] -= POSITION_DIFFERENCE_STEP;
+            let difference = ob.get_measurement_differences(
+                &ob.get_predicted_vector_at_state(plus),
+                &ob.get_predicted_vector_at_state(minus),
+            );
+            for (row, value) in difference.into_iter().enumerate() {
+                let expected = value / (2.0 * POSITION_DIFFERENCE_STEP);
+                assert!((partials[(row, i)] - expected).abs() < 1e-6 * expected.abs().max(1e-3));
+            }
+        }
+
+        // The corrections move the angles enough that the geometric partials alone would not match
+        let geometric = Observation::new(
+            Sensor::new("SENSOR".to_string(), 0.001),
+            epoch,
+            TopocentricElements::new(0.0, 0.0),
+            observer_teme_position,
+        )
+        .get_state_partials_at_state(satellite_state);
+        assert!((geometric[(1, 2)] - partials[(1, 2)]).abs() > 1e-6);
+    }
+}
@@ -16,6 +16,7 @@ pub struct ObservationResidual {
     beta: f64,
     height: f64,
     angular_momentum: f64,
+    accepted: bool,
 }
 
 impl From<[f64; sat_state_interface::XA_DELTA_SIZE]> for ObservationResidual {
@@ -33,10 +34,17 @@ impl From<[f64; sat_state_interface::XA_DELTA_SIZE]> for ObservationResidual {
             beta: delta[sat_state_interface::XA_DELTA_BETA],
             height: delta[sat_state_interface::XA_DELTA_HEIGHT],
             angular_momentum: delta[sat_state_interface::XA_DELTA_ANGMOM],
+            accepted: true,
         }
     }
 }
 
+impl ObservationResidual {
+    pub fn set_accepted(&mut self, accepted: bool) {
+        self.accepted = accepted;
+    }
+}
+
 #[pymethods]
 impl ObservationResidual {
     #[getter]
@@ -98,4 +106,9 @@ impl ObservationResidual {
     pub fn get_angular_momentum(&self) -> f64 {
         self.angular_momentum
     }
+
+    #[getter]
+    pub fn get_accepted(&self) -> bool {
+        self.accepted
+    }
 }
@@ -0,0 +1,265 @@
+use super::Observation;
+use crate::bodies::{Observatory, Satellite, Sensor};
+use crate::elements::{CartesianVector, HorizonElements, TopocentricElements};
+use crate::enums::MeasurementType;
+use crate::saal::astro_func_interface;
+use crate::time::Epoch;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use std::f64::consts::PI;
+
+pub const DEFAULT_MIN_ELEVATION: f64 = 10.0;
+
+// SplitMix64 keeps simulations reproducible across platforms without an external dependency
+#[derive(Debug, Clone, PartialEq)]
+struct RandomNumberGenerator {
+    state: u64,
+}
+
+impl RandomNumberGenerator {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform sample on (0, 1]
+    fn next_uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    // Standard normal sample from the Box-Muller transform
+    fn next_gaussian(&mut self) -> f64 {
+        let u1 = self.next_uniform();
+        let u2 = self.next_uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+}
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservationSimulator {
+    seed: u64,
+    rng: RandomNumberGenerator,
+    min_elevation: f64,
+    max_range: Option<f64>,
+    add_noise: bool,
+    light_time_correction: bool,
+    aberration_correction: bool,
+    refraction_correction: bool,
+}
+
+impl ObservationSimulator {
+    fn get_template(&self, sensor: &Sensor, epoch: Epoch, observer_teme_position: CartesianVector) -> Observation {
+        // Placeholder values only select which measurements the sensor is able to report
+        let range = sensor.get_range_noise().map(|_| 0.0);
+        let range_rate = sensor.get_range_rate_noise().map(|_| 0.0);
+        let angular_rate = sensor.get_angular_rate_noise().map(|_| 0.0);
+        let mut ob = match sensor.get_horizon_angles() {
+            true => {
+                let mut horizon = HorizonElements::new(0.0, 0.0);
+                horizon.set_range(range);
+                horizon.set_range_rate(range_rate);
+                horizon.set_azimuth_rate(angular_rate);
+                horizon.set_elevation_rate(angular_rate);
+                Observation::from_horizon(sensor.clone(), epoch, horizon, observer_teme_position)
+            }
+            false => {
+                let mut topo = TopocentricElements::new(0.0, 0.0);
+                topo.set_range(range);
+                topo.set_range_rate(range_rate);
+                topo.set_right_ascension_rate(angular_rate);
+                topo.set_declination_rate(angular_rate);
+                Observation::new(sensor.clone(), epoch, topo, observer_teme_position)
+            }
+        };
+        ob.set_light_time_correction(self.light_time_correction);
+        ob.set_aberration_correction(self.aberration_correction);
+        ob.set_refraction_correction(self.refraction_correction);
+        ob
+    }
+
+    fn get_observation(
+        &mut self,
+        satellite: &Satellite,
+        sensor: &Sensor,
+        epoch: Epoch,
+        observer_teme_position: CartesianVector,
+    ) -> Result<Option<Observation>, String> {
+        let satellite_state = match satellite.get_state_at_epoch(epoch) {
+            Some(satellite_state) => satellite_state,
+            None => {
+                return Err(format!(
+                    "Error propagating satellite {} to {}",
+                    satellite.get_satellite_id(),
+                    epoch.to_iso()
+                ))
+            }
+        };
+        let horizon = astro_func_interface::teme_to_horizon(observer_teme_position, satellite_state);
+        // Sensor limits tighten, but never relax, the limits of the simulator
+        let min_elevation = sensor.get_min_elevation().unwrap_or(f64::MIN).max(self.min_elevation);
+        if horizon.get_elevation() < min_elevation {
+            return Ok(None);
+        }
+        let max_range = match (self.max_range, sensor.get_max_range()) {
+            (Some(simulator_range), Some(sensor_range)) => Some(simulator_range.min(sensor_range)),
+            (simulator_range, sensor_range) => simulator_range.or(sensor_range),
+        };
+        if let Some(max_range) = max_range {
+            if horizon.get_range().is_some_and(|range| range > max_range) {
+                return Ok(None);
+            }
+        }
+
+        // The observation's own measurement model keeps simulated values consistent with the estimator
+        let mut ob = self.get_template(sensor, epoch, observer_teme_position);
+        let predicted = ob.get_predicted_vector(satellite)?;
+        for (measurement_type, value) in ob.get_measurement_types().into_iter().zip(predicted) {
+            let noise = match (self.add_noise, sensor.get_noise(measurement_type)) {
+                (true, Some(sigma)) => sigma * self.rng.next_gaussian(),
+                _ => 0.0,
+            };
+            let value = value + noise;
+            match measurement_type {
+                MeasurementType::RightAscension => ob.set_right_ascension(value.rem_euclid(360.0)),
+                MeasurementType::Declination => ob.set_declination(value),
+                MeasurementType::Range => ob.set_range(Some(value)),
+                MeasurementType::RangeRate => ob.set_range_rate(Some(value)),
+                MeasurementType::RightAscensionRate => ob.set_right_ascension_rate(Some(value)),
+                MeasurementType::DeclinationRate => ob.set_declination_rate(Some(value)),
+                MeasurementType::Azimuth => ob.set_azimuth(value.rem_euclid(360.0)),
+                MeasurementType::Elevation => ob.set_elevation(value),
+                MeasurementType::AzimuthRate => ob.set_azimuth_rate(Some(value)),
+                MeasurementType::ElevationRate => ob.set_elevation_rate(Some(value)),
+            }
+            .map_err(|e| e.to_string())?;
+
+            // Biases are part of the simulated truth and are left for the estimator to recover
+            ob.set_sensor_bias(measurement_type, 0.0).ok();
+        }
+        ob.set_observed_satellite_id(satellite.get_satellite_id());
+        Ok(Some(ob))
+    }
+}
+
+#[pymethods]
+impl ObservationSimulator {
+    #[new]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: RandomNumberGenerator::new(seed),
+            min_elevation: DEFAULT_MIN_ELEVATION,
+            max_range: None,
+            add_noise: true,
+            light_time_correction: false,
+            aberration_correction: false,
+            refraction_correction: false,
+        }
+    }
+
+    #[getter]
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[setter]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = RandomNumberGenerator::new(seed);
+    }
+
+    #[getter]
+    pub fn get_min_elevation(&self) -> f64 {
+        self.min_elevation
+    }
+
+    #[setter]
+    pub fn set_min_elevation(&mut self, min_elevation: f64) {
+        self.min_elevation = min_elevation;
+    }
+
+    #[getter]
+    pub fn get_max_range(&self) -> Option<f64> {
+        self.max_range
+    }
+
+    #[setter]
+    pub fn set_max_range(&mut self, max_range: Option<f64>) {
+        self.max_range = max_range;
+    }
+
+    #[getter]
+    pub fn get_add_noise(&self) -> bool {
+        self.add_noise
+    }
+
+    #[setter]
+    pub fn set_add_noise(&mut self, add_noise: bool) {
+        self.add_noise = add_noise;
+    }
+
+    #[getter]
+    pub fn get_light_time_correction(&self) -> bool {
+        self.light_time_correction
+    }
+
+    #[setter]
+    pub fn set_light_time_correction(&mut self, light_time_correction: bool) {
+        self.light_time_correction = light_time_correction;
+    }
+
+    #[getter]
+    pub fn get_aberration_correction(&self) -> bool {
+        self.aberration_correction
+    }
+
+    #[setter]
+    pub fn set_aberration_correction(&mut self, aberration_correction: bool) {
+        self.aberration_correction = aberration_correction;
+    }
+
+    #[getter]
+    pub fn get_refraction_correction(&self) -> bool {
+        self.refraction_correction
+    }
+
+    #[setter]
+    pub fn set_refraction_correction(&mut self, refraction_correction: bool) {
+        self.refraction_correction = refraction_correction;
+    }
+
+    pub fn reset(&mut self) {
+        self.rng = RandomNumberGenerator::new(self.seed);
+    }
+
+    pub fn generate(
+        &mut self,
+        satellite: &Satellite,
+        observatories: Vec<Observatory>,
+        epochs: Vec<Epoch>,
+    ) -> PyResult<Vec<Observation>> {
+        let mut obs = Vec::new();
+        for epoch in epochs {
+            for observatory in observatories.iter() {
+                let observer_teme_position = observatory.get_state_at_epoch(epoch).position;
+                for sensor in observatory.get_sensors() {
+                    if let Some(ob) = self
+                        .get_observation(satellite, &sensor, epoch, observer_teme_position)
+                        .map_err(PyRuntimeError::new_err)?
+                    {
+                        obs.push(ob);
+                    }
+                }
+            }
+        }
+        Ok(obs)
+    }
+}
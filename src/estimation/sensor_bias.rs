@@ -0,0 +1,58 @@
+use crate::enums::MeasurementType;
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorBias {
+    sensor_id: Option<i32>,
+    sensor_name: String,
+    measurement_type: MeasurementType,
+    value: f64,
+    sigma: Option<f64>,
+}
+
+impl SensorBias {
+    pub fn new(
+        sensor_id: Option<i32>,
+        sensor_name: String,
+        measurement_type: MeasurementType,
+        value: f64,
+        sigma: Option<f64>,
+    ) -> Self {
+        Self {
+            sensor_id,
+            sensor_name,
+            measurement_type,
+            value,
+            sigma,
+        }
+    }
+}
+
+#[pymethods]
+impl SensorBias {
+    #[getter]
+    pub fn get_sensor_id(&self) -> Option<i32> {
+        self.sensor_id
+    }
+
+    #[getter]
+    pub fn get_sensor_name(&self) -> String {
+        self.sensor_name.clone()
+    }
+
+    #[getter]
+    pub fn get_measurement_type(&self) -> MeasurementType {
+        self.measurement_type
+    }
+
+    #[getter]
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    #[getter]
+    pub fn get_sigma(&self) -> Option<f64> {
+        self.sigma
+    }
+}
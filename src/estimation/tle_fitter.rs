@@ -0,0 +1,365 @@
+use crate::configs;
+use crate::elements::{CartesianState, CartesianVector, Ephemeris, TLE};
+use crate::enums::{Classification, KeplerianType, ReferenceFrame};
+use crate::propagation::ForceProperties;
+use crate::saal::sat_state_interface;
+use crate::time::{Epoch, EpochRange, TimeSpan};
+use nalgebra::{DMatrix, DVector};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+pub const DEFAULT_MAX_FIT_ITERATIONS: usize = 20;
+pub const DEFAULT_FIT_RMS_TOLERANCE: f64 = 1e-4;
+pub const MIN_FIT_STATES: usize = 3;
+const MAX_STEP_HALVINGS: usize = 10;
+
+type FitResiduals = (DVector<f64>, DMatrix<f64>);
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TLEFitter {
+    satellite_id: i32,
+    states: Vec<CartesianState>,
+    output_keplerian_type: KeplerianType,
+    use_drag: bool,
+    use_srp: bool,
+    max_iterations: usize,
+    rms_tolerance: f64,
+    current_estimate: TLE,
+    iteration_count: usize,
+    converged: bool,
+    rms: Option<f64>,
+}
+
+fn get_posvel(state: &CartesianState) -> [f64; 6] {
+    [
+        state.position[0],
+        state.position[1],
+        state.position[2],
+        state.velocity[0],
+        state.velocity[1],
+        state.velocity[2],
+    ]
+}
+
+#[pymethods]
+impl TLEFitter {
+    #[new]
+    pub fn new(satellite_id: i32, states: Vec<CartesianState>) -> PyResult<Self> {
+        if states.len() < MIN_FIT_STATES {
+            return Err(PyErr::new::<PyValueError, _>(format!(
+                "At least {} states are required to fit a TLE",
+                MIN_FIT_STATES
+            )));
+        }
+
+        // SGP4 propagates in TEME, so the reference states are compared in the same frame
+        let mut states: Vec<CartesianState> = states
+            .into_iter()
+            .map(|state| state.to_frame(ReferenceFrame::TEME))
+            .collect();
+        states.sort_by(|a, b| a.epoch.partial_cmp(&b.epoch).unwrap());
+        Ok(Self {
+            satellite_id,
+            current_estimate: get_seeded_estimate(satellite_id, &states, KeplerianType::MeanKozaiGP, false, false),
+            states,
+            output_keplerian_type: KeplerianType::MeanKozaiGP,
+            use_drag: false,
+            use_srp: false,
+            max_iterations: DEFAULT_MAX_FIT_ITERATIONS,
+            rms_tolerance: DEFAULT_FIT_RMS_TOLERANCE,
+            iteration_count: 0,
+            converged: false,
+            rms: None,
+        })
+    }
+
+    #[staticmethod]
+    pub fn from_ephemeris(ephemeris: &Ephemeris, start: Epoch, end: Epoch, step: TimeSpan) -> PyResult<Self> {
+        if step.in_seconds() <= 0.0 {
+            return Err(PyErr::new::<PyValueError, _>("Sample step must be positive"));
+        }
+        let mut states = Vec::new();
+        for epoch in &EpochRange::new(start, end, step)? {
+            match ephemeris.get_state_at_epoch(epoch) {
+                Some(state) => states.push(state),
+                None => {
+                    return Err(PyErr::new::<PyValueError, _>(format!(
+                        "Ephemeris for satellite {} does not cover {}",
+                        ephemeris.get_satellite_id(),
+                        epoch.to_iso()
+                    )))
+                }
+            }
+        }
+        Self::new(ephemeris.get_satellite_id(), states)
+    }
+
+    #[getter]
+    pub fn get_satellite_id(&self) -> i32 {
+        self.satellite_id
+    }
+
+    #[getter]
+    pub fn get_states(&self) -> Vec<CartesianState> {
+        self.states.clone()
+    }
+
+    #[getter]
+    pub fn get_output_type(&self) -> KeplerianType {
+        self.output_keplerian_type
+    }
+
+    #[setter]
+    pub fn set_output_type(&mut self, output_keplerian_type: KeplerianType) -> PyResult<()> {
+        if output_keplerian_type == KeplerianType::Osculating {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Osculating elements cannot be fit as a TLE",
+            ));
+        }
+        self.output_keplerian_type = output_keplerian_type;
+        self.reset();
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_estimate_drag(&self) -> bool {
+        self.use_drag
+    }
+
+    #[setter]
+    pub fn set_estimate_drag(&mut self, use_drag: bool) {
+        self.use_drag = use_drag;
+        self.reset();
+    }
+
+    #[getter]
+    pub fn get_estimate_srp(&self) -> bool {
+        self.use_srp
+    }
+
+    #[setter]
+    pub fn set_estimate_srp(&mut self, use_srp: bool) {
+        self.use_srp = use_srp;
+        self.reset();
+    }
+
+    #[getter]
+    pub fn get_max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    #[setter]
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    #[getter]
+    pub fn get_rms_tolerance(&self) -> f64 {
+        self.rms_tolerance
+    }
+
+    #[setter]
+    pub fn set_rms_tolerance(&mut self, rms_tolerance: f64) {
+        self.rms_tolerance = rms_tolerance;
+    }
+
+    #[getter]
+    pub fn get_current_estimate(&self) -> TLE {
+        self.current_estimate.clone()
+    }
+
+    #[getter]
+    pub fn get_iteration_count(&self) -> usize {
+        self.iteration_count
+    }
+
+    #[getter]
+    pub fn get_converged(&self) -> bool {
+        self.converged
+    }
+
+    #[getter]
+    pub fn get_rms(&self) -> Option<f64> {
+        self.rms
+    }
+
+    #[getter]
+    pub fn get_residuals(&self) -> PyResult<Vec<(Epoch, CartesianVector, CartesianVector)>> {
+        self.states
+            .iter()
+            .map(|state| {
+                let predicted = self.current_estimate.get_cartesian_state_at_epoch(state.epoch)?;
+                let xa_delta = sat_state_interface::get_relative_state(
+                    &get_posvel(&predicted),
+                    &get_posvel(state),
+                    state.epoch.days_since_1950,
+                );
+                Ok((
+                    state.epoch,
+                    CartesianVector::new(
+                        xa_delta[sat_state_interface::XA_DELTA_PRADIAL],
+                        xa_delta[sat_state_interface::XA_DELTA_PINTRCK],
+                        xa_delta[sat_state_interface::XA_DELTA_PCRSSTRCK],
+                    ),
+                    CartesianVector::new(
+                        xa_delta[sat_state_interface::XA_DELTA_VRADIAL],
+                        xa_delta[sat_state_interface::XA_DELTA_VINTRCK],
+                        xa_delta[sat_state_interface::XA_DELTA_VCRSSTRCK],
+                    ),
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(PyRuntimeError::new_err)
+    }
+
+    pub fn solve(&mut self) -> PyResult<()> {
+        if self.use_srp && self.output_keplerian_type != KeplerianType::MeanBrouwerXP {
+            return Err(PyErr::new::<PyValueError, _>(
+                "AGOM can only be estimated for MeanBrouwerXP element sets",
+            ));
+        }
+        self.iteration_count = 0;
+        self.converged = false;
+        self.rms = None;
+        for _ in 0..self.max_iterations {
+            self.iterate().map_err(PyRuntimeError::new_err)?;
+            if self.converged {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TLEFitter {
+    fn reset(&mut self) {
+        self.current_estimate = get_seeded_estimate(
+            self.satellite_id,
+            &self.states,
+            self.output_keplerian_type,
+            self.use_drag,
+            self.use_srp,
+        );
+        self.iteration_count = 0;
+        self.converged = false;
+        self.rms = None;
+    }
+
+    fn iterate(&mut self) -> Result<(), String> {
+        self.iteration_count += 1;
+        let (r, h) = self.get_residuals_and_partials(&self.current_estimate, true)?;
+        let rms = get_rms(&r);
+        if let Some(previous_rms) = self.rms {
+            if (previous_rms - rms).abs() <= self.rms_tolerance * previous_rms {
+                self.rms = Some(rms);
+                self.converged = true;
+                return Ok(());
+            }
+        }
+        self.rms = Some(rms);
+
+        let n = h.transpose() * &h;
+        let b = h.transpose() * &r;
+        let mut delta_x = match n.cholesky() {
+            Some(cholesky) => cholesky.solve(&b),
+            None => return Err("Normal equations are singular".to_string()),
+        };
+
+        // Halve the correction until it produces a valid orbit that reduces the residuals
+        for _ in 0..MAX_STEP_HALVINGS {
+            if let Some(trial) = self.get_valid_correction(&delta_x) {
+                let (trial_r, _) = self.get_residuals_and_partials(&trial, false)?;
+                if get_rms(&trial_r) < rms {
+                    self.current_estimate = trial;
+                    return Ok(());
+                }
+            }
+            delta_x *= 0.5;
+        }
+
+        // A stalled fit has not met the tolerance, so it is reported rather than marked converged
+        Err("Fit stalled: no step along the correction reduces the residuals".to_string())
+    }
+
+    fn get_valid_correction(&self, delta_x: &DVector<f64>) -> Option<TLE> {
+        let epoch = self.current_estimate.get_epoch();
+        let elements = self.current_estimate.get_equinoctial_elements_at_epoch(epoch);
+        let a_f = elements[0] + delta_x[0];
+        let a_g = elements[1] + delta_x[1];
+        let mean_motion = elements[5] + delta_x[5];
+        match a_f.hypot(a_g) < 1.0 && mean_motion > 0.0 && delta_x.iter().all(|dx| dx.is_finite()) {
+            true => Some(
+                self.current_estimate
+                    .new_with_delta_x(delta_x, self.use_drag, self.use_srp),
+            ),
+            false => None,
+        }
+    }
+
+    // Position residuals against the reference states and, optionally, their partials
+    fn get_residuals_and_partials(&self, tle: &TLE, with_partials: bool) -> Result<FitResiduals, String> {
+        let n = 6 + self.use_drag as usize + self.use_srp as usize;
+        let perturbations = match with_partials {
+            true => Some(tle.get_force_perturbations(self.use_drag, self.use_srp)?),
+            false => None,
+        };
+        let rows = self
+            .states
+            .par_iter()
+            .map(|state| {
+                let predicted = tle.get_cartesian_state_at_epoch(state.epoch)?;
+                let partials = match perturbations.as_ref() {
+                    Some(perturbations) => tle.get_state_partials(&predicted, perturbations)?,
+                    None => DMatrix::zeros(6, n),
+                };
+                Ok((state.position - predicted.position, partials))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let m = 3 * rows.len();
+        let mut r = DVector::zeros(m);
+        let mut h = DMatrix::zeros(m, n);
+        for (i, (residual, partials)) in rows.iter().enumerate() {
+            for j in 0..3 {
+                r[3 * i + j] = residual[j];
+            }
+            h.view_mut((3 * i, 0), (3, n)).copy_from(&partials.rows(0, 3));
+        }
+        Ok((r, h))
+    }
+}
+
+fn get_rms(r: &DVector<f64>) -> f64 {
+    (r.norm_squared() / (r.len() / 3) as f64).sqrt()
+}
+
+// Osculating elements of the last state serve as the first guess of the mean elements at the fit epoch
+fn get_seeded_estimate(
+    satellite_id: i32,
+    states: &[CartesianState],
+    output_keplerian_type: KeplerianType,
+    use_drag: bool,
+    use_srp: bool,
+) -> TLE {
+    let mut force_properties = ForceProperties::default();
+    match use_drag {
+        true => force_properties.set_drag_coefficient(configs::DEFAULT_DRAG_TERM),
+        false => force_properties.set_drag_coefficient(0.0),
+    }
+    match use_srp {
+        true => force_properties.set_srp_coefficient(configs::DEFAULT_SRP_TERM),
+        false => force_properties.set_srp_coefficient(0.0),
+    }
+    let mut keplerian_state = states.last().unwrap().to_keplerian();
+    keplerian_state.set_type(output_keplerian_type);
+    TLE::new(
+        satellite_id,
+        None,
+        Classification::Unclassified,
+        "".to_string(),
+        keplerian_state,
+        force_properties,
+    )
+}
@@ -0,0 +1,148 @@
+use super::Observation;
+use crate::bodies::Sensor;
+use crate::time::Epoch;
+use nalgebra::{DMatrix, DVector};
+use pyo3::prelude::*;
+
+const FIT_SINGULAR_TOLERANCE: f64 = 1e-12;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tracklet {
+    observations: Vec<Observation>,
+    observation: Observation,
+    right_ascension_acceleration: Option<f64>,
+    declination_acceleration: Option<f64>,
+    rms: f64,
+}
+
+impl Tracklet {
+    pub fn from_observations(observations: Vec<Observation>, use_quadratic_fit: bool) -> Result<Self, String> {
+        if observations.len() < 2 {
+            return Err("A tracklet requires at least two observations".to_string());
+        }
+
+        // The middle detection anchors the fit so the observer position is known at the reference epoch
+        let mut observation = observations[observations.len() / 2].clone();
+        let epoch = observation.get_epoch();
+        let ra_0 = observation.get_right_ascension().unwrap();
+        let mut times = Vec::with_capacity(observations.len());
+        let mut ras = Vec::with_capacity(observations.len());
+        let mut decs = Vec::with_capacity(observations.len());
+        for ob in observations.iter() {
+            let (ra, dec) = match (ob.get_right_ascension(), ob.get_declination()) {
+                (Some(ra), Some(dec)) => (ra, dec),
+                _ => return Err("Tracklets require right ascension and declination observations".to_string()),
+            };
+            times.push((ob.get_epoch() - epoch).in_seconds());
+            ras.push(ra_0 + (ra - ra_0 + 540.0).rem_euclid(360.0) - 180.0);
+            decs.push(dec);
+        }
+
+        // Quadratic terms need a redundant observation to be meaningful
+        let order = if use_quadratic_fit && observations.len() > 3 {
+            3
+        } else {
+            2
+        };
+        let design = DMatrix::from_fn(times.len(), order, |i, j| match j {
+            0 => 1.0,
+            1 => times[i],
+            _ => 0.5 * times[i] * times[i],
+        });
+        let svd = design.clone().svd(true, true);
+        let ra_coefficients = svd.solve(&DVector::from_vec(ras.clone()), FIT_SINGULAR_TOLERANCE)?;
+        let dec_coefficients = svd.solve(&DVector::from_vec(decs.clone()), FIT_SINGULAR_TOLERANCE)?;
+        if svd.rank(FIT_SINGULAR_TOLERANCE) < order {
+            return Err("Tracklet observations do not span enough time to fit a motion model".to_string());
+        }
+
+        let ra_residuals = DVector::from_vec(ras) - &design * &ra_coefficients;
+        let dec_residuals = DVector::from_vec(decs) - &design * &dec_coefficients;
+        let dec_0 = dec_coefficients[0];
+        let cos_dec = dec_0.to_radians().cos();
+        let rss = ra_residuals.iter().map(|r| (r * cos_dec).powi(2)).sum::<f64>() + dec_residuals.norm_squared();
+
+        observation
+            .set_right_ascension(ra_coefficients[0].rem_euclid(360.0))
+            .unwrap();
+        observation.set_declination(dec_0).unwrap();
+        observation.set_right_ascension_rate(Some(ra_coefficients[1])).unwrap();
+        observation.set_declination_rate(Some(dec_coefficients[1])).unwrap();
+        Ok(Self {
+            rms: (rss / times.len() as f64).sqrt(),
+            right_ascension_acceleration: (order > 2).then(|| ra_coefficients[2]),
+            declination_acceleration: (order > 2).then(|| dec_coefficients[2]),
+            observations,
+            observation,
+        })
+    }
+}
+
+#[pymethods]
+impl Tracklet {
+    #[getter]
+    pub fn get_sensor(&self) -> Sensor {
+        self.observation.get_sensor()
+    }
+
+    #[getter]
+    pub fn get_epoch(&self) -> Epoch {
+        self.observation.get_epoch()
+    }
+
+    #[getter]
+    pub fn get_start(&self) -> Epoch {
+        self.observations[0].get_epoch()
+    }
+
+    #[getter]
+    pub fn get_end(&self) -> Epoch {
+        self.observations[self.observations.len() - 1].get_epoch()
+    }
+
+    #[getter]
+    pub fn get_observations(&self) -> Vec<Observation> {
+        self.observations.clone()
+    }
+
+    #[getter]
+    pub fn get_observation(&self) -> Observation {
+        self.observation.clone()
+    }
+
+    #[getter]
+    pub fn get_right_ascension(&self) -> f64 {
+        self.observation.get_right_ascension().unwrap()
+    }
+
+    #[getter]
+    pub fn get_declination(&self) -> f64 {
+        self.observation.get_declination().unwrap()
+    }
+
+    #[getter]
+    pub fn get_right_ascension_rate(&self) -> f64 {
+        self.observation.get_right_ascension_rate().unwrap()
+    }
+
+    #[getter]
+    pub fn get_declination_rate(&self) -> f64 {
+        self.observation.get_declination_rate().unwrap()
+    }
+
+    #[getter]
+    pub fn get_right_ascension_acceleration(&self) -> Option<f64> {
+        self.right_ascension_acceleration
+    }
+
+    #[getter]
+    pub fn get_declination_acceleration(&self) -> Option<f64> {
+        self.declination_acceleration
+    }
+
+    #[getter]
+    pub fn get_rms(&self) -> f64 {
+        self.rms
+    }
+}
@@ -0,0 +1,191 @@
+use super::observation::SensorKey;
+use super::{Observation, Tracklet};
+use crate::enums::MeasurementType;
+use crate::time::TimeSpan;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+pub const DEFAULT_MAX_TRACKLET_GAP_SECONDS: f64 = 300.0;
+pub const DEFAULT_MAX_ANGULAR_RATE: f64 = 1.0;
+pub const DEFAULT_RATE_TOLERANCE: f64 = 0.002;
+pub const DEFAULT_MIN_TRACKLET_OBSERVATIONS: usize = 3;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackletBuilder {
+    max_time_gap: TimeSpan,
+    max_angular_rate: f64,
+    rate_tolerance: f64,
+    min_observations: usize,
+    use_quadratic_fit: bool,
+}
+
+impl Default for TrackletBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrackletBuilder {
+    fn get_sensor_tracks(&self, mut obs: Vec<Observation>) -> Vec<Vec<Observation>> {
+        obs.sort_by_key(|ob| ob.get_epoch());
+        let max_gap = self.max_time_gap.in_seconds();
+        let mut open_tracks: Vec<Vec<Observation>> = Vec::new();
+        let mut closed_tracks = Vec::new();
+        for ob in obs {
+            // Tracks that can no longer be extended are set aside
+            let (stale, active): (Vec<_>, Vec<_>) = open_tracks
+                .into_iter()
+                .partition(|track| (ob.get_epoch() - track[track.len() - 1].get_epoch()).in_seconds() > max_gap);
+            closed_tracks.extend(stale);
+            open_tracks = active;
+
+            let mut best: Option<(usize, f64, f64)> = None;
+            for (i, track) in open_tracks.iter().enumerate() {
+                if let Some((mismatch, separation)) = self.get_association_score(track, &ob) {
+                    if best.is_none_or(|(_, best_mismatch, best_separation)| {
+                        (mismatch, separation) < (best_mismatch, best_separation)
+                    }) {
+                        best = Some((i, mismatch, separation));
+                    }
+                }
+            }
+            match best {
+                Some((i, _, _)) => open_tracks[i].push(ob),
+                None => open_tracks.push(vec![ob]),
+            }
+        }
+        closed_tracks.extend(open_tracks);
+        closed_tracks.sort_by_key(|track| track[0].get_epoch());
+        closed_tracks
+    }
+
+    // Rate mismatch and angular separation of a detection appended to a track
+    fn get_association_score(&self, track: &[Observation], ob: &Observation) -> Option<(f64, f64)> {
+        let last = &track[track.len() - 1];
+        let dt = (ob.get_epoch() - last.get_epoch()).in_seconds();
+        if dt <= 0.0 {
+            return None;
+        }
+        let (ra_rate, dec_rate) = get_angular_rates(last, ob, dt);
+        let rate = ra_rate.hypot(dec_rate);
+        if rate > self.max_angular_rate {
+            return None;
+        }
+        let mismatch = match track.len() {
+            1 => 0.0,
+            n => {
+                let previous = &track[n - 2];
+                let (previous_ra_rate, previous_dec_rate) =
+                    get_angular_rates(previous, last, (last.get_epoch() - previous.get_epoch()).in_seconds());
+                (ra_rate - previous_ra_rate).hypot(dec_rate - previous_dec_rate)
+            }
+        };
+        if mismatch > self.rate_tolerance {
+            return None;
+        }
+        Some((mismatch, rate * dt))
+    }
+}
+
+// On-sky rates with right ascension scaled to a great-circle rate
+fn get_angular_rates(first: &Observation, second: &Observation, dt: f64) -> (f64, f64) {
+    let (ra_1, dec_1) = (first.get_right_ascension().unwrap(), first.get_declination().unwrap());
+    let (ra_2, dec_2) = (second.get_right_ascension().unwrap(), second.get_declination().unwrap());
+    let cos_dec = (0.5 * (dec_1 + dec_2)).to_radians().cos();
+    let d_ra = (ra_2 - ra_1 + 540.0).rem_euclid(360.0) - 180.0;
+    (d_ra * cos_dec / dt, (dec_2 - dec_1) / dt)
+}
+
+#[pymethods]
+impl TrackletBuilder {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            max_time_gap: TimeSpan::from_seconds(DEFAULT_MAX_TRACKLET_GAP_SECONDS),
+            max_angular_rate: DEFAULT_MAX_ANGULAR_RATE,
+            rate_tolerance: DEFAULT_RATE_TOLERANCE,
+            min_observations: DEFAULT_MIN_TRACKLET_OBSERVATIONS,
+            use_quadratic_fit: false,
+        }
+    }
+
+    #[getter]
+    pub fn get_max_time_gap(&self) -> TimeSpan {
+        self.max_time_gap
+    }
+
+    #[setter]
+    pub fn set_max_time_gap(&mut self, max_time_gap: TimeSpan) {
+        self.max_time_gap = max_time_gap;
+    }
+
+    #[getter]
+    pub fn get_max_angular_rate(&self) -> f64 {
+        self.max_angular_rate
+    }
+
+    #[setter]
+    pub fn set_max_angular_rate(&mut self, max_angular_rate: f64) {
+        self.max_angular_rate = max_angular_rate;
+    }
+
+    #[getter]
+    pub fn get_rate_tolerance(&self) -> f64 {
+        self.rate_tolerance
+    }
+
+    #[setter]
+    pub fn set_rate_tolerance(&mut self, rate_tolerance: f64) {
+        self.rate_tolerance = rate_tolerance;
+    }
+
+    #[getter]
+    pub fn get_min_observations(&self) -> usize {
+        self.min_observations
+    }
+
+    #[setter]
+    pub fn set_min_observations(&mut self, min_observations: usize) -> PyResult<()> {
+        if min_observations < 2 {
+            return Err(PyErr::new::<PyValueError, _>(
+                "Tracklets require at least two observations",
+            ));
+        }
+        self.min_observations = min_observations;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn get_use_quadratic_fit(&self) -> bool {
+        self.use_quadratic_fit
+    }
+
+    #[setter]
+    pub fn set_use_quadratic_fit(&mut self, use_quadratic_fit: bool) {
+        self.use_quadratic_fit = use_quadratic_fit;
+    }
+
+    pub fn build(&self, obs: Vec<Observation>) -> Vec<Tracklet> {
+        // Only angles-only detections from the same sensor can share a tracklet
+        let mut sensor_obs: BTreeMap<SensorKey, Vec<Observation>> = BTreeMap::new();
+        for ob in obs {
+            if ob.get_measurement_types().contains(&MeasurementType::RightAscension) {
+                sensor_obs.entry(ob.get_sensor_key()).or_default().push(ob);
+            }
+        }
+
+        let mut tracklets = Vec::new();
+        for (_, obs) in sensor_obs {
+            for track in self.get_sensor_tracks(obs) {
+                if track.len() >= self.min_observations {
+                    if let Ok(tracklet) = Tracklet::from_observations(track, self.use_quadratic_fit) {
+                        tracklets.push(tracklet);
+                    }
+                }
+            }
+        }
+        tracklets
+    }
+}
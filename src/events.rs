@@ -1,8 +1,10 @@
 mod close_approach;
 mod close_approach_report;
+mod maneuver_event;
 
 pub use close_approach::CloseApproach;
 pub use close_approach_report::CloseApproachReport;
+pub use maneuver_event::ManeuverEvent;
 
 use pyo3::prelude::*;
 use pyo3::py_run;
@@ -11,6 +13,7 @@ pub fn register_events(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let events = PyModule::new(parent_module.py(), "events")?;
     events.add_class::<CloseApproach>()?;
     events.add_class::<CloseApproachReport>()?;
+    events.add_class::<ManeuverEvent>()?;
     py_run!(
         parent_module.py(),
         events,
@@ -0,0 +1,44 @@
+use crate::elements::CartesianVector;
+use crate::time::Epoch;
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManeuverEvent {
+    satellite_id: i32,
+    epoch: Epoch,
+    delta_v: Option<CartesianVector>,
+}
+
+#[pymethods]
+impl ManeuverEvent {
+    #[new]
+    #[pyo3(signature = (satellite_id, epoch, delta_v = None))]
+    pub fn new(satellite_id: i32, epoch: Epoch, delta_v: Option<CartesianVector>) -> Self {
+        Self {
+            satellite_id,
+            epoch,
+            delta_v,
+        }
+    }
+
+    #[getter]
+    pub fn get_satellite_id(&self) -> i32 {
+        self.satellite_id
+    }
+
+    #[getter]
+    pub fn get_epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    #[getter]
+    pub fn get_delta_v(&self) -> Option<CartesianVector> {
+        self.delta_v
+    }
+
+    #[getter]
+    pub fn get_delta_v_magnitude(&self) -> Option<f64> {
+        self.delta_v.map(|delta_v| delta_v.get_magnitude())
+    }
+}
@@ -1,9 +1,8 @@
 use super::ForceProperties;
-use crate::elements::{CartesianState, CartesianVector, KeplerianState, TLE};
+use crate::elements::{CartesianState, CartesianVector, EquinoctialElements, ForcePerturbations, KeplerianState, TLE};
 use crate::enums::{ReferenceFrame, TimeSystem};
-use crate::estimation::Observation;
 use crate::saal::{sat_state_interface, sgp4_prop_interface};
-use crate::time::{Epoch, TimeSpan};
+use crate::time::{Epoch, EpochRange, TimeSpan};
 use nalgebra::{DMatrix, DVector};
 use pyo3::prelude::*;
 
@@ -111,22 +110,14 @@ impl InertialPropagator {
         end_epoch: Epoch,
         step_size: TimeSpan,
     ) -> Option<Vec<CartesianState>> {
-        let mut states = Vec::new();
-        let mut current_epoch = start_epoch;
-
-        while current_epoch <= end_epoch {
-            let result = self.get_cartesian_state_at_epoch(current_epoch);
-            let state = match result {
-                Some(state) => state,
-                None => {
-                    states.clear();
-                    break;
-                }
-            };
-            states.push(state);
-            current_epoch += step_size;
-        }
+        self.get_ephemeris_over(&EpochRange::new(start_epoch, end_epoch, step_size).ok()?)
+    }
 
+    pub fn get_ephemeris_over(&self, epochs: &EpochRange) -> Option<Vec<CartesianState>> {
+        let states = epochs
+            .iter()
+            .map(|epoch| self.get_cartesian_state_at_epoch(epoch))
+            .collect::<Option<Vec<_>>>()?;
         match states.is_empty() {
             true => None,
             false => Some(states),
@@ -154,9 +145,27 @@ impl InertialPropagator {
         }
     }
 
-    pub fn get_jacobian(&self, ob: &Observation, use_drag: bool, use_srp: bool) -> Result<DMatrix<f64>, String> {
+    pub fn get_equinoctial_elements_at_epoch(&self, epoch: Epoch) -> Result<EquinoctialElements, String> {
+        match &self.tle {
+            Some(tle) => Ok(tle.get_equinoctial_elements_at_epoch(epoch)),
+            None => Err("Propagation of osculating elements has not been implemented".to_string()),
+        }
+    }
+
+    pub fn get_force_perturbations(&self, use_drag: bool, use_srp: bool) -> Result<ForcePerturbations, String> {
+        match &self.tle {
+            Some(tle) => tle.get_force_perturbations(use_drag, use_srp),
+            None => Err("Propagation of osculating elements has not been implemented".to_string()),
+        }
+    }
+
+    pub fn get_state_partials(
+        &self,
+        state: &CartesianState,
+        perturbations: &ForcePerturbations,
+    ) -> Result<DMatrix<f64>, String> {
         match &self.tle {
-            Some(tle) => tle.get_jacobian(ob, use_drag, use_srp),
+            Some(tle) => tle.get_state_partials(state, perturbations),
             None => Err("Propagation of osculating elements has not been implemented".to_string()),
         }
     }
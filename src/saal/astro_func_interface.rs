@@ -5,7 +5,9 @@ use std::os::raw::c_char;
 
 use crate::elements::CartesianState;
 use crate::elements::CartesianVector;
+use crate::elements::HorizonElements;
 use crate::elements::TopocentricElements;
+use crate::time::Epoch;
 
 use super::time_func_interface;
 use super::GetSetString;
@@ -625,6 +627,26 @@ pub fn teme_to_efg(ds50utc: f64, pos_teme: &[f64; 3], vel_teme: &[f64; 3]) -> ([
     (pos_efg, vel_efg)
 }
 
+#[inline]
+pub fn teme_to_efg_at_angle(theta_g: f64, pos_teme: &[f64; 3], vel_teme: &[f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let mut pos_efg = [0.0; 3];
+    let mut vel_efg = [0.0; 3];
+    unsafe {
+        ECIToEFG(theta_g, pos_teme, vel_teme, &mut pos_efg, &mut vel_efg);
+    }
+    (pos_efg, vel_efg)
+}
+
+#[inline]
+pub fn efg_to_teme_at_angle(theta_g: f64, pos_efg: &[f64; 3], vel_efg: &[f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let mut pos_teme = [0.0; 3];
+    let mut vel_teme = [0.0; 3];
+    unsafe {
+        EFGToECI(theta_g, pos_efg, vel_efg, &mut pos_teme, &mut vel_teme);
+    }
+    (pos_teme, vel_teme)
+}
+
 #[inline]
 pub fn efg_to_ecr(ds50utc: f64, pos_efg: &[f64; 3], vel_efg: &[f64; 3]) -> ([f64; 3], [f64; 3]) {
     let mut pos_ecr = [0.0; 3];
@@ -712,6 +734,15 @@ pub fn topo_equinox_to_date(yr_of_equinox: i32, ds50utc: f64, ra: f64, dec: f64)
     (ra_out, dec_out)
 }
 
+pub fn topo_date_to_equinox(yr_of_equinox: i32, ds50utc: f64, ra: f64, dec: f64) -> (f64, f64) {
+    let mut ra_out = 0.0;
+    let mut dec_out = 0.0;
+    unsafe {
+        RotRADec_DateToEqnx(106, yr_of_equinox, ds50utc, ra, dec, &mut ra_out, &mut dec_out);
+    }
+    (ra_out, dec_out)
+}
+
 pub fn topo_date_to_epoch(ds50_in: f64, ra: f64, dec: f64, ds50_out: f64) -> (f64, f64) {
     let mut ra_out = 0.0;
     let mut dec_out = 0.0;
@@ -730,17 +761,22 @@ pub fn topo_epoch_to_date(ds50_in: f64, ra: f64, dec: f64, ds50_out: f64) -> (f6
     (ra_out, dec_out)
 }
 
-pub fn teme_to_topocentric(sensor_teme: CartesianVector, observed_teme: CartesianState) -> TopocentricElements {
+pub fn teme_to_topocentric_components(
+    sensor_teme: CartesianVector,
+    observed_teme: CartesianState,
+) -> [f64; XA_TOPO_SIZE] {
     let theta = observed_teme.epoch.to_fk5_greenwich_angle();
     let mut llh = [0.0; 3];
     unsafe {
         XYZToLLH(theta, &sensor_teme.into(), &mut llh);
     }
 
+    // The horizon components are referenced to the local sidereal time of the sensor
+    let local_sidereal_time = sensor_teme[1].atan2(sensor_teme[0]);
     let mut xa_topo = [0.0; XA_TOPO_SIZE];
     unsafe {
         ECIToTopoComps(
-            theta,
+            local_sidereal_time,
             llh[0],
             &sensor_teme.into(),
             &observed_teme.position.into(),
@@ -748,6 +784,11 @@ pub fn teme_to_topocentric(sensor_teme: CartesianVector, observed_teme: Cartesia
             &mut xa_topo,
         );
     }
+    xa_topo
+}
+
+pub fn teme_to_topocentric(sensor_teme: CartesianVector, observed_teme: CartesianState) -> TopocentricElements {
+    let xa_topo = teme_to_topocentric_components(sensor_teme, observed_teme);
     let mut topo_elements = TopocentricElements::new(xa_topo[XA_TOPO_RA], xa_topo[XA_TOPO_DEC]);
     topo_elements.set_declination_rate(Some(xa_topo[XA_TOPO_DECDOT]));
     topo_elements.set_right_ascension_rate(Some(xa_topo[XA_TOPO_RADOT]));
@@ -756,6 +797,67 @@ pub fn teme_to_topocentric(sensor_teme: CartesianVector, observed_teme: Cartesia
     topo_elements
 }
 
+pub fn teme_to_horizon(sensor_teme: CartesianVector, observed_teme: CartesianState) -> HorizonElements {
+    let xa_topo = teme_to_topocentric_components(sensor_teme, observed_teme);
+    let mut horizon_elements = HorizonElements::new(xa_topo[XA_TOPO_AZ], xa_topo[XA_TOPO_EL]);
+    horizon_elements.set_azimuth_rate(Some(xa_topo[XA_TOPO_AZDOT]));
+    horizon_elements.set_elevation_rate(Some(xa_topo[XA_TOPO_ELDOT]));
+    horizon_elements.set_range(Some(xa_topo[XA_TOPO_RANGE]));
+    horizon_elements.set_range_rate(Some(xa_topo[XA_TOPO_RANGEDOT]));
+    horizon_elements
+}
+
+pub fn get_topocentric_basis(epoch: Epoch, sensor_teme: CartesianVector) -> [CartesianVector; 3] {
+    let theta = epoch.to_fk5_greenwich_angle();
+    let mut llh = [0.0; 3];
+    unsafe {
+        XYZToLLH(theta, &sensor_teme.into(), &mut llh);
+    }
+
+    // Local east, north and up directions expressed in TEME
+    let latitude = llh[0].to_radians();
+    let local_sidereal_time = sensor_teme[1].atan2(sensor_teme[0]);
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let (sin_lst, cos_lst) = local_sidereal_time.sin_cos();
+    [
+        CartesianVector::new(-sin_lst, cos_lst, 0.0),
+        CartesianVector::new(-sin_lat * cos_lst, -sin_lat * sin_lst, cos_lat),
+        CartesianVector::new(cos_lat * cos_lst, cos_lat * sin_lst, sin_lat),
+    ]
+}
+
+pub fn horizon_to_topocentric(epoch: Epoch, sensor_teme: CartesianVector, azimuth: f64, elevation: f64) -> (f64, f64) {
+    let [east, north, up] = get_topocentric_basis(epoch, sensor_teme);
+    let (sin_az, cos_az) = azimuth.to_radians().sin_cos();
+    let (sin_el, cos_el) = elevation.to_radians().sin_cos();
+    let direction = (east * (cos_el * sin_az) + north * (cos_el * cos_az) + up * sin_el).to_spherical();
+    (
+        direction.get_right_ascension().rem_euclid(360.0),
+        direction.get_declination(),
+    )
+}
+
+pub fn annual_aberration(ds50utc: f64, ra: f64, dec: f64) -> (f64, f64) {
+    let mut ra_delta = 0.0;
+    let mut dec_delta = 0.0;
+    unsafe {
+        AberrationAnnual(ra, dec, ds50utc, &mut ra_delta, &mut dec_delta);
+    }
+    (ra_delta, dec_delta)
+}
+
+pub fn diurnal_aberration(ds50utc: f64, sensor_teme: CartesianVector, ra: f64, dec: f64) -> (f64, f64) {
+    // The sensor position is expected as an earth-fixed vector in meters
+    let (sensor_efg, _) = teme_to_efg(ds50utc, &sensor_teme.into(), &[0.0; 3]);
+    let sensor_efg = [sensor_efg[0] * 1000.0, sensor_efg[1] * 1000.0, sensor_efg[2] * 1000.0];
+    let mut ra_delta = 0.0;
+    let mut dec_delta = 0.0;
+    unsafe {
+        AberrationDiurnal(ra, dec, ds50utc, &sensor_efg, &mut ra_delta, &mut dec_delta);
+    }
+    (ra_delta, dec_delta)
+}
+
 pub fn osculating_to_mean(xa_osc: &[f64; XA_KEP_SIZE]) -> [f64; XA_KEP_SIZE] {
     let mut xa_mean = [0.0; XA_KEP_SIZE];
     unsafe {
@@ -764,6 +866,14 @@ pub fn osculating_to_mean(xa_osc: &[f64; XA_KEP_SIZE]) -> [f64; XA_KEP_SIZE] {
     xa_mean
 }
 
+pub fn cartesian_to_equinoctial(pos: &[f64; 3], vel: &[f64; 3]) -> [f64; XA_EQNX_SIZE] {
+    let mut xa_eqnx = [0.0; XA_EQNX_SIZE];
+    unsafe {
+        PosVelToEqnx(pos, vel, &mut xa_eqnx);
+    }
+    xa_eqnx
+}
+
 pub fn equinoctial_to_keplerian(xa_eqnx: &[f64; XA_EQNX_SIZE]) -> [f64; XA_KEP_SIZE] {
     let mut xa_kep = [0.0; XA_KEP_SIZE];
     unsafe {
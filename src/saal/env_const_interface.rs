@@ -213,6 +213,10 @@ pub fn get_ke() -> f64 {
     unsafe { EnvGetGeoConst(XF_GEOCON_KE) }
 }
 
+pub fn get_j2() -> f64 {
+    unsafe { EnvGetGeoConst(XF_GEOCON_J2) }
+}
+
 pub fn set_geo_model(model: i32) {
     unsafe { EnvSetGeoIdx(model) }
 }
@@ -303,3 +303,24 @@ pub fn get_fk5_greenwich_angle(ds50_ut1: f64) -> f64 {
 pub fn time_constants_loaded() -> bool {
     unsafe { IsTConFileLoaded() != 0 }
 }
+
+pub fn clear_time_constants() -> Result<(), String> {
+    match unsafe { TConRemoveAll() } {
+        0 => Ok(()),
+        _ => Err(main_interface::get_last_error_message()),
+    }
+}
+
+pub fn add_time_constants(
+    ds50_utc: f64,
+    tai_minus_utc: f64,
+    ut1_minus_utc: f64,
+    ut1_rate: f64,
+    polar_x: f64,
+    polar_y: f64,
+) -> Result<(), String> {
+    match unsafe { TConAddOne(ds50_utc, tai_minus_utc, ut1_minus_utc, ut1_rate, polar_x, polar_y) } {
+        0 => Ok(()),
+        _ => Err(main_interface::get_last_error_message()),
+    }
+}
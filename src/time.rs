@@ -1,5 +1,10 @@
 use crate::saal::time_func_interface;
+mod earth_rotation;
+mod eop;
 mod epoch;
+mod epoch_range;
+mod iso;
+mod leap_seconds;
 mod time_components;
 mod time_span;
 
@@ -7,6 +12,8 @@ use pyo3::prelude::*;
 use pyo3::py_run;
 
 pub use epoch::Epoch;
+pub use epoch_range::{EpochRange, EpochRangeIterator};
+pub use iso::IsoParseError;
 pub use time_components::TimeComponents;
 pub use time_span::TimeSpan;
 
@@ -21,9 +28,15 @@ pub fn register_time(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let time = PyModule::new(parent_module.py(), "time")?;
     time.add_function(wrap_pyfunction!(time_func_interface::load_time_constants, &time)?)?;
     time.add_function(wrap_pyfunction!(time_func_interface::time_constants_loaded, &time)?)?;
+    time.add_function(wrap_pyfunction!(leap_seconds::get_leap_seconds, &time)?)?;
+    time.add_function(wrap_pyfunction!(eop::load_finals_file, &time)?)?;
+    time.add_function(wrap_pyfunction!(eop::load_celestrak_eop_file, &time)?)?;
     time.add_class::<TimeSpan>()?;
     time.add_class::<Epoch>()?;
+    time.add_class::<EpochRange>()?;
+    time.add_class::<EpochRangeIterator>()?;
     time.add_class::<TimeComponents>()?;
+    time.add("IsoParseError", time.py().get_type::<IsoParseError>())?;
     py_run!(
         parent_module.py(),
         time,
@@ -0,0 +1,265 @@
+// Sidereal time, Earth Rotation Angle and equation of the equinoxes from UT1 and TT days since 1950
+use std::f64::consts::TAU;
+
+const J2000_DAYS_SINCE_1950: f64 = 18263.5;
+const DAYS_PER_JULIAN_CENTURY: f64 = 36525.0;
+const SECONDS_PER_DAY: f64 = 86400.0;
+const ARCSECONDS_TO_RADIANS: f64 = TAU / 1_296_000.0;
+
+// Earth Rotation Angle in turns at J2000 and turns beyond one per UT1 day
+const ERA_AT_J2000: f64 = 0.779057273264;
+const ERA_EXCESS_TURNS_PER_DAY: f64 = 0.00273781191135448;
+
+// Amplitude units of the IAU 1980 and IAU 2000 nutation tables in arcseconds
+const IAU_1980_UNIT: f64 = 1e-4;
+const IAU_2000_UNIT: f64 = 1e-7;
+
+// Multipliers of l, l', F, D and Omega with the sine amplitude, its rate per century and the cosine amplitude of the
+// nutation in longitude.  Only terms above roughly a milliarcsecond are kept, so the equation of the equinoxes is
+// good to a few milliarcseconds (a fraction of a millisecond of time) rather than the microarcseconds of the full
+// series.
+type NutationTerm = ([f64; 5], f64, f64, f64);
+
+const IAU_1980_NUTATION: [NutationTerm; 34] = [
+    ([0.0, 0.0, 0.0, 0.0, 1.0], -171996.0, -174.2, 0.0),
+    ([0.0, 0.0, 2.0, -2.0, 2.0], -13187.0, -1.6, 0.0),
+    ([0.0, 0.0, 2.0, 0.0, 2.0], -2274.0, -0.2, 0.0),
+    ([0.0, 0.0, 0.0, 0.0, 2.0], 2062.0, 0.2, 0.0),
+    ([0.0, 1.0, 0.0, 0.0, 0.0], 1426.0, -3.4, 0.0),
+    ([1.0, 0.0, 0.0, 0.0, 0.0], 712.0, 0.1, 0.0),
+    ([0.0, 1.0, 2.0, -2.0, 2.0], -517.0, 1.2, 0.0),
+    ([0.0, 0.0, 2.0, 0.0, 1.0], -386.0, -0.4, 0.0),
+    ([1.0, 0.0, 2.0, 0.0, 2.0], -301.0, 0.0, 0.0),
+    ([0.0, -1.0, 2.0, -2.0, 2.0], 217.0, -0.5, 0.0),
+    ([1.0, 0.0, 0.0, -2.0, 0.0], -158.0, 0.0, 0.0),
+    ([0.0, 0.0, 2.0, -2.0, 1.0], 129.0, 0.1, 0.0),
+    ([-1.0, 0.0, 2.0, 0.0, 2.0], 123.0, 0.0, 0.0),
+    ([1.0, 0.0, 0.0, 0.0, 1.0], 63.0, 0.1, 0.0),
+    ([0.0, 0.0, 0.0, 2.0, 0.0], 63.0, 0.0, 0.0),
+    ([-1.0, 0.0, 2.0, 2.0, 2.0], -59.0, 0.0, 0.0),
+    ([-1.0, 0.0, 0.0, 0.0, 1.0], -58.0, -0.1, 0.0),
+    ([1.0, 0.0, 2.0, 0.0, 1.0], -51.0, 0.0, 0.0),
+    ([2.0, 0.0, 0.0, -2.0, 0.0], 48.0, 0.0, 0.0),
+    ([-2.0, 0.0, 2.0, 0.0, 1.0], 46.0, 0.0, 0.0),
+    ([0.0, 0.0, 2.0, 2.0, 2.0], -38.0, 0.0, 0.0),
+    ([2.0, 0.0, 2.0, 0.0, 2.0], -31.0, 0.0, 0.0),
+    ([2.0, 0.0, 0.0, 0.0, 0.0], 29.0, 0.0, 0.0),
+    ([1.0, 0.0, 2.0, -2.0, 2.0], 29.0, 0.0, 0.0),
+    ([0.0, 0.0, 2.0, 0.0, 0.0], 26.0, 0.0, 0.0),
+    ([0.0, 0.0, 2.0, -2.0, 0.0], -22.0, 0.0, 0.0),
+    ([-1.0, 0.0, 2.0, 0.0, 1.0], 21.0, 0.0, 0.0),
+    ([0.0, 2.0, 0.0, 0.0, 0.0], 17.0, -0.1, 0.0),
+    ([0.0, 2.0, 2.0, -2.0, 2.0], -16.0, 0.1, 0.0),
+    ([-1.0, 0.0, 0.0, 2.0, 1.0], 16.0, 0.0, 0.0),
+    ([0.0, 1.0, 0.0, 0.0, 1.0], -15.0, 0.0, 0.0),
+    ([1.0, 0.0, 0.0, -2.0, 1.0], -13.0, 0.0, 0.0),
+    ([0.0, -1.0, 0.0, 0.0, 1.0], -12.0, 0.0, 0.0),
+    ([2.0, 0.0, -2.0, 0.0, 0.0], 11.0, 0.0, 0.0),
+];
+
+const IAU_2000_NUTATION: [NutationTerm; 29] = [
+    ([0.0, 0.0, 0.0, 0.0, 1.0], -172064161.0, -174666.0, 33386.0),
+    ([0.0, 0.0, 2.0, -2.0, 2.0], -13170906.0, -1675.0, -13696.0),
+    ([0.0, 0.0, 2.0, 0.0, 2.0], -2276413.0, -234.0, 2796.0),
+    ([0.0, 0.0, 0.0, 0.0, 2.0], 2074554.0, 207.0, -698.0),
+    ([0.0, 1.0, 0.0, 0.0, 0.0], 1475877.0, -3633.0, 11817.0),
+    ([0.0, 1.0, 2.0, -2.0, 2.0], -516821.0, 1226.0, -524.0),
+    ([1.0, 0.0, 0.0, 0.0, 0.0], 711159.0, 73.0, -872.0),
+    ([0.0, 0.0, 2.0, 0.0, 1.0], -387298.0, -367.0, 380.0),
+    ([1.0, 0.0, 2.0, 0.0, 2.0], -301461.0, -36.0, 816.0),
+    ([0.0, -1.0, 2.0, -2.0, 2.0], 215829.0, -494.0, 111.0),
+    ([0.0, 0.0, 2.0, -2.0, 1.0], 128227.0, 137.0, 181.0),
+    ([-1.0, 0.0, 2.0, 0.0, 2.0], 123457.0, 11.0, 19.0),
+    ([-1.0, 0.0, 0.0, 2.0, 0.0], 156994.0, 10.0, -168.0),
+    ([1.0, 0.0, 0.0, 0.0, 1.0], 63110.0, 63.0, 27.0),
+    ([-1.0, 0.0, 0.0, 0.0, 1.0], -57976.0, -63.0, -189.0),
+    ([-1.0, 0.0, 2.0, 2.0, 2.0], -59641.0, -11.0, 149.0),
+    ([1.0, 0.0, 2.0, 0.0, 1.0], -51613.0, -42.0, 129.0),
+    ([-2.0, 0.0, 2.0, 0.0, 1.0], 45893.0, 50.0, 31.0),
+    ([0.0, 0.0, 0.0, 2.0, 0.0], 63384.0, 11.0, -150.0),
+    ([0.0, 0.0, 2.0, 2.0, 2.0], -38571.0, -1.0, 158.0),
+    ([-2.0, 0.0, 0.0, 2.0, 0.0], -47722.0, 0.0, -18.0),
+    ([2.0, 0.0, 2.0, 0.0, 2.0], -31046.0, -1.0, 131.0),
+    ([1.0, 0.0, 2.0, -2.0, 2.0], 28593.0, 0.0, -1.0),
+    ([-1.0, 0.0, 2.0, 0.0, 1.0], 20441.0, 21.0, 10.0),
+    ([2.0, 0.0, 0.0, 0.0, 0.0], 29243.0, 0.0, -74.0),
+    ([0.0, 0.0, 2.0, 0.0, 0.0], 25887.0, 0.0, -66.0),
+    ([0.0, 1.0, 0.0, 0.0, 1.0], -14053.0, -25.0, 79.0),
+    ([-1.0, 0.0, 0.0, 2.0, 1.0], 15164.0, 10.0, 11.0),
+    ([0.0, 2.0, 2.0, -2.0, 2.0], -15794.0, 72.0, -16.0),
+];
+
+// Multipliers of l, l', F, D and Omega with the sine amplitude in arcseconds of the IAU 2000 complementary terms
+const IAU_2000_COMPLEMENTARY_TERMS: [([f64; 5], f64); 10] = [
+    ([0.0, 0.0, 0.0, 0.0, 1.0], 2640.96e-6),
+    ([0.0, 0.0, 0.0, 0.0, 2.0], 63.52e-6),
+    ([0.0, 0.0, 2.0, -2.0, 3.0], 11.75e-6),
+    ([0.0, 0.0, 2.0, -2.0, 1.0], 11.21e-6),
+    ([0.0, 0.0, 2.0, -2.0, 2.0], -4.55e-6),
+    ([0.0, 0.0, 2.0, 0.0, 3.0], 2.02e-6),
+    ([0.0, 0.0, 2.0, 0.0, 1.0], 1.98e-6),
+    ([0.0, 0.0, 0.0, 0.0, 3.0], -1.72e-6),
+    ([0.0, 1.0, 0.0, 0.0, 1.0], -1.41e-6),
+    ([0.0, 1.0, 0.0, 0.0, -1.0], -1.26e-6),
+];
+const IAU_2000_COMPLEMENTARY_RATE: f64 = -0.87e-6;
+
+fn get_centuries_since_j2000(days_since_1950: f64) -> f64 {
+    (days_since_1950 - J2000_DAYS_SINCE_1950) / DAYS_PER_JULIAN_CENTURY
+}
+
+fn evaluate_polynomial(coefficients: &[f64], t: f64) -> f64 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |sum, coefficient| sum * t + coefficient)
+}
+
+// IERS 2003 mean anomalies of the Moon and Sun, argument of latitude of the Moon, elongation of the Moon from the
+// Sun and longitude of the ascending node of the Moon in radians
+fn get_delaunay_arguments(t: f64) -> [f64; 5] {
+    [
+        [485868.249036, 1717915923.2178, 31.8792, 0.051635, -0.0002447],
+        [1287104.793048, 129596581.0481, -0.5532, 0.000136, -0.00001149],
+        [335779.526232, 1739527262.8478, -12.7512, -0.001037, 0.00000417],
+        [1072260.703692, 1602961601.209, -6.3706, 0.006593, -0.00003169],
+        [450160.398036, -6962890.5431, 7.4722, 0.007702, -0.00005939],
+    ]
+    .map(|coefficients| (evaluate_polynomial(&coefficients, t) * ARCSECONDS_TO_RADIANS).rem_euclid(TAU))
+}
+
+fn get_argument(multipliers: &[f64; 5], arguments: &[f64; 5]) -> f64 {
+    multipliers
+        .iter()
+        .zip(arguments)
+        .map(|(multiplier, argument)| multiplier * argument)
+        .sum()
+}
+
+// Nutation in longitude in radians
+fn get_nutation_in_longitude(table: &[NutationTerm], unit: f64, arguments: &[f64; 5], t: f64) -> f64 {
+    let sum: f64 = table
+        .iter()
+        .map(|(multipliers, sine, sine_rate, cosine)| {
+            let argument = get_argument(multipliers, arguments);
+            (sine + sine_rate * t) * argument.sin() + cosine * argument.cos()
+        })
+        .sum();
+    sum * unit * ARCSECONDS_TO_RADIANS
+}
+
+// Angle in radians in [0, 2pi)
+pub fn get_earth_rotation_angle(ut1_days_since_1950: f64) -> f64 {
+    let days = ut1_days_since_1950 - J2000_DAYS_SINCE_1950;
+    // Whole days are split off so the fraction of a turn keeps its precision
+    let turns = (ut1_days_since_1950 + 0.5).fract() + ERA_AT_J2000 + ERA_EXCESS_TURNS_PER_DAY * days;
+    (TAU * turns).rem_euclid(TAU)
+}
+
+pub fn get_gmst_82(ut1_days_since_1950: f64) -> f64 {
+    let t = get_centuries_since_j2000(ut1_days_since_1950);
+    let seconds = evaluate_polynomial(&[24110.54841, 8640184.812866, 0.093104, -6.2e-6], t)
+        + SECONDS_PER_DAY * ut1_days_since_1950.fract();
+    (seconds * TAU / SECONDS_PER_DAY).rem_euclid(TAU)
+}
+
+pub fn get_gmst_06(ut1_days_since_1950: f64, tt_days_since_1950: f64) -> f64 {
+    let t = get_centuries_since_j2000(tt_days_since_1950);
+    let arcseconds = evaluate_polynomial(
+        &[
+            0.014506,
+            4612.156534,
+            1.3915817,
+            -0.00000044,
+            -0.000029956,
+            -0.0000000368,
+        ],
+        t,
+    );
+    (get_earth_rotation_angle(ut1_days_since_1950) + arcseconds * ARCSECONDS_TO_RADIANS).rem_euclid(TAU)
+}
+
+// IAU 1994 form used with IAU-82 sidereal time
+pub fn get_equation_of_equinoxes_94(tt_days_since_1950: f64) -> f64 {
+    let t = get_centuries_since_j2000(tt_days_since_1950);
+    let arguments = get_delaunay_arguments(t);
+    let node = arguments[4];
+    let obliquity = evaluate_polynomial(&[84381.448, -46.815, -0.00059, 0.001813], t) * ARCSECONDS_TO_RADIANS;
+    let nutation = get_nutation_in_longitude(&IAU_1980_NUTATION, IAU_1980_UNIT, &arguments, t);
+    nutation * obliquity.cos() + (0.00264 * node.sin() + 0.000063 * (2.0 * node).sin()) * ARCSECONDS_TO_RADIANS
+}
+
+// IAU 2000 form used with IAU 2006 sidereal time
+pub fn get_equation_of_equinoxes_00(tt_days_since_1950: f64) -> f64 {
+    let t = get_centuries_since_j2000(tt_days_since_1950);
+    let arguments = get_delaunay_arguments(t);
+    let obliquity = evaluate_polynomial(
+        &[
+            84381.406,
+            -46.836769,
+            -0.0001831,
+            0.0020034,
+            -0.000000576,
+            -0.0000000434,
+        ],
+        t,
+    ) * ARCSECONDS_TO_RADIANS;
+    let nutation = get_nutation_in_longitude(&IAU_2000_NUTATION, IAU_2000_UNIT, &arguments, t);
+    let complementary: f64 = IAU_2000_COMPLEMENTARY_TERMS
+        .iter()
+        .map(|(multipliers, sine)| sine * get_argument(multipliers, &arguments).sin())
+        .sum::<f64>()
+        + IAU_2000_COMPLEMENTARY_RATE * t * arguments[4].sin();
+    nutation * obliquity.cos() + complementary * ARCSECONDS_TO_RADIANS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    // 2012 December 20 00:00:00 UTC
+    const UT1_DAYS_SINCE_1950: f64 = 23000.0;
+    const TT_DAYS_SINCE_1950: f64 = 23000.0 + 67.184 / SECONDS_PER_DAY;
+
+    #[test]
+    fn test_earth_rotation_angle() {
+        assert_abs_diff_eq!(
+            get_earth_rotation_angle(J2000_DAYS_SINCE_1950),
+            TAU * ERA_AT_J2000,
+            epsilon = 1e-12
+        );
+        // One sidereal day later the Earth has turned exactly once
+        let sidereal_day = 1.0 / (1.0 + ERA_EXCESS_TURNS_PER_DAY);
+        assert_abs_diff_eq!(
+            get_earth_rotation_angle(UT1_DAYS_SINCE_1950 + sidereal_day),
+            get_earth_rotation_angle(UT1_DAYS_SINCE_1950),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_sidereal_time() {
+        // 18h 41m 50.54841s at J2000
+        assert_abs_diff_eq!(
+            get_gmst_82(J2000_DAYS_SINCE_1950),
+            67310.54841 * TAU / SECONDS_PER_DAY,
+            epsilon = 1e-12
+        );
+        let gmst_82 = get_gmst_82(UT1_DAYS_SINCE_1950);
+        let gmst_06 = get_gmst_06(UT1_DAYS_SINCE_1950, TT_DAYS_SINCE_1950);
+        assert_abs_diff_eq!(gmst_82, 1.5529830656299097, epsilon = 1e-8);
+        // The IAU 2006 origin and precession rate move mean sidereal time by tens of milliarcseconds
+        assert_abs_diff_eq!(gmst_06, gmst_82, epsilon = 0.1 * ARCSECONDS_TO_RADIANS);
+    }
+
+    #[test]
+    fn test_equation_of_equinoxes() {
+        let equation_94 = get_equation_of_equinoxes_94(TT_DAYS_SINCE_1950);
+        let equation_00 = get_equation_of_equinoxes_00(TT_DAYS_SINCE_1950);
+        // Bounded by the 17.2 arcsecond nodal term
+        assert!(equation_94.abs() < 17.3 * ARCSECONDS_TO_RADIANS);
+        // The IAU 1980 nutation is in error by tens of milliarcseconds
+        assert_abs_diff_eq!(equation_94, equation_00, epsilon = 0.1 * ARCSECONDS_TO_RADIANS);
+    }
+}
@@ -0,0 +1,207 @@
+use super::leap_seconds;
+use crate::saal::time_func_interface;
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::fs;
+
+// Modified Julian Date of 1950 January 0.0, the origin of days since 1950
+const MJD_DAYS_SINCE_1950_OFFSET: f64 = 33281.0;
+const CELESTRAK_COLUMNS: [&str; 6] = ["MJD", "X", "Y", "UT1-UTC", "LOD", "DAT"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EopRecord {
+    pub days_since_1950: f64,
+    pub tai_minus_utc: f64,
+    pub ut1_minus_utc: f64,
+    pub polar_x: f64,
+    pub polar_y: f64,
+    // Excess length of day in milliseconds
+    pub length_of_day: Option<f64>,
+}
+
+impl EopRecord {
+    // Rate of UT1 - UTC in milliseconds per day as expected by the time constants
+    fn get_ut1_rate(&self, previous: Option<&EopRecord>) -> f64 {
+        match (self.length_of_day, previous) {
+            (Some(length_of_day), _) => -length_of_day,
+            (None, Some(previous)) => {
+                let leap = self.tai_minus_utc - previous.tai_minus_utc;
+                let change = self.ut1_minus_utc - previous.ut1_minus_utc - leap;
+                1e3 * change / (self.days_since_1950 - previous.days_since_1950)
+            }
+            (None, None) => 0.0,
+        }
+    }
+}
+
+fn parse_column(line: &str, start: usize, end: usize, name: &str) -> Result<Option<f64>, String> {
+    let field = line.get(start..end.min(line.len())).unwrap_or("").trim();
+    match field.is_empty() {
+        true => Ok(None),
+        false => field
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("Invalid {} '{}'", name, field)),
+    }
+}
+
+// Rows of finals2000A.all or finals.data with Bulletin A values, ending at the first row without UT1 - UTC
+pub fn parse_finals(contents: &str) -> Result<Vec<EopRecord>, String> {
+    let mut records = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let context = |e: String| format!("Line {}: {}", i + 1, e);
+        let mjd = parse_column(line, 7, 15, "MJD")
+            .map_err(context)?
+            .ok_or_else(|| context("Missing MJD".to_string()))?;
+        let ut1_minus_utc = match parse_column(line, 58, 68, "UT1-UTC").map_err(context)? {
+            Some(ut1_minus_utc) => ut1_minus_utc,
+            None => break,
+        };
+        let days_since_1950 = mjd - MJD_DAYS_SINCE_1950_OFFSET;
+        records.push(EopRecord {
+            days_since_1950,
+            tai_minus_utc: leap_seconds::get_tai_minus_utc_at_utc(days_since_1950),
+            ut1_minus_utc,
+            polar_x: parse_column(line, 18, 27, "PM-x").map_err(context)?.unwrap_or(0.0),
+            polar_y: parse_column(line, 37, 46, "PM-y").map_err(context)?.unwrap_or(0.0),
+            length_of_day: parse_column(line, 79, 86, "LOD").map_err(context)?,
+        });
+    }
+    Ok(records)
+}
+
+// Rows of CelesTrak EOP-All.csv or EOP-Last5Years.csv, located by the header row
+pub fn parse_celestrak(contents: &str) -> Result<Vec<EopRecord>, String> {
+    let mut lines = contents.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let header: Vec<&str> = match lines.next() {
+        Some((_, header)) => header.split(',').map(str::trim).collect(),
+        None => return Ok(Vec::new()),
+    };
+    let mut indices = [0; CELESTRAK_COLUMNS.len()];
+    for (index, name) in indices.iter_mut().zip(CELESTRAK_COLUMNS) {
+        *index = header
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Line 1: Missing {} column", name))?;
+    }
+
+    let mut records = Vec::new();
+    for (i, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let mut values = [None; CELESTRAK_COLUMNS.len()];
+        for ((value, index), name) in values.iter_mut().zip(indices).zip(CELESTRAK_COLUMNS) {
+            let field = fields.get(index).copied().unwrap_or("");
+            if !field.is_empty() {
+                *value = Some(
+                    field
+                        .parse::<f64>()
+                        .map_err(|_| format!("Line {}: Invalid {} '{}'", i + 1, name, field))?,
+                );
+            }
+        }
+        let [mjd, polar_x, polar_y, ut1_minus_utc, length_of_day, tai_minus_utc] = values;
+        let (mjd, ut1_minus_utc) = match (mjd, ut1_minus_utc) {
+            (Some(mjd), Some(ut1_minus_utc)) => (mjd, ut1_minus_utc),
+            _ => return Err(format!("Line {}: Missing MJD or UT1-UTC", i + 1)),
+        };
+        let days_since_1950 = mjd - MJD_DAYS_SINCE_1950_OFFSET;
+        records.push(EopRecord {
+            days_since_1950,
+            tai_minus_utc: tai_minus_utc.unwrap_or_else(|| leap_seconds::get_tai_minus_utc_at_utc(days_since_1950)),
+            ut1_minus_utc,
+            polar_x: polar_x.unwrap_or(0.0),
+            polar_y: polar_y.unwrap_or(0.0),
+            // CelesTrak reports length of day in seconds
+            length_of_day: length_of_day.map(|length_of_day| 1e3 * length_of_day),
+        });
+    }
+    Ok(records)
+}
+
+// Replaces the loaded time constants with one record per EOP row
+pub fn load_records(records: &[EopRecord]) -> Result<(), String> {
+    if records.is_empty() {
+        return Err("No EOP records found".to_string());
+    }
+    time_func_interface::clear_time_constants()?;
+    let mut previous = None;
+    for record in records {
+        time_func_interface::add_time_constants(
+            record.days_since_1950,
+            record.tai_minus_utc,
+            record.ut1_minus_utc,
+            record.get_ut1_rate(previous),
+            record.polar_x,
+            record.polar_y,
+        )?;
+        previous = Some(record);
+    }
+    Ok(())
+}
+
+fn load_file(file_path: &str, parser: fn(&str) -> Result<Vec<EopRecord>, String>) -> PyResult<()> {
+    let contents = fs::read_to_string(file_path)
+        .map_err(|e| PyIOError::new_err(format!("Unable to read {}: {}", file_path, e)))?;
+    let records = parser(&contents).map_err(|e| PyValueError::new_err(format!("{}: {}", file_path, e)))?;
+    load_records(&records).map_err(PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+pub fn load_finals_file(file_path: &str) -> PyResult<()> {
+    load_file(file_path, parse_finals)
+}
+
+#[pyfunction]
+pub fn load_celestrak_eop_file(file_path: &str) -> PyResult<()> {
+    load_file(file_path, parse_celestrak)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FINALS: &str = "\
+25 1 1 60676.00 I  0.082367 0.000091  0.375451 0.000091  I 0.0432513 0.0000186  0.6178 0.0128  I   -30.580     0.300    -5.330     0.300  0.082330  0.375440  0.0433220
+25 1 2 60677.00 P  0.081098 0.003843  0.376567 0.005302  P 0.0425630 0.0053060                 P   -30.600     0.300    -5.320     0.300
+25 1 3 60678.00 P  0.079800 0.004000  0.377600 0.005400
+";
+
+    const CELESTRAK: &str = "\
+DATE,MJD,X,Y,UT1-UTC,LOD,DPSI,DEPS,DX,DY,DAT,DATA_TYPE
+2025-01-01,60676,0.082367,0.375451,0.0432513,0.0006178,-0.115963,-0.007941,0.000283,-0.000035,37,O
+2025-01-02,60677,0.081098,0.376567,0.0425630,,,,,,37,P
+";
+
+    #[test]
+    fn test_parse_finals() {
+        let records = parse_finals(FINALS).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].days_since_1950, 27395.0);
+        assert_eq!(records[0].tai_minus_utc, 37.0);
+        assert_eq!(records[0].ut1_minus_utc, 0.0432513);
+        assert_eq!(records[0].polar_x, 0.082367);
+        assert_eq!(records[0].polar_y, 0.375451);
+        assert_eq!(records[0].length_of_day, Some(0.6178));
+        assert_eq!(records[1].length_of_day, None);
+        assert!((records[1].get_ut1_rate(Some(&records[0])) + 0.6883).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_celestrak() {
+        let records = parse_celestrak(CELESTRAK).unwrap();
+        let finals = parse_finals(FINALS).unwrap();
+        assert_eq!(records.len(), 2);
+        for (record, expected) in records.iter().zip(&finals) {
+            assert_eq!(record.days_since_1950, expected.days_since_1950);
+            assert_eq!(record.tai_minus_utc, expected.tai_minus_utc);
+            assert_eq!(record.ut1_minus_utc, expected.ut1_minus_utc);
+            assert_eq!(record.polar_x, expected.polar_x);
+            assert_eq!(record.polar_y, expected.polar_y);
+        }
+        assert!((records[0].length_of_day.unwrap() - 0.6178).abs() < 1e-12);
+        assert!(parse_celestrak("DATE,MJD,X,Y\n").is_err());
+    }
+}
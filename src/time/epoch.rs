@@ -1,21 +1,59 @@
-use super::{TimeComponents, TimeSpan};
-use crate::enums::TimeSystem;
+use super::earth_rotation;
+use super::iso::{self, IsoError, DEFAULT_ISO_PRECISION};
+use super::leap_seconds;
+use super::{TimeComponents, TimeSpan, DAYS_TO_SECONDS, SECONDS_TO_DAYS};
+use crate::enums::{EarthRotationModel, TimeSystem};
 use crate::saal::time_func_interface;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyAny;
+use std::f64::consts::TAU;
 use std::hash::Hash;
 use std::ops::{Add, AddAssign, Sub};
 
+const TT_MINUS_TAI_SECONDS: f64 = 32.184;
+const TAI_MINUS_GPS_SECONDS: f64 = 19.0;
+const J2000_DAYS_SINCE_1950: f64 = 18263.5;
+// IAU 2000 Resolution B1.9 rate of TCG relative to TT and the 1977 January 1.0 TAI epoch where they coincide
+const L_G: f64 = 6.969290134e-10;
+const TCG_TT_REFERENCE_DAYS_SINCE_1950: f64 = 9863.0003725;
+const MAX_INVERSE_ITERATIONS: usize = 5;
+const NANOSECONDS_PER_MICROSECOND: u64 = 1_000;
+const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+const NANOSECONDS_PER_DAY: u64 = 86_400 * NANOSECONDS_PER_SECOND;
+// Julian and Modified Julian Dates of 1950 January 0.0, the origin of days since 1950
+const JD_DAYS_SINCE_1950_OFFSET: f64 = 2433281.5;
+const MJD_DAYS_SINCE_1950_OFFSET: f64 = 33281.0;
+// Days since 1950 of the GPS epoch on 1980 January 6 and the Unix epoch on 1970 January 1
+const GPS_EPOCH_DAYS_SINCE_1950: f64 = 10963.0;
+const UNIX_EPOCH_DAYS_SINCE_1950: f64 = 7306.0;
+const DAYS_PER_WEEK: f64 = 7.0;
+const TLE_YEAR_PIVOT: i32 = 57;
+
+// The epoch is the unevaluated sum of days_since_1950 and residual_days, where days_since_1950 is the nearest
+// double to the full value and residual_days keeps the sub-microsecond part that a single double cannot hold
 #[pyclass]
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone)]
 pub struct Epoch {
     pub days_since_1950: f64,
+    residual_days: f64,
     time_system: TimeSystem,
 }
 
+// Epochs in different systems describe the same instant when they agree in TAI, so equality, ordering and
+// hashing all use the TAI representation
 impl Hash for Epoch {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.to_iso().hash(state);
+        let (days_since_1950, residual_days) = self.get_tai_parts();
+        // Adding zero folds -0.0 into 0.0 so equal epochs hash alike
+        (days_since_1950 + 0.0).to_bits().hash(state);
+        (residual_days + 0.0).to_bits().hash(state);
+    }
+}
+
+impl PartialEq for Epoch {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
     }
 }
 
@@ -23,8 +61,10 @@ impl Sub for Epoch {
     type Output = TimeSpan;
 
     fn sub(self, other_epoch: Self) -> TimeSpan {
-        let norm_ds50 = other_epoch.to_system(self.time_system).unwrap().days_since_1950;
-        TimeSpan::from_days(self.days_since_1950 - norm_ds50)
+        let other_epoch = other_epoch.to_system(self.time_system).unwrap();
+        TimeSpan::from_days(
+            (self.days_since_1950 - other_epoch.days_since_1950) + (self.residual_days - other_epoch.residual_days),
+        )
     }
 }
 
@@ -32,10 +72,7 @@ impl Sub<TimeSpan> for Epoch {
     type Output = Epoch;
 
     fn sub(self, rhs: TimeSpan) -> Epoch {
-        Epoch {
-            days_since_1950: self.days_since_1950 - rhs.in_days(),
-            time_system: self.time_system,
-        }
+        self.plus_days(-rhs.in_days())
     }
 }
 
@@ -43,16 +80,13 @@ impl Add<TimeSpan> for Epoch {
     type Output = Epoch;
 
     fn add(self, rhs: TimeSpan) -> Epoch {
-        Epoch {
-            days_since_1950: self.days_since_1950 + rhs.in_days(),
-            time_system: self.time_system,
-        }
+        self.plus_days(rhs.in_days())
     }
 }
 
 impl AddAssign<TimeSpan> for Epoch {
     fn add_assign(&mut self, rhs: TimeSpan) {
-        self.days_since_1950 += rhs.in_days();
+        *self = self.plus_days(rhs.in_days());
     }
 }
 
@@ -64,30 +98,178 @@ impl PartialOrd for Epoch {
 
 impl Ord for Epoch {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.days_since_1950 == other.days_since_1950 {
-            std::cmp::Ordering::Equal
-        } else if self.days_since_1950 < other.days_since_1950 {
-            std::cmp::Ordering::Less
-        } else {
-            std::cmp::Ordering::Greater
-        }
+        let (days_since_1950, residual_days) = self.get_tai_parts();
+        let (other_days_since_1950, other_residual_days) = other.get_tai_parts();
+        let days_order = days_since_1950.partial_cmp(&other_days_since_1950);
+        let residual_order = residual_days.partial_cmp(&other_residual_days);
+        days_order
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(residual_order.unwrap_or(std::cmp::Ordering::Equal))
     }
 }
 impl Eq for Epoch {}
 
-impl Epoch {}
+// Error-free transformation of a sum into its rounded value and the rounding error
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let error = (a - (sum - b_virtual)) + (b - b_virtual);
+    (sum, error)
+}
+
+impl Epoch {
+    fn from_parts(days_since_1950: f64, residual_days: f64, time_system: TimeSystem) -> Self {
+        let (days_since_1950, residual_days) = two_sum(days_since_1950, residual_days);
+        Self {
+            days_since_1950,
+            residual_days,
+            time_system,
+        }
+    }
+
+    fn get_tai_parts(&self) -> (f64, f64) {
+        let tai = self.to_system(TimeSystem::TAI).unwrap();
+        (tai.days_since_1950, tai.residual_days)
+    }
+
+    fn plus_days(&self, days: f64) -> Self {
+        let (sum, error) = two_sum(self.days_since_1950, days);
+        Self::from_parts(sum, error + self.residual_days, self.time_system)
+    }
+
+    // Every scale is converted through TAI so that each pair of scales shares one implementation
+    fn get_days_to_tai(&self) -> f64 {
+        let days_since_1950 = self.days_since_1950;
+        match self.time_system {
+            TimeSystem::TAI => 0.0,
+            TimeSystem::UTC => leap_seconds::get_tai_minus_utc_at_utc(days_since_1950) * SECONDS_TO_DAYS,
+            TimeSystem::UT1 => {
+                let utc_minus_ut1 = get_utc_minus_ut1_days(days_since_1950);
+                let utc = days_since_1950 + utc_minus_ut1;
+                utc_minus_ut1 + leap_seconds::get_tai_minus_utc_at_utc(utc) * SECONDS_TO_DAYS
+            }
+            TimeSystem::GPS => TAI_MINUS_GPS_SECONDS * SECONDS_TO_DAYS,
+            TimeSystem::TT => -TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS,
+            TimeSystem::TDB => get_tt_minus_tdb_days(days_since_1950) - TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS,
+            TimeSystem::TCG => get_tt_minus_tcg_days(days_since_1950) - TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS,
+        }
+    }
+
+    fn get_days_from_tai(tai: f64, time_system: TimeSystem) -> f64 {
+        let tt = tai + TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS;
+        match time_system {
+            TimeSystem::TAI => 0.0,
+            TimeSystem::UTC => -leap_seconds::get_tai_minus_utc_at_tai(tai) * SECONDS_TO_DAYS,
+            TimeSystem::UT1 => {
+                let utc_minus_tai = -leap_seconds::get_tai_minus_utc_at_tai(tai) * SECONDS_TO_DAYS;
+                let utc = tai + utc_minus_tai;
+                utc_minus_tai + (time_func_interface::ds50_utc_to_ut1(utc) - utc)
+            }
+            TimeSystem::GPS => -TAI_MINUS_GPS_SECONDS * SECONDS_TO_DAYS,
+            TimeSystem::TT => TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS,
+            TimeSystem::TDB => TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS + get_tdb_minus_tt_days(tt),
+            TimeSystem::TCG => TT_MINUS_TAI_SECONDS * SECONDS_TO_DAYS + get_tcg_minus_tt_days(tt),
+        }
+    }
+
+    // Days elapsed since a whole-day origin, split so the fraction keeps the residual precision
+    fn get_days_since(&self, origin_days_since_1950: f64) -> (f64, f64) {
+        let day = self.days_since_1950.floor();
+        (
+            day - origin_days_since_1950,
+            (self.days_since_1950 - day) + self.residual_days,
+        )
+    }
+
+    fn from_valid_components(components: &TimeComponents, time_system: TimeSystem) -> Result<Self, String> {
+        components.validate(time_system)?;
+
+        // The date and the time of day are combined without rounding so sub-microsecond seconds are kept
+        let day = components.get_day_days_since_1950();
+        let seconds = 3600.0 * components.hour as f64 + 60.0 * components.minute as f64 + components.second;
+        match components.is_leap_second(time_system) {
+            true => {
+                // UTC days since 1950 skip over the leap second, so it can only be held in TAI
+                let tai_minus_utc = leap_seconds::get_tai_minus_utc_at_utc(day);
+                Ok(Self::from_days_and_seconds(
+                    day,
+                    seconds + tai_minus_utc,
+                    TimeSystem::TAI,
+                ))
+            }
+            false => Ok(Self::from_days_and_seconds(day, seconds, time_system)),
+        }
+    }
+
+    // Parses with offsets applied, so "12:00+01:00" in UTC is the epoch of 11:00 UTC
+    pub fn parse_iso(iso: &str, time_system: TimeSystem) -> Result<Self, IsoError> {
+        let components = iso::parse(iso)?.get_components_at_zero_offset();
+        Self::from_valid_components(&components, time_system).map_err(IsoError::Range)
+    }
+
+    pub fn to_iso(&self) -> String {
+        self.to_time_components().to_iso()
+    }
+
+    // Whole days since 1950 and nanoseconds into that day. An epoch held in a single double only resolves a few
+    // tenths of a microsecond, so it is rounded to the nearest microsecond rather than reporting that noise.
+    fn get_day_and_nanoseconds(&self) -> (f64, u64) {
+        let day = self.days_since_1950.floor();
+        let day_fraction = (self.days_since_1950 - day) + self.residual_days;
+        let resolution = match self.residual_days == 0.0 {
+            true => NANOSECONDS_PER_MICROSECOND as f64,
+            false => 1.0,
+        };
+        let nanoseconds = (day_fraction * NANOSECONDS_PER_DAY as f64 / resolution).round() * resolution;
+        match nanoseconds {
+            n if n < 0.0 => (day - 1.0, (n + NANOSECONDS_PER_DAY as f64) as u64),
+            n if n >= NANOSECONDS_PER_DAY as f64 => (day + 1.0, (n - NANOSECONDS_PER_DAY as f64) as u64),
+            n => (day, n as u64),
+        }
+    }
+}
+
+// Leading periodic terms of TDB - TT, accurate to tens of microseconds
+fn get_tdb_minus_tt_days(tt: f64) -> f64 {
+    let mean_anomaly = (357.53 + 0.98560028 * (tt - J2000_DAYS_SINCE_1950)).to_radians();
+    (0.001657 * mean_anomaly.sin() + 0.00001385 * (2.0 * mean_anomaly).sin()) * SECONDS_TO_DAYS
+}
+
+// The periodic terms are evaluated at TT, so the inverse is found by fixed-point iteration
+fn get_tt_minus_tdb_days(tdb: f64) -> f64 {
+    let mut tt_minus_tdb = 0.0;
+    for _ in 0..MAX_INVERSE_ITERATIONS {
+        tt_minus_tdb = -get_tdb_minus_tt_days(tdb + tt_minus_tdb);
+    }
+    tt_minus_tdb
+}
+
+fn get_tcg_minus_tt_days(tt: f64) -> f64 {
+    L_G * (tt - TCG_TT_REFERENCE_DAYS_SINCE_1950) / (1.0 - L_G)
+}
+
+fn get_tt_minus_tcg_days(tcg: f64) -> f64 {
+    -L_G * (tcg - TCG_TT_REFERENCE_DAYS_SINCE_1950)
+}
+
+// UT1 - UTC is interpolated at UTC, so the inverse is found by fixed-point iteration
+fn get_utc_minus_ut1_days(ut1: f64) -> f64 {
+    let mut utc_minus_ut1 = 0.0;
+    for _ in 0..MAX_INVERSE_ITERATIONS {
+        let utc = ut1 + utc_minus_ut1;
+        utc_minus_ut1 = -(time_func_interface::ds50_utc_to_ut1(utc) - utc);
+    }
+    utc_minus_ut1
+}
 
 #[pymethods]
 impl Epoch {
     fn __eq__(&self, other: &Self) -> bool {
-        self.days_since_1950 == other.days_since_1950 && self.time_system == other.time_system
+        self == other
     }
 
     fn __add__(&self, span: &TimeSpan) -> Self {
-        Self {
-            days_since_1950: self.days_since_1950 + span.in_days(),
-            time_system: self.time_system,
-        }
+        *self + *span
     }
 
     fn __sub__<'py>(&self, other: &Bound<'py, PyAny>, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
@@ -114,43 +296,93 @@ impl Epoch {
         self.time_system
     }
 
+    #[getter]
+    pub fn seconds_of_day(&self) -> f64 {
+        let day = self.days_since_1950.floor();
+        ((self.days_since_1950 - day) + self.residual_days) * DAYS_TO_SECONDS
+    }
+
     #[staticmethod]
     pub fn from_days_since_1950(days_since_1950: f64, time_system: TimeSystem) -> Self {
-        Self {
-            days_since_1950,
-            time_system,
-        }
+        Self::from_parts(days_since_1950, 0.0, time_system)
+    }
+
+    #[staticmethod]
+    pub fn from_days_and_seconds(days_since_1950: f64, seconds: f64, time_system: TimeSystem) -> Self {
+        Self::from_days_since_1950(days_since_1950, time_system).plus_days(seconds * SECONDS_TO_DAYS)
     }
 
     #[staticmethod]
-    pub fn from_iso(iso: &str, time_system: TimeSystem) -> Self {
-        let components = TimeComponents::from_iso(iso);
-        Self::from_time_components(&components, time_system)
+    pub fn from_iso(iso: &str, time_system: TimeSystem) -> PyResult<Self> {
+        Ok(Self::parse_iso(iso, time_system)?)
     }
 
     #[staticmethod]
     pub fn from_dtg(dtg: &str, time_system: TimeSystem) -> Self {
         let days_since_1950 = time_func_interface::dtg_to_ds50(dtg);
-        Self {
-            days_since_1950,
-            time_system,
-        }
+        Self::from_days_since_1950(days_since_1950, time_system)
     }
 
     #[staticmethod]
-    pub fn from_time_components(components: &TimeComponents, time_system: TimeSystem) -> Self {
-        let days_since_1950 = time_func_interface::ymd_components_to_ds50(
-            components.year,
-            components.month,
-            components.day,
-            components.hour,
-            components.minute,
-            components.second,
-        );
-        Self {
-            days_since_1950,
-            time_system,
+    pub fn from_time_components(components: &TimeComponents, time_system: TimeSystem) -> PyResult<Self> {
+        Self::from_valid_components(components, time_system).map_err(PyValueError::new_err)
+    }
+
+    #[staticmethod]
+    pub fn from_jd(jd: f64, time_system: TimeSystem) -> Self {
+        Self::from_days_since_1950(jd - JD_DAYS_SINCE_1950_OFFSET, time_system)
+    }
+
+    #[staticmethod]
+    pub fn from_split_jd(jd1: f64, jd2: f64, time_system: TimeSystem) -> Self {
+        Self::from_days_since_1950(jd1 - JD_DAYS_SINCE_1950_OFFSET, time_system).plus_days(jd2)
+    }
+
+    #[staticmethod]
+    pub fn from_mjd(mjd: f64, time_system: TimeSystem) -> Self {
+        Self::from_days_since_1950(mjd - MJD_DAYS_SINCE_1950_OFFSET, time_system)
+    }
+
+    #[staticmethod]
+    pub fn from_gps_week(week: i32, seconds: f64) -> Self {
+        let day = GPS_EPOCH_DAYS_SINCE_1950 + DAYS_PER_WEEK * week as f64;
+        Self::from_days_and_seconds(day, seconds, TimeSystem::GPS)
+    }
+
+    #[staticmethod]
+    pub fn from_unix(seconds: f64) -> Self {
+        // Unix time counts every day as 86400 seconds, so leap seconds are skipped just as in UTC days since 1950
+        let days = (seconds * SECONDS_TO_DAYS).floor();
+        Self::from_days_and_seconds(
+            UNIX_EPOCH_DAYS_SINCE_1950 + days,
+            seconds - days * DAYS_TO_SECONDS,
+            TimeSystem::UTC,
+        )
+    }
+
+    #[staticmethod]
+    pub fn from_tle_epoch(tle_epoch: &str) -> PyResult<Self> {
+        let tle_epoch = tle_epoch.trim();
+        let error = || PyValueError::new_err(format!("Invalid TLE epoch '{}'", tle_epoch));
+        let (whole, fraction) = tle_epoch.split_once('.').unwrap_or((tle_epoch, ""));
+        if whole.len() != 5 || !whole.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
         }
+        let two_digit_year: i32 = whole[..2].parse().map_err(|_| error())?;
+        let day_of_year: i32 = whole[2..].parse().map_err(|_| error())?;
+        let fraction: f64 = match fraction.is_empty() {
+            true => 0.0,
+            false => format!("0.{}", fraction).parse().map_err(|_| error())?,
+        };
+        let year = match two_digit_year < TLE_YEAR_PIVOT {
+            true => 2000 + two_digit_year,
+            false => 1900 + two_digit_year,
+        };
+        let day = time_func_interface::year_doy_to_ds50(year, day_of_year as f64);
+        if day_of_year < 1 || day >= time_func_interface::year_doy_to_ds50(year + 1, 1.0) {
+            return Err(error());
+        }
+        Ok(Self::from_days_since_1950(day, TimeSystem::UTC).plus_days(fraction))
     }
 
     pub fn to_dtg_20(&self) -> String {
@@ -170,15 +402,38 @@ impl Epoch {
     }
 
     pub fn to_time_components(&self) -> TimeComponents {
-        let components = time_func_interface::ds50_to_ymd_components(self.days_since_1950);
+        let (day, nanoseconds) = self.get_day_and_nanoseconds();
+        let (year, month, day, _, _, _) = time_func_interface::ds50_to_ymd_components(day);
+        let seconds_of_day = nanoseconds / NANOSECONDS_PER_SECOND;
         TimeComponents {
-            year: components.0,
-            month: components.1,
-            day: components.2,
-            hour: components.3,
-            minute: components.4,
-            second: components.5,
+            year,
+            month,
+            day,
+            hour: (seconds_of_day / 3600) as i32,
+            minute: (seconds_of_day / 60 % 60) as i32,
+            second: (seconds_of_day % 60) as f64
+                + (nanoseconds % NANOSECONDS_PER_SECOND) as f64 / NANOSECONDS_PER_SECOND as f64,
+        }
+    }
+
+    pub fn to_utc_components(&self) -> TimeComponents {
+        let tai = self.to_system(TimeSystem::TAI).unwrap();
+        if let Some((midnight, tai_minus_utc)) = leap_seconds::find_leap_second(tai.days_since_1950) {
+            let leap_second_start = Self::from_days_and_seconds(midnight, tai_minus_utc, TimeSystem::TAI);
+            let elapsed = (tai - leap_second_start).in_seconds();
+            if (0.0..1.0).contains(&elapsed) {
+                let mut components = Self::from_days_and_seconds(midnight, -1.0, TimeSystem::UTC).to_time_components();
+                components.second = 60.0 + elapsed;
+                return components;
+            }
         }
+        tai.to_system(TimeSystem::UTC).unwrap().to_time_components()
+    }
+
+    #[getter]
+    pub fn get_tai_minus_utc(&self) -> f64 {
+        let tai = self.to_system(TimeSystem::TAI).unwrap();
+        leap_seconds::get_tai_minus_utc_at_tai(tai.days_since_1950)
     }
 
     #[getter]
@@ -186,6 +441,64 @@ impl Epoch {
         time_func_interface::ds50_to_year_doy(self.days_since_1950).1
     }
 
+    #[getter]
+    pub fn get_julian_date(&self) -> f64 {
+        let (jd1, jd2) = self.get_split_julian_date();
+        jd1 + jd2
+    }
+
+    #[getter]
+    pub fn get_split_julian_date(&self) -> (f64, f64) {
+        let (days, fraction) = self.get_days_since(0.0);
+        (days + JD_DAYS_SINCE_1950_OFFSET, fraction)
+    }
+
+    #[getter]
+    pub fn get_modified_julian_date(&self) -> f64 {
+        let (days, fraction) = self.get_days_since(-MJD_DAYS_SINCE_1950_OFFSET);
+        days + fraction
+    }
+
+    #[getter]
+    pub fn get_gps_week(&self) -> i32 {
+        let (days, _) = self
+            .to_system(TimeSystem::GPS)
+            .unwrap()
+            .get_days_since(GPS_EPOCH_DAYS_SINCE_1950);
+        (days / DAYS_PER_WEEK).floor() as i32
+    }
+
+    #[getter]
+    pub fn get_gps_seconds(&self) -> f64 {
+        let (days, fraction) = self
+            .to_system(TimeSystem::GPS)
+            .unwrap()
+            .get_days_since(GPS_EPOCH_DAYS_SINCE_1950);
+        let days_of_week = days - DAYS_PER_WEEK * (days / DAYS_PER_WEEK).floor();
+        (days_of_week + fraction) * DAYS_TO_SECONDS
+    }
+
+    #[getter]
+    pub fn get_unix_time(&self) -> f64 {
+        let (days, fraction) = self
+            .to_system(TimeSystem::UTC)
+            .unwrap()
+            .get_days_since(UNIX_EPOCH_DAYS_SINCE_1950);
+        days * DAYS_TO_SECONDS + fraction * DAYS_TO_SECONDS
+    }
+
+    pub fn to_tle_epoch(&self) -> String {
+        let (day, nanoseconds) = self.to_system(TimeSystem::UTC).unwrap().get_day_and_nanoseconds();
+        let (year, day_of_year) = time_func_interface::ds50_to_year_doy(day);
+        let fraction = nanoseconds as f64 / NANOSECONDS_PER_DAY as f64;
+        // Rounding to the eight TLE decimals may carry into the next day
+        let (year, day_of_year) = match (fraction * 1e8).round() >= 1e8 {
+            true => time_func_interface::ds50_to_year_doy(day + 1.0),
+            false => (year, day_of_year.floor() + fraction),
+        };
+        format!("{:02}{:012.8}", year % 100, day_of_year)
+    }
+
     pub fn to_fk4_greenwich_angle(&self) -> f64 {
         time_func_interface::get_fk4_greenwich_angle(self.to_system(TimeSystem::UT1).unwrap().days_since_1950)
     }
@@ -194,57 +507,83 @@ impl Epoch {
         time_func_interface::get_fk5_greenwich_angle(self.to_system(TimeSystem::UT1).unwrap().days_since_1950)
     }
 
+    pub fn to_earth_rotation_angle(&self) -> f64 {
+        earth_rotation::get_earth_rotation_angle(self.to_system(TimeSystem::UT1).unwrap().days_since_1950)
+    }
+
+    pub fn to_greenwich_angle(&self, model: EarthRotationModel) -> f64 {
+        let ut1 = self.to_system(TimeSystem::UT1).unwrap().days_since_1950;
+        let tt = self.to_system(TimeSystem::TT).unwrap().days_since_1950;
+        match model {
+            EarthRotationModel::FK4 => time_func_interface::get_fk4_greenwich_angle(ut1),
+            EarthRotationModel::FK5 => time_func_interface::get_fk5_greenwich_angle(ut1),
+            EarthRotationModel::GMST82 => earth_rotation::get_gmst_82(ut1),
+            EarthRotationModel::GAST82 => {
+                (earth_rotation::get_gmst_82(ut1) + earth_rotation::get_equation_of_equinoxes_94(tt)).rem_euclid(TAU)
+            }
+            EarthRotationModel::GMST06 => earth_rotation::get_gmst_06(ut1, tt),
+            EarthRotationModel::GAST06 => (earth_rotation::get_gmst_06(ut1, tt)
+                + earth_rotation::get_equation_of_equinoxes_00(tt))
+            .rem_euclid(TAU),
+            EarthRotationModel::ERA => earth_rotation::get_earth_rotation_angle(ut1),
+        }
+    }
+
+    // IAU 1994 form for the IAU-82 models and IAU 2000 form for the IAU 2006 models
+    pub fn to_equation_of_equinoxes(&self, model: EarthRotationModel) -> PyResult<f64> {
+        let tt = self.to_system(TimeSystem::TT)?.days_since_1950;
+        match model {
+            EarthRotationModel::GMST82 | EarthRotationModel::GAST82 => {
+                Ok(earth_rotation::get_equation_of_equinoxes_94(tt))
+            }
+            EarthRotationModel::GMST06 | EarthRotationModel::GAST06 => {
+                Ok(earth_rotation::get_equation_of_equinoxes_00(tt))
+            }
+            _ => Err(PyValueError::new_err(format!(
+                "{:?} has no equation of the equinoxes",
+                model
+            ))),
+        }
+    }
+
     fn __gt__(&self, other: &Self) -> bool {
-        self.days_since_1950 > other.days_since_1950
+        self > other
     }
 
     fn __lt__(&self, other: &Self) -> bool {
-        self.days_since_1950 < other.days_since_1950
+        self < other
     }
 
     fn __ne__(&self, other: &Self) -> bool {
-        self.days_since_1950 != other.days_since_1950
+        self != other
     }
 
     fn __ge__(&self, other: &Self) -> bool {
-        self.days_since_1950 >= other.days_since_1950
+        self >= other
     }
 
     fn __le__(&self, other: &Self) -> bool {
-        self.days_since_1950 <= other.days_since_1950
+        self <= other
     }
 
-    pub fn to_iso(&self) -> String {
-        self.to_time_components().to_iso()
+    #[pyo3(name = "to_iso", signature = (precision = DEFAULT_ISO_PRECISION))]
+    pub fn to_iso_with_precision(&self, precision: usize) -> PyResult<String> {
+        self.to_time_components().to_iso_with_precision(precision)
     }
 
     pub fn to_system(&self, time_system: TimeSystem) -> PyResult<Self> {
-        let days_since_1950 = match self.time_system {
-            TimeSystem::TAI => match time_system {
-                TimeSystem::UTC => time_func_interface::ds50_tai_to_utc(self.days_since_1950),
-                TimeSystem::UT1 => time_func_interface::ds50_tai_to_ut1(self.days_since_1950),
-                TimeSystem::TAI => self.days_since_1950,
-                _ => -1.0,
-            },
-            TimeSystem::UTC => match time_system {
-                TimeSystem::TAI => time_func_interface::ds50_utc_to_tai(self.days_since_1950),
-                TimeSystem::UT1 => time_func_interface::ds50_utc_to_ut1(self.days_since_1950),
-                TimeSystem::TT => time_func_interface::ds50_utc_to_tt(self.days_since_1950),
-                TimeSystem::UTC => self.days_since_1950,
-            },
-            TimeSystem::TT => -1.0,
-            TimeSystem::UT1 => -1.0,
-        };
-
-        match days_since_1950 {
-            -1.0 => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                "{} to {} conversion not supported",
-                self.time_system, time_system
-            ))),
-            _ => Ok(Self {
-                days_since_1950,
-                time_system,
-            }),
+        match self.time_system == time_system {
+            true => Ok(*self),
+            false => {
+                let tai = Self {
+                    time_system: TimeSystem::TAI,
+                    ..*self
+                }
+                .plus_days(self.get_days_to_tai());
+                let mut converted = tai.plus_days(Self::get_days_from_tai(tai.days_since_1950, time_system));
+                converted.time_system = time_system;
+                Ok(converted)
+            }
         }
     }
 }
@@ -266,7 +605,7 @@ mod tests {
             minute: 42,
             second: 42.0,
         };
-        Epoch::from_time_components(&components, TimeSystem::UTC)
+        Epoch::from_time_components(&components, TimeSystem::UTC).unwrap()
     }
 
     fn dec_20_2012_000000() -> Epoch {
@@ -278,7 +617,7 @@ mod tests {
             minute: 0,
             second: 0.0,
         };
-        Epoch::from_time_components(&components, TimeSystem::UTC)
+        Epoch::from_time_components(&components, TimeSystem::UTC).unwrap()
     }
 
     #[test]
@@ -342,4 +681,160 @@ mod tests {
         assert_eq!(utc_minus_ut1.in_seconds(), -0.28466011863201857);
         assert_abs_diff_eq!(utc_minus_tt.in_seconds(), -67.184, epsilon = 1e-6);
     }
+
+    #[test]
+    fn test_to_system_round_trip() {
+        let systems = [
+            TimeSystem::UTC,
+            TimeSystem::TAI,
+            TimeSystem::UT1,
+            TimeSystem::TT,
+            TimeSystem::GPS,
+            TimeSystem::TDB,
+            TimeSystem::TCG,
+        ];
+        for from_system in systems {
+            let epoch = Epoch::from_days_since_1950(dec_20_2012_000000().days_since_1950, from_system);
+            for to_system in systems {
+                let round_trip = epoch.to_system(to_system).unwrap().to_system(from_system).unwrap();
+                assert_eq!(round_trip.time_system(), from_system);
+                assert_abs_diff_eq!((round_trip - epoch).in_seconds(), 0.0, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_system_fixed_offsets() {
+        let tai = Epoch::from_days_since_1950(dec_20_2012_000000().days_since_1950, TimeSystem::TAI);
+        let tt = tai.to_system(TimeSystem::TT).unwrap();
+        let gps = tai.to_system(TimeSystem::GPS).unwrap();
+        let tdb = tai.to_system(TimeSystem::TDB).unwrap();
+        let tcg = tai.to_system(TimeSystem::TCG).unwrap();
+        let days_since_1950 = tai.days_since_1950;
+        assert_abs_diff_eq!((tt.days_since_1950 - days_since_1950) * 86400.0, 32.184, epsilon = 1e-6);
+        assert_abs_diff_eq!((gps.days_since_1950 - days_since_1950) * 86400.0, -19.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(
+            (tdb.days_since_1950 - tt.days_since_1950) * 86400.0,
+            0.0,
+            epsilon = 2e-3
+        );
+        assert_abs_diff_eq!((tcg.days_since_1950 - tt.days_since_1950) * 86400.0, 0.8, epsilon = 0.1);
+    }
+
+    #[test]
+    fn test_nanosecond_arithmetic() {
+        let start = dec_20_2012_000000();
+        let mut epoch = start;
+        for _ in 0..86400 {
+            epoch += TimeSpan::from_seconds(1.0);
+        }
+        assert_abs_diff_eq!((epoch - start).in_seconds(), 86400.0, epsilon = 1e-9);
+
+        let offset = start + TimeSpan::from_seconds(1e-9);
+        assert!(offset > start);
+        assert_abs_diff_eq!((offset - start).in_seconds(), 1e-9, epsilon = 1e-12);
+        assert_abs_diff_eq!(offset.seconds_of_day(), 1e-9, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_from_time_components_nanoseconds() {
+        let components = TimeComponents {
+            year: 2012,
+            month: 12,
+            day: 20,
+            hour: 23,
+            minute: 59,
+            second: 59.123456789,
+        };
+        let epoch = Epoch::from_time_components(&components, TimeSystem::UTC).unwrap();
+        let round_trip = epoch.to_time_components();
+        assert_eq!(round_trip.hour, 23);
+        assert_eq!(round_trip.minute, 59);
+        assert_abs_diff_eq!(round_trip.second, 59.123456789, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            (epoch - dec_20_2012_000000()).in_seconds(),
+            86399.123456789,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_leap_second() {
+        let components = TimeComponents {
+            year: 2016,
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59,
+            second: 60.25,
+        };
+        let leap_second = Epoch::from_time_components(&components, TimeSystem::UTC).unwrap();
+        assert_eq!(leap_second.time_system(), TimeSystem::TAI);
+        let round_trip = leap_second.to_utc_components();
+        assert_eq!((round_trip.day, round_trip.hour, round_trip.minute), (31, 23, 59));
+        assert_abs_diff_eq!(round_trip.second, 60.25, epsilon = 1e-9);
+        assert_eq!(leap_second.get_tai_minus_utc(), 36.0);
+
+        let midnight = Epoch::from_iso("2017-01-01T00:00:00.000", TimeSystem::UTC).unwrap();
+        assert_abs_diff_eq!((leap_second - midnight).in_seconds(), -0.75, epsilon = 1e-9);
+        assert_eq!(midnight.get_tai_minus_utc(), 37.0);
+        let last_second = Epoch::from_iso("2016-12-31T23:59:59.000", TimeSystem::UTC).unwrap();
+        assert!(last_second < leap_second && leap_second < midnight);
+        let tai_midnight = midnight.to_system(TimeSystem::TAI).unwrap();
+        assert_eq!(tai_midnight, midnight);
+        assert!(std::collections::HashSet::from([midnight]).contains(&tai_midnight));
+
+        let invalid = TimeComponents { day: 30, ..components };
+        assert!(invalid.validate(TimeSystem::UTC).is_err());
+        assert!(components.validate(TimeSystem::TAI).is_err());
+        assert!(TimeComponents {
+            month: 2,
+            day: 30,
+            second: 0.0,
+            ..components
+        }
+        .validate(TimeSystem::UTC)
+        .is_err());
+    }
+
+    #[test]
+    fn test_get_tai_minus_utc() {
+        assert_eq!(oct_31_1989_044242().get_tai_minus_utc(), 24.0);
+        assert_eq!(dec_20_2012_000000().get_tai_minus_utc(), 35.0);
+    }
+
+    #[test]
+    fn test_epoch_formats() {
+        let epoch = dec_20_2012_000000();
+        assert_eq!(epoch.get_julian_date(), 2456281.5);
+        assert_eq!(epoch.get_split_julian_date(), (2456281.5, 0.0));
+        assert_eq!(epoch.get_modified_julian_date(), 56281.0);
+        assert_eq!(epoch.get_unix_time(), 1355961600.0);
+        assert_eq!(epoch.get_gps_week(), 1719);
+        assert_eq!(epoch.get_gps_seconds(), 345616.0);
+        assert_eq!(epoch.to_tle_epoch(), "12355.00000000");
+
+        assert_eq!(Epoch::from_jd(2456281.5, TimeSystem::UTC), epoch);
+        assert_eq!(Epoch::from_mjd(56281.0, TimeSystem::UTC), epoch);
+        assert_eq!(Epoch::from_unix(1355961600.0), epoch);
+        assert_eq!(Epoch::from_tle_epoch("12355.00000000").unwrap(), epoch);
+        let gps = Epoch::from_gps_week(1719, 345616.0);
+        assert_eq!(gps.time_system(), TimeSystem::GPS);
+        assert_abs_diff_eq!((gps - epoch).in_seconds(), 0.0, epsilon = 1e-9);
+
+        let split = Epoch::from_split_jd(2456281.5, 1e-10, TimeSystem::UTC);
+        assert_abs_diff_eq!((split - epoch).in_seconds(), 8.64e-6, epsilon = 1e-12);
+        assert_eq!(split.get_split_julian_date().0, 2456281.5);
+        assert_abs_diff_eq!(split.get_split_julian_date().1, 1e-10, epsilon = 1e-16);
+
+        let afternoon = Epoch::from_tle_epoch("25123.75000000").unwrap();
+        assert_eq!(afternoon.to_iso(), "2025-05-03T18:00:00.000");
+        assert_eq!(afternoon.to_tle_epoch(), "25123.75000000");
+        assert_eq!(
+            Epoch::from_tle_epoch("99365.5").unwrap().to_iso(),
+            "1999-12-31T12:00:00.000"
+        );
+        assert!(Epoch::from_tle_epoch("25366.0").is_err());
+        assert!(Epoch::from_tle_epoch("2512.0").is_err());
+    }
 }
@@ -0,0 +1,265 @@
+use super::{Epoch, TimeSpan};
+use pyo3::exceptions::{PyIndexError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyAny, PySlice};
+
+// Epochs closer than this to a grid point are treated as lying on it
+const GRID_TOLERANCE_SECONDS: f64 = 1e-6;
+
+// Evenly spaced epochs start + i * step for i in 0..count
+#[pyclass]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochRange {
+    start: Epoch,
+    step: TimeSpan,
+    count: usize,
+}
+
+impl EpochRange {
+    pub fn from_step_and_count(start: Epoch, step: TimeSpan, count: usize) -> Result<Self, String> {
+        match step.in_seconds() > 0.0 {
+            true => Ok(Self { start, step, count }),
+            false => Err("Step must be positive".to_string()),
+        }
+    }
+
+    pub fn get_epoch(&self, index: usize) -> Epoch {
+        self.start + TimeSpan::from_days(self.step.in_days() * index as f64)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Epoch> + '_ {
+        (0..self.count).map(|index| self.get_epoch(index))
+    }
+
+    // Fractional number of steps from the start of the range to the epoch
+    fn get_grid_position(&self, epoch: Epoch) -> f64 {
+        (epoch - self.start).in_seconds() / self.step.in_seconds()
+    }
+
+    fn get_grid_tolerance(&self) -> f64 {
+        GRID_TOLERANCE_SECONDS / self.step.in_seconds()
+    }
+
+    fn is_aligned_with(&self, other: &Self) -> bool {
+        let step_difference = (self.step.in_seconds() - other.step.in_seconds()).abs();
+        let position = self.get_grid_position(other.start);
+        step_difference * self.count.max(other.count) as f64 <= GRID_TOLERANCE_SECONDS
+            && (position - position.round()).abs() <= self.get_grid_tolerance()
+    }
+
+    pub fn merge(&self, other: &Self) -> Result<Self, String> {
+        if !self.is_aligned_with(other) {
+            return Err("Ranges must share a step and a common grid to be merged".to_string());
+        }
+        let other_start = self.get_grid_position(other.start).round();
+        let other_end = other_start + other.count as f64;
+        if other_start > self.count as f64 || other_end < 0.0 {
+            return Err("Ranges must overlap or be adjacent to be merged".to_string());
+        }
+        let first = other_start.min(0.0);
+        let last = other_end.max(self.count as f64);
+        let start = match first < 0.0 {
+            true => other.start.to_system(self.start.time_system()).unwrap(),
+            false => self.start,
+        };
+        Self::from_step_and_count(start, self.step, (last - first) as usize)
+    }
+
+    // Epochs of this range that fall within the span of the other range
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let tolerance = self.get_grid_tolerance();
+        let first = (self.get_grid_position(other.start) - tolerance).ceil().max(0.0);
+        let last = (self.get_grid_position(other.get_end()?) + tolerance)
+            .floor()
+            .min(self.count as f64 - 1.0);
+        match last >= first {
+            true => Some(Self {
+                start: self.get_epoch(first as usize),
+                step: self.step,
+                count: (last - first) as usize + 1,
+            }),
+            false => None,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a EpochRange {
+    type Item = Epoch;
+    type IntoIter = Box<dyn Iterator<Item = Epoch> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+#[pyclass]
+pub struct EpochRangeIterator {
+    range: EpochRange,
+    index: usize,
+}
+
+#[pymethods]
+impl EpochRangeIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<Epoch> {
+        match self.index < self.range.count {
+            true => {
+                self.index += 1;
+                Some(self.range.get_epoch(self.index - 1))
+            }
+            false => None,
+        }
+    }
+}
+
+#[pymethods]
+impl EpochRange {
+    // Includes the end epoch when it falls on the grid
+    #[new]
+    pub fn new(start: Epoch, end: Epoch, step: TimeSpan) -> PyResult<Self> {
+        // The step divides the span, so it is checked before the count is formed
+        if !(step.in_seconds() > 0.0 && step.in_seconds().is_finite()) {
+            return Err(PyValueError::new_err("Step must be positive and finite"));
+        }
+        let span = (end - start).in_seconds();
+        if span < 0.0 {
+            return Err(PyValueError::new_err("End must not precede start"));
+        }
+        let steps = span / step.in_seconds();
+        let count = (steps + GRID_TOLERANCE_SECONDS / step.in_seconds()).floor() as usize + 1;
+        Self::from_step_and_count(start, step, count).map_err(PyValueError::new_err)
+    }
+
+    #[staticmethod]
+    pub fn from_count(start: Epoch, end: Epoch, count: usize) -> PyResult<Self> {
+        if count < 2 {
+            return Err(PyValueError::new_err("Count must be at least 2"));
+        }
+        let step = TimeSpan::from_days((end - start).in_days() / (count - 1) as f64);
+        Self::from_step_and_count(start, step, count).map_err(|_| PyValueError::new_err("End must follow start"))
+    }
+
+    #[getter]
+    pub fn get_start(&self) -> Epoch {
+        self.start
+    }
+
+    #[getter]
+    pub fn get_end(&self) -> Option<Epoch> {
+        match self.count {
+            0 => None,
+            count => Some(self.get_epoch(count - 1)),
+        }
+    }
+
+    #[getter]
+    pub fn get_step(&self) -> TimeSpan {
+        self.step
+    }
+
+    #[getter]
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    #[pyo3(name = "merge")]
+    fn py_merge(&self, other: &Self) -> PyResult<Self> {
+        self.merge(other).map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "intersection")]
+    fn py_intersection(&self, other: &Self) -> Option<Self> {
+        self.intersection(other)
+    }
+
+    fn __len__(&self) -> usize {
+        self.count
+    }
+
+    fn __iter__(&self) -> EpochRangeIterator {
+        EpochRangeIterator { range: *self, index: 0 }
+    }
+
+    fn __contains__(&self, epoch: Epoch) -> bool {
+        let position = self.get_grid_position(epoch);
+        let index = position.round();
+        (position - index).abs() <= self.get_grid_tolerance() && index >= 0.0 && index < self.count as f64
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn __getitem__<'py>(&self, key: &Bound<'py, PyAny>, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        if let Ok(slice) = key.downcast::<PySlice>() {
+            let indices = slice.indices(self.count as isize)?;
+            if indices.step < 0 {
+                return Err(PyValueError::new_err("Epoch ranges cannot be reversed"));
+            }
+            let range = Self {
+                start: self.get_epoch(indices.start.max(0) as usize),
+                step: TimeSpan::from_days(self.step.in_days() * indices.step as f64),
+                count: indices.slicelength,
+            };
+            Ok(Py::new(py, range)?.into_bound(py).into_any())
+        } else if let Ok(index) = key.extract::<isize>() {
+            let index = match index < 0 {
+                true => index + self.count as isize,
+                false => index,
+            };
+            match index >= 0 && (index as usize) < self.count {
+                true => Ok(Py::new(py, self.get_epoch(index as usize))?.into_bound(py).into_any()),
+                false => Err(PyIndexError::new_err("Epoch range index out of range")),
+            }
+        } else {
+            Err(PyTypeError::new_err("Epoch range indices must be integers or slices"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::TimeSystem;
+
+    fn hourly(start_hour: f64, end_hour: f64) -> EpochRange {
+        let start = Epoch::from_days_since_1950(27000.0, TimeSystem::UTC);
+        EpochRange::new(
+            start + TimeSpan::from_hours(start_hour),
+            start + TimeSpan::from_hours(end_hour),
+            TimeSpan::from_hours(1.0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let range = hourly(0.0, 3.0);
+        assert_eq!(range.get_count(), 4);
+        assert_eq!(range.get_end(), Some(range.get_start() + TimeSpan::from_hours(3.0)));
+        assert_eq!(hourly(0.0, 3.5).get_count(), 4);
+
+        let by_count = EpochRange::from_count(range.get_start(), range.get_end().unwrap(), 4).unwrap();
+        assert_eq!(by_count, range);
+        assert_eq!(range.iter().collect::<Vec<_>>(), by_count.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_merge() {
+        let merged = hourly(2.0, 5.0).merge(&hourly(0.0, 3.0)).unwrap();
+        assert_eq!(merged, hourly(0.0, 5.0));
+        assert_eq!(hourly(0.0, 2.0).merge(&hourly(3.0, 4.0)).unwrap(), hourly(0.0, 4.0));
+        assert!(hourly(0.0, 2.0).merge(&hourly(4.0, 5.0)).is_err());
+        assert!(hourly(0.0, 2.0).merge(&hourly(0.5, 2.5)).is_err());
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(hourly(0.0, 5.0).intersection(&hourly(2.0, 8.0)), Some(hourly(2.0, 5.0)));
+        assert_eq!(hourly(0.0, 5.0).intersection(&hourly(1.5, 3.5)), Some(hourly(2.0, 3.0)));
+        assert_eq!(hourly(0.0, 2.0).intersection(&hourly(3.0, 4.0)), None);
+    }
+}
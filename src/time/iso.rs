@@ -0,0 +1,405 @@
+use super::time_components::{get_days_in_month, is_leap_year};
+use super::TimeComponents;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::fmt;
+
+pub const DEFAULT_ISO_PRECISION: usize = 3;
+pub const MAX_ISO_PRECISION: usize = 9;
+const MINUTES_PER_DAY: i64 = 1440;
+
+create_exception!(time, IsoParseError, PyValueError);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IsoError {
+    // The text does not follow any supported layout
+    Syntax(String),
+    // A field is well formed but outside of its valid range
+    Range(String),
+}
+
+impl fmt::Display for IsoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IsoError::Syntax(message) | IsoError::Range(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<IsoError> for PyErr {
+    fn from(error: IsoError) -> Self {
+        IsoParseError::new_err(error.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IsoTimestamp {
+    pub components: TimeComponents,
+    pub offset_minutes: i32,
+}
+
+impl IsoTimestamp {
+    // Components of the same instant on a clock with no offset
+    pub fn get_components_at_zero_offset(&self) -> TimeComponents {
+        shift_minutes(&self.components, -self.offset_minutes as i64)
+    }
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date
+fn get_days_from_civil(year: i32, month: i32, day: i32) -> i64 {
+    let year = (year - (month <= 2) as i32) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn get_civil_from_days(days: i64) -> (i32, i32, i32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year as i32, month as i32, day as i32)
+}
+
+// ISO weekday from 1 (Monday) to 7 (Sunday), where 1970-01-01 was a Thursday
+fn get_weekday(days: i64) -> i64 {
+    (days + 3).rem_euclid(7) + 1
+}
+
+fn get_weeks_in_year(year: i32) -> i32 {
+    let jan_1 = get_weekday(get_days_from_civil(year, 1, 1));
+    match jan_1 == 4 || (jan_1 == 3 && is_leap_year(year)) {
+        true => 53,
+        false => 52,
+    }
+}
+
+// Hours and minutes are carried across days while seconds, including a leap second, are kept
+fn shift_minutes(components: &TimeComponents, minutes: i64) -> TimeComponents {
+    let days = get_days_from_civil(components.year, components.month, components.day);
+    let minute_of_day = 60 * components.hour as i64 + components.minute as i64 + minutes;
+    let (year, month, day) = get_civil_from_days(days + minute_of_day.div_euclid(MINUTES_PER_DAY));
+    let minute_of_day = minute_of_day.rem_euclid(MINUTES_PER_DAY);
+    TimeComponents::new(
+        year,
+        month,
+        day,
+        (minute_of_day / 60) as i32,
+        (minute_of_day % 60) as i32,
+        components.second,
+    )
+}
+
+fn parse_digits(text: &str, field: &str) -> Result<i32, IsoError> {
+    match !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) {
+        true => Ok(text.parse().unwrap()),
+        false => Err(IsoError::Syntax(format!("Invalid {} '{}'", field, text))),
+    }
+}
+
+fn check_range(value: i32, min: i32, max: i32, field: &str) -> Result<i32, IsoError> {
+    match (min..=max).contains(&value) {
+        true => Ok(value),
+        false => Err(IsoError::Range(format!(
+            "{} {} is outside {}-{}",
+            field, value, min, max
+        ))),
+    }
+}
+
+fn get_calendar_date(year: i32, month: i32, day: i32) -> Result<(i32, i32, i32), IsoError> {
+    check_range(month, 1, 12, "Month")?;
+    check_range(day, 1, get_days_in_month(year, month), "Day")?;
+    Ok((year, month, day))
+}
+
+fn get_ordinal_date(year: i32, day_of_year: i32) -> Result<(i32, i32, i32), IsoError> {
+    let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+    check_range(day_of_year, 1, days_in_year, "Day of year")?;
+    Ok(get_civil_from_days(
+        get_days_from_civil(year, 1, 1) + day_of_year as i64 - 1,
+    ))
+}
+
+// Week 1 is the week that contains January 4
+fn get_week_date(year: i32, week: i32, weekday: i32) -> Result<(i32, i32, i32), IsoError> {
+    check_range(week, 1, get_weeks_in_year(year), "Week")?;
+    check_range(weekday, 1, 7, "Weekday")?;
+    let jan_4 = get_days_from_civil(year, 1, 4);
+    let week_1_monday = jan_4 - (get_weekday(jan_4) - 1);
+    Ok(get_civil_from_days(
+        week_1_monday + 7 * (week as i64 - 1) + weekday as i64 - 1,
+    ))
+}
+
+fn parse_week(text: &str) -> Result<(i32, i32), IsoError> {
+    let digits = text
+        .strip_prefix('W')
+        .ok_or_else(|| IsoError::Syntax(format!("Invalid week '{}'", text)))?;
+    match digits.len() {
+        2 => Ok((parse_digits(digits, "week")?, 1)),
+        3 => Ok((
+            parse_digits(&digits[..2], "week")?,
+            parse_digits(&digits[2..], "weekday")?,
+        )),
+        _ => Err(IsoError::Syntax(format!("Invalid week '{}'", text))),
+    }
+}
+
+fn parse_date(text: &str) -> Result<(i32, i32, i32), IsoError> {
+    let invalid = || IsoError::Syntax(format!("Invalid date '{}'", text));
+    if text.len() < 4 {
+        return Err(invalid());
+    }
+    let year = parse_digits(&text[..4], "year")?;
+    let rest = &text[4..];
+    if let Some(extended) = rest.strip_prefix('-') {
+        let fields: Vec<&str> = extended.split('-').collect();
+        return match fields.as_slice() {
+            [week] if week.starts_with('W') && week.len() == 3 => get_week_date(year, parse_week(week)?.0, 1),
+            [week, weekday] if week.starts_with('W') && week.len() == 3 && weekday.len() == 1 => {
+                get_week_date(year, parse_week(week)?.0, parse_digits(weekday, "weekday")?)
+            }
+            [month] if month.len() == 2 => get_calendar_date(year, parse_digits(month, "month")?, 1),
+            [day_of_year] if day_of_year.len() == 3 => {
+                get_ordinal_date(year, parse_digits(day_of_year, "day of year")?)
+            }
+            [month, day] if month.len() == 2 && day.len() == 2 => {
+                get_calendar_date(year, parse_digits(month, "month")?, parse_digits(day, "day")?)
+            }
+            _ => Err(invalid()),
+        };
+    }
+    match rest.len() {
+        0 => Ok((year, 1, 1)),
+        _ if rest.starts_with('W') => {
+            let (week, weekday) = parse_week(rest)?;
+            get_week_date(year, week, weekday)
+        }
+        3 => get_ordinal_date(year, parse_digits(rest, "day of year")?),
+        4 => get_calendar_date(
+            year,
+            parse_digits(&rest[..2], "month")?,
+            parse_digits(&rest[2..], "day")?,
+        ),
+        _ => Err(invalid()),
+    }
+}
+
+// Offsets are Z or a sign followed by hh, hh:mm or hhmm
+fn parse_offset(text: &str) -> Result<i32, IsoError> {
+    if text.eq_ignore_ascii_case("Z") {
+        return Ok(0);
+    }
+    let invalid = || IsoError::Syntax(format!("Invalid offset '{}'", text));
+    let sign = match text.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return Err(invalid()),
+    };
+    let digits = text[1..].replacen(':', "", 1);
+    if (text.len() == 6 && text.as_bytes()[3] != b':') || !matches!(digits.len(), 2 | 4) {
+        return Err(invalid());
+    }
+    let hours = check_range(parse_digits(&digits[..2], "offset hours")?, 0, 23, "Offset hours")?;
+    let minutes = match digits.len() {
+        4 => check_range(parse_digits(&digits[2..], "offset minutes")?, 0, 59, "Offset minutes")?,
+        _ => 0,
+    };
+    Ok(sign * (60 * hours + minutes))
+}
+
+// Times are hh, hh:mm or hh:mm:ss in extended or basic form, where the last field may carry a decimal fraction
+fn parse_time(text: &str) -> Result<(i32, i32, f64), IsoError> {
+    let invalid = || IsoError::Syntax(format!("Invalid time '{}'", text));
+    let (whole, fraction) = match text.find(['.', ',']) {
+        Some(i) => {
+            let digits = &text[i + 1..];
+            parse_digits(digits, "fraction")?;
+            (&text[..i], format!("0.{}", digits).parse::<f64>().unwrap())
+        }
+        None => (text, 0.0),
+    };
+    let fields: Vec<&str> = match whole.contains(':') {
+        true => whole.split(':').collect(),
+        false => whole
+            .as_bytes()
+            .chunks(2)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap())
+            .collect(),
+    };
+    if fields.is_empty() || fields.len() > 3 || fields.iter().any(|field| field.len() != 2) {
+        return Err(invalid());
+    }
+    let hour = parse_digits(fields[0], "hour")?;
+    let (minute, second) = match fields.len() {
+        1 => {
+            let seconds = 3600.0 * fraction;
+            let minute = (seconds / 60.0).floor();
+            (minute as i32, seconds - 60.0 * minute)
+        }
+        2 => (parse_digits(fields[1], "minute")?, 60.0 * fraction),
+        _ => (
+            parse_digits(fields[1], "minute")?,
+            parse_digits(fields[2], "second")? as f64 + fraction,
+        ),
+    };
+    check_range(hour, 0, 24, "Hour")?;
+    check_range(minute, 0, 59, "Minute")?;
+    if !(0.0..61.0).contains(&second) {
+        return Err(IsoError::Range(format!("Second {} is outside 0-60", second)));
+    }
+    if hour == 24 && (minute != 0 || second != 0.0) {
+        return Err(IsoError::Range("Hour 24 is only valid at 24:00:00".to_string()));
+    }
+    Ok((hour, minute, second))
+}
+
+pub fn parse(text: &str) -> Result<IsoTimestamp, IsoError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(IsoError::Syntax("Empty timestamp".to_string()));
+    }
+    if !text.is_ascii() {
+        return Err(IsoError::Syntax(format!("Invalid timestamp '{}'", text)));
+    }
+    let (date, time) = match text.find(['T', 't', ' ']) {
+        Some(i) => (&text[..i], Some(&text[i + 1..])),
+        None => (text, None),
+    };
+    let (year, month, day) = parse_date(date)?;
+    let (hour, minute, second, offset_minutes) = match time {
+        Some(time) => {
+            let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+                Some(i) => (&time[..i], parse_offset(&time[i..])?),
+                None => (time, 0),
+            };
+            let (hour, minute, second) = parse_time(time)?;
+            (hour, minute, second, offset)
+        }
+        None => (0, 0, 0.0, 0),
+    };
+
+    // 24:00 is the end of the day and is carried into the start of the next
+    let components = match hour {
+        24 => shift_minutes(&TimeComponents::new(year, month, day, 0, 0, 0.0), MINUTES_PER_DAY),
+        _ => TimeComponents::new(year, month, day, hour, minute, second),
+    };
+    Ok(IsoTimestamp {
+        components,
+        offset_minutes,
+    })
+}
+
+// Seconds are rounded to the given number of decimals and carried into the minute when they round up to 60
+pub fn format(components: &TimeComponents, precision: usize) -> Result<String, String> {
+    if precision > MAX_ISO_PRECISION {
+        return Err(format!("Precision must not exceed {} decimals", MAX_ISO_PRECISION));
+    }
+    let scale = 10u64.pow(precision as u32);
+    let mut units = (components.second * scale as f64).round() as u64;
+    let components = match components.second < 60.0 && units >= 60 * scale {
+        true => {
+            units -= 60 * scale;
+            shift_minutes(components, 1)
+        }
+        false => *components,
+    };
+    let units = units.min(61 * scale - 1);
+    let fraction = match precision {
+        0 => String::new(),
+        _ => format!(".{:0width$}", units % scale, width = precision),
+    };
+    Ok(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        components.year,
+        components.month,
+        components.day,
+        components.hour,
+        components.minute,
+        units / scale,
+        fraction
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_components(text: &str) -> (i32, i32, i32, i32, i32, f64) {
+        let components = parse(text).unwrap().get_components_at_zero_offset();
+        (
+            components.year,
+            components.month,
+            components.day,
+            components.hour,
+            components.minute,
+            components.second,
+        )
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let expected = (2025, 4, 15, 0, 0, 0.0);
+        assert_eq!(parse_components("2025-04-15"), expected);
+        assert_eq!(parse_components("20250415"), expected);
+        assert_eq!(parse_components("2025-105"), expected);
+        assert_eq!(parse_components("2025105"), expected);
+        assert_eq!(parse_components("2025-W16-2"), expected);
+        assert_eq!(parse_components("2025W162"), expected);
+        assert_eq!(parse_components("2020-W53-7"), (2021, 1, 3, 0, 0, 0.0));
+        assert_eq!(parse_components("2025-W01"), (2024, 12, 30, 0, 0, 0.0));
+    }
+
+    #[test]
+    fn test_parse_times() {
+        assert_eq!(parse_components("2025-04-15T04:02:42.42"), (2025, 4, 15, 4, 2, 42.42));
+        assert_eq!(parse_components("2025-04-15t040242,42Z"), (2025, 4, 15, 4, 2, 42.42));
+        assert_eq!(parse_components("2025-04-15 04:30"), (2025, 4, 15, 4, 30, 0.0));
+        assert_eq!(parse_components("2025-04-15T04.5"), (2025, 4, 15, 4, 30, 0.0));
+        assert_eq!(parse_components("2025-04-15T24:00:00"), (2025, 4, 16, 0, 0, 0.0));
+        assert_eq!(parse_components("2025-04-15T01:00:00+02:00"), (2025, 4, 14, 23, 0, 0.0));
+        assert_eq!(parse_components("2025-04-15T23:30:00-0100"), (2025, 4, 16, 0, 30, 0.0));
+        assert_eq!(
+            parse_components("2017-01-01T00:59:60.5+01"),
+            (2016, 12, 31, 23, 59, 60.5)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse(""), Err(IsoError::Syntax(_))));
+        assert!(matches!(parse("2025-4-15"), Err(IsoError::Syntax(_))));
+        assert!(matches!(parse("2025-04-15T04:02:4x"), Err(IsoError::Syntax(_))));
+        assert!(matches!(parse("2025-04-15T04:02:42+2"), Err(IsoError::Syntax(_))));
+        assert!(matches!(parse("2025-02-29"), Err(IsoError::Range(_))));
+        assert!(matches!(parse("2025-366"), Err(IsoError::Range(_))));
+        assert!(matches!(parse("2025-W53-1"), Err(IsoError::Range(_))));
+        assert!(matches!(parse("2025-04-15T24:00:01"), Err(IsoError::Range(_))));
+        assert!(matches!(parse("2025-04-15T12:60:00"), Err(IsoError::Range(_))));
+    }
+
+    #[test]
+    fn test_format() {
+        let components = TimeComponents::new(2025, 12, 31, 23, 59, 59.9996);
+        assert_eq!(format(&components, 3).unwrap(), "2026-01-01T00:00:00.000");
+        assert_eq!(format(&components, 4).unwrap(), "2025-12-31T23:59:59.9996");
+        assert_eq!(format(&components, 0).unwrap(), "2026-01-01T00:00:00");
+        let leap_second = TimeComponents::new(2016, 12, 31, 23, 59, 60.9999);
+        assert_eq!(format(&leap_second, 3).unwrap(), "2016-12-31T23:59:60.999");
+        assert!(format(&components, 10).is_err());
+    }
+}
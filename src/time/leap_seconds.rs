@@ -0,0 +1,133 @@
+use super::{Epoch, DAYS_TO_SECONDS, SECONDS_TO_DAYS};
+use crate::enums::TimeSystem;
+use pyo3::prelude::*;
+
+// Modified Julian Date of 1950 January 0.0, the origin of days since 1950
+const MJD_DAYS_SINCE_1950_OFFSET: f64 = 33281.0;
+const MJD_OF_1972: f64 = 41317.0;
+
+// TAI - UTC from USNO tai-utc.dat as (MJD of change, seconds at reference MJD, reference MJD, seconds per day)
+const TAI_MINUS_UTC: [(f64, f64, f64, f64); 41] = [
+    (37300.0, 1.4228180, 37300.0, 0.001296),
+    (37512.0, 1.3728180, 37300.0, 0.001296),
+    (37665.0, 1.8458580, 37665.0, 0.0011232),
+    (38334.0, 1.9458580, 37665.0, 0.0011232),
+    (38395.0, 3.2401300, 38761.0, 0.001296),
+    (38486.0, 3.3401300, 38761.0, 0.001296),
+    (38639.0, 3.4401300, 38761.0, 0.001296),
+    (38761.0, 3.5401300, 38761.0, 0.001296),
+    (38820.0, 3.6401300, 38761.0, 0.001296),
+    (38942.0, 3.7401300, 38761.0, 0.001296),
+    (39004.0, 3.8401300, 38761.0, 0.001296),
+    (39126.0, 4.3131700, 39126.0, 0.002592),
+    (39887.0, 4.2131700, 39126.0, 0.002592),
+    (41317.0, 10.0, 0.0, 0.0),
+    (41499.0, 11.0, 0.0, 0.0),
+    (41683.0, 12.0, 0.0, 0.0),
+    (42048.0, 13.0, 0.0, 0.0),
+    (42413.0, 14.0, 0.0, 0.0),
+    (42778.0, 15.0, 0.0, 0.0),
+    (43144.0, 16.0, 0.0, 0.0),
+    (43509.0, 17.0, 0.0, 0.0),
+    (43874.0, 18.0, 0.0, 0.0),
+    (44239.0, 19.0, 0.0, 0.0),
+    (44786.0, 20.0, 0.0, 0.0),
+    (45151.0, 21.0, 0.0, 0.0),
+    (45516.0, 22.0, 0.0, 0.0),
+    (46247.0, 23.0, 0.0, 0.0),
+    (47161.0, 24.0, 0.0, 0.0),
+    (47892.0, 25.0, 0.0, 0.0),
+    (48257.0, 26.0, 0.0, 0.0),
+    (48804.0, 27.0, 0.0, 0.0),
+    (49169.0, 28.0, 0.0, 0.0),
+    (49534.0, 29.0, 0.0, 0.0),
+    (50083.0, 30.0, 0.0, 0.0),
+    (50630.0, 31.0, 0.0, 0.0),
+    (51179.0, 32.0, 0.0, 0.0),
+    (53736.0, 33.0, 0.0, 0.0),
+    (54832.0, 34.0, 0.0, 0.0),
+    (56109.0, 35.0, 0.0, 0.0),
+    (57204.0, 36.0, 0.0, 0.0),
+    (57754.0, 37.0, 0.0, 0.0),
+];
+
+fn get_mjd(days_since_1950: f64) -> f64 {
+    days_since_1950 + MJD_DAYS_SINCE_1950_OFFSET
+}
+
+fn get_entry_offset(index: usize, utc_mjd: f64) -> f64 {
+    let (_, seconds, reference_mjd, rate) = TAI_MINUS_UTC[index];
+    seconds + (utc_mjd - reference_mjd) * rate
+}
+
+fn find_entry(utc_mjd: f64) -> Option<usize> {
+    TAI_MINUS_UTC
+        .iter()
+        .rposition(|(start_mjd, _, _, _)| utc_mjd >= *start_mjd)
+}
+
+// TAI - UTC in seconds at a UTC epoch, or zero before UTC was defined in 1961
+pub fn get_tai_minus_utc_at_utc(utc_days_since_1950: f64) -> f64 {
+    let utc_mjd = get_mjd(utc_days_since_1950);
+    match find_entry(utc_mjd) {
+        Some(index) => get_entry_offset(index, utc_mjd),
+        None => 0.0,
+    }
+}
+
+// TAI - UTC in seconds at a TAI epoch, where an inserted leap second takes the offset that precedes it
+pub fn get_tai_minus_utc_at_tai(tai_days_since_1950: f64) -> f64 {
+    let tai_mjd = get_mjd(tai_days_since_1950);
+    let index = (0..TAI_MINUS_UTC.len()).rev().find(|index| {
+        let start_mjd = TAI_MINUS_UTC[*index].0;
+        tai_mjd >= start_mjd + get_entry_offset(*index, start_mjd) * SECONDS_TO_DAYS
+    });
+    match index {
+        Some(index) => {
+            // Drifting offsets before 1972 are defined at UTC, which is found by fixed-point iteration
+            let mut utc_mjd = tai_mjd;
+            for _ in 0..2 {
+                utc_mjd = tai_mjd - get_entry_offset(index, utc_mjd) * SECONDS_TO_DAYS;
+            }
+            get_entry_offset(index, utc_mjd)
+        }
+        None => 0.0,
+    }
+}
+
+// UTC midnight that ends an inserted leap second and the TAI - UTC in seconds before it, for a TAI epoch within
+// or near that leap second
+pub fn find_leap_second(tai_days_since_1950: f64) -> Option<(f64, f64)> {
+    let tai_mjd = get_mjd(tai_days_since_1950);
+    TAI_MINUS_UTC.windows(2).find_map(|entries| {
+        let (start_mjd, seconds, _, _) = entries[1];
+        let previous_seconds = entries[0].1;
+        let elapsed = (tai_mjd - start_mjd) * DAYS_TO_SECONDS - previous_seconds;
+        match start_mjd > MJD_OF_1972 && seconds > previous_seconds && elapsed.abs() <= 1.0 {
+            true => Some((start_mjd - MJD_DAYS_SINCE_1950_OFFSET, previous_seconds)),
+            false => None,
+        }
+    })
+}
+
+// True when the UTC day starting at the given whole days since 1950 ends with an inserted leap second
+pub fn ends_with_leap_second(utc_day: f64) -> bool {
+    let next_mjd = get_mjd(utc_day) + 1.0;
+    TAI_MINUS_UTC
+        .windows(2)
+        .any(|entries| entries[1].0 == next_mjd && entries[1].0 > MJD_OF_1972 && entries[1].1 > entries[0].1)
+}
+
+#[pyfunction]
+pub fn get_leap_seconds() -> Vec<(Epoch, f64)> {
+    TAI_MINUS_UTC
+        .iter()
+        .filter(|(start_mjd, _, _, _)| *start_mjd >= MJD_OF_1972)
+        .map(|(start_mjd, seconds, _, _)| {
+            (
+                Epoch::from_days_since_1950(start_mjd - MJD_DAYS_SINCE_1950_OFFSET, TimeSystem::UTC),
+                *seconds,
+            )
+        })
+        .collect()
+}
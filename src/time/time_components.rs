@@ -1,3 +1,8 @@
+use super::iso::{self, DEFAULT_ISO_PRECISION};
+use super::leap_seconds;
+use crate::enums::TimeSystem;
+use crate::saal::time_func_interface;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
 #[pyclass]
@@ -11,7 +16,60 @@ pub struct TimeComponents {
     pub second: f64,
 }
 
-impl TimeComponents {}
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn get_days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl TimeComponents {
+    // Seconds of 60 are only valid in UTC at the end of a day that ends with an inserted leap second
+    pub fn validate(&self, time_system: TimeSystem) -> Result<(), String> {
+        if !(1..=12).contains(&self.month) {
+            return Err(format!("Invalid month {}", self.month));
+        }
+        if !(1..=get_days_in_month(self.year, self.month)).contains(&self.day) {
+            return Err(format!(
+                "Invalid day {} for {:04}-{:02}",
+                self.day, self.year, self.month
+            ));
+        }
+        if !(0..=23).contains(&self.hour) || !(0..=59).contains(&self.minute) {
+            return Err(format!("Invalid time {:02}:{:02}", self.hour, self.minute));
+        }
+        let max_second = match self.is_leap_second(time_system) {
+            true => 61.0,
+            false => 60.0,
+        };
+        if !(0.0..max_second).contains(&self.second) {
+            return Err(format!("Invalid second {} in {}", self.second, self.to_iso()));
+        }
+        Ok(())
+    }
+
+    pub fn is_leap_second(&self, time_system: TimeSystem) -> bool {
+        time_system == TimeSystem::UTC
+            && self.hour == 23
+            && self.minute == 59
+            && self.second >= 60.0
+            && leap_seconds::ends_with_leap_second(self.get_day_days_since_1950())
+    }
+
+    pub fn get_day_days_since_1950(&self) -> f64 {
+        time_func_interface::ymd_components_to_ds50(self.year, self.month, self.day, 0, 0, 0.0)
+    }
+
+    pub fn to_iso(&self) -> String {
+        iso::format(self, DEFAULT_ISO_PRECISION).unwrap()
+    }
+}
 
 #[pymethods]
 impl TimeComponents {
@@ -87,28 +145,15 @@ impl TimeComponents {
         }
     }
 
-    pub fn to_iso(&self) -> String {
-        format!(
-            "{:04}-{:02}-{:02}T{:02}:{:02}:{:06.3}",
-            self.year, self.month, self.day, self.hour, self.minute, self.second
-        )
+    #[pyo3(name = "to_iso", signature = (precision = DEFAULT_ISO_PRECISION))]
+    pub fn to_iso_with_precision(&self, precision: usize) -> PyResult<String> {
+        iso::format(self, precision).map_err(PyValueError::new_err)
     }
 
+    // Timestamps with an offset are returned as the components of the same instant at zero offset
     #[staticmethod]
-    pub fn from_iso(iso: &str) -> Self {
-        let date_time: Vec<&str> = iso.split("T").collect();
-        let ymd: Vec<&str> = date_time[0].split("-").collect();
-        let z_stripped = date_time[1].replace("Z", "");
-        let hms: Vec<&str> = z_stripped.split(":").collect();
-
-        Self {
-            year: ymd[0].parse().unwrap(),
-            month: ymd[1].parse().unwrap(),
-            day: ymd[2].parse().unwrap(),
-            hour: hms[0].parse().unwrap(),
-            minute: hms[1].parse().unwrap(),
-            second: hms[2].parse().unwrap(),
-        }
+    pub fn from_iso(iso: &str) -> PyResult<Self> {
+        Ok(iso::parse(iso)?.get_components_at_zero_offset())
     }
 
     fn __eq__(&self, other: &Self) -> PyResult<bool> {
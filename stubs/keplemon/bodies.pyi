@@ -1,8 +1,9 @@
 # flake8: noqa
 from keplemon.elements import TLE, CartesianState, Ephemeris
 from keplemon.catalogs import TLECatalog
-from keplemon.time import Epoch, TimeSpan
+from keplemon.time import Epoch, EpochRange, TimeSpan
 from keplemon.events import CloseApproach, CloseApproachReport
+from keplemon.estimation import Observation, CorrelationReport
 
 class Earth:
     @staticmethod
@@ -49,7 +50,44 @@ class Satellite:
         start: Epoch,
         end: Epoch,
         step: TimeSpan,
-    ) -> Ephemeris: ...
+    ) -> Ephemeris | None:
+        """
+        Args:
+            start: UTC epoch of the start of the ephemeris
+            end: UTC epoch of the end of the ephemeris
+            step: Time step for the ephemeris
+
+        Returns:
+            Ephemeris from `start` through `end`, holding only the state at `start` when `end` precedes it, or
+            None if any epoch cannot be propagated
+
+        Raises:
+            ValueError: If `step` is not positive
+        """
+        ...
+    def get_ephemeris_over(self, epochs: EpochRange) -> Ephemeris | None:
+        """
+        Args:
+            epochs: UTC epochs at which states are generated
+
+        Returns:
+            Ephemeris with one state per epoch or None if any epoch cannot be propagated
+        """
+        ...
+
+    def get_close_approach_over(
+        self,
+        other: Satellite,
+        epochs: EpochRange,
+        distance_threshold: float,
+    ) -> None | CloseApproach:
+        """
+        Args:
+            other: Satellite to compare against
+            epochs: UTC epochs searched, where the step sets the search interval
+            distance_threshold: Distance threshold for close approach screening in **_kilometers_**
+        """
+        ...
     def get_state_at_epoch(self, epoch: Epoch) -> CartesianState: ...
     def to_tle(self) -> TLE | None:
         """
@@ -104,6 +142,19 @@ class Constellation:
             end: UTC epoch of the end of the ephemeris
             step: Time step for the ephemeris
 
+        Returns:
+            (satellite_id, ephemeris) dictionary for the constellation
+
+        Raises:
+            ValueError: If `step` is not positive
+        """
+        ...
+
+    def get_ephemeris_over(self, epochs: EpochRange) -> dict[int, Ephemeris | None]:
+        """
+        Args:
+            epochs: UTC epochs at which states are generated
+
         Returns:
             (satellite_id, ephemeris) dictionary for the constellation
         """
@@ -130,6 +181,25 @@ class Constellation:
         """
         ...
 
+    def get_ca_report_vs_one_over(
+        self,
+        other: Satellite,
+        epochs: EpochRange,
+        distance_threshold: float,
+    ) -> CloseApproachReport:
+        """
+        Calculate close approaches between the constellation and a given satellite over a grid of epochs.
+
+        Args:
+            other: Satellite to compare against
+            epochs: UTC epochs searched, where the step sets the search interval
+            distance_threshold: Distance threshold for close approach screening in **_kilometers_**
+
+        Returns:
+            Close approach report for the constellation vs. the given satellite
+        """
+        ...
+
     def get_ca_report_vs_many(
         self,
         start: Epoch,
@@ -152,6 +222,47 @@ class Constellation:
         """
         ...
 
+    def get_ca_report_vs_many_over(
+        self,
+        epochs: EpochRange,
+        distance_threshold: float,
+    ) -> CloseApproachReport:
+        """
+        Calculate close approaches among satellites in the calling constellation over a grid of epochs.
+
+        Args:
+            epochs: UTC epochs searched, where the step sets the search interval
+            distance_threshold: Distance threshold for close approach screening in **_kilometers_**
+
+        Returns:
+            Close approach report for the constellation vs. all other satellites
+        """
+        ...
+
+    def get_correlation_report(
+        self,
+        obs: list[Observation],
+        significance: float | None = None,
+    ) -> CorrelationReport:
+        """
+        Score observations of an unknown object against every satellite in the constellation.
+
+        !!! note
+            All observations are treated as a single track of one object.
+            Satellites that cannot be propagated to every observation are not reported.
+
+        Args:
+            obs: Observations to correlate
+            significance: Minimum p-value for a candidate to be considered correlated (defaults to 0.001)
+
+        Returns:
+            Candidates ranked by chi-square of the residuals against the sensor noise
+
+        Raises:
+            ValueError: If a sensor is missing the noise for one of its measurements
+        """
+        ...
+
     def __getitem__(self, satellite_id: int) -> Satellite: ...
 
 class Sensor:
@@ -161,6 +272,9 @@ class Sensor:
         angular_noise: Angular noise in **_degrees_**
     """
 
+    sensor_id: int | None
+    """Number used to identify the sensor in B3 records"""
+
     name: str
     angular_noise: float
     range_noise: float | None
@@ -171,6 +285,37 @@ class Sensor:
 
     angular_rate_noise: float | None
     """Angular rate noise in **_degrees per second_**"""
+
+    horizon_angles: bool
+    """Flag for sensors that report azimuth and elevation instead of right ascension and declination
+
+    !!! note
+        Defaults to False.
+    """
+
+    right_ascension_bias: float
+    """Bias added to predicted right ascension in **_degrees_**"""
+
+    declination_bias: float
+    """Bias added to predicted declination in **_degrees_**"""
+
+    range_bias: float
+    """Bias added to predicted range in **_kilometers_**"""
+
+    range_rate_bias: float
+    """Bias added to predicted range rate in **_kilometers per second_**"""
+
+    azimuth_bias: float
+    """Bias added to predicted azimuth in **_degrees_**"""
+
+    elevation_bias: float
+    """Bias added to predicted elevation in **_degrees_**"""
+
+    min_elevation: float | None
+    """Lowest elevation at which the sensor can observe in **_degrees_**"""
+
+    max_range: float | None
+    """Largest range at which the sensor can observe in **_kilometers_**"""
     def __init__(self, name: str, angular_noise: float) -> None: ...
 
 class Observatory:
@@ -182,6 +327,9 @@ class Observatory:
         altitude: Altitude in **_kilometers_**
     """
 
+    site_id: int | None
+    """Number used to identify the site in observation files such as the CRD pad ID"""
+
     name: str
     latitude: float
     longitude: float
@@ -1,5 +1,7 @@
 # flake8: noqa
 from keplemon.elements import TLE
+from keplemon.bodies import Observatory, Sensor
+from keplemon.estimation import Observation
 
 class TLECatalog:
     count: int
@@ -14,3 +16,146 @@ class TLECatalog:
     def get_count(self) -> int: ...
     def clear(self) -> None: ...
     def __getitem__(self, satellite_id: int) -> TLE: ...
+
+class ObservationCatalog:
+    count: int
+    name: str | None
+    observations: list[Observation]
+    def __init__(self) -> None: ...
+    @classmethod
+    def from_b3_file(cls, filename: str, observatories: list[Observatory]) -> ObservationCatalog:
+        """
+        Args:
+            filename: Path to a file of fixed-column B3 records
+            observatories: Observatories whose sensor IDs (or site IDs) match the B3 sensor numbers
+
+        Returns:
+            Catalog of the angles, range and range rate observations in the file
+
+        !!! note
+            Right ascension and declination are rotated to TEME of date.  Records of types 7 and 8 are skipped,
+            and type 9 records are space-based observations that take the observer position from the record
+            rather than the observatory.
+
+        Raises:
+            OSError: If the file cannot be read
+            ValueError: If a record is malformed or references an unknown sensor
+        """
+        ...
+
+    @classmethod
+    def from_tdm_file(cls, filename: str, observatories: list[Observatory]) -> ObservationCatalog:
+        """
+        Args:
+            filename: Path to a CCSDS Tracking Data Message in KVN or XML notation
+            observatories: Observatories whose sensor or observatory names match a TDM participant
+
+        Returns:
+            Catalog of the angles, range and instantaneous Doppler observations in the file
+
+        !!! note
+            A numeric participant other than the matched observatory is used as the observed satellite ID.
+            Ranges must be in kilometers and
+            Doppler in kilometers per second.  EME2000 and ICRF angles are rotated to TEME of date.
+
+        Raises:
+            OSError: If the file cannot be read
+            ValueError: If the message is malformed or uses unsupported metadata
+        """
+        ...
+
+    @classmethod
+    def from_crd_file(cls, filename: str, observatories: list[Observatory]) -> ObservationCatalog:
+        """
+        Args:
+            filename: Path to an ILRS Consolidated Laser Ranging Data file
+            observatories: Observatories whose site IDs match the CDP pad IDs or whose names match the stations
+
+        Returns:
+            Catalog of two-way range observations tagged at the spacecraft bounce time
+
+        !!! note
+            Ranges are half the time of flight and are not corrected for troposphere or the target center of mass.
+            Sessions that are not two-way are skipped.
+
+        Raises:
+            OSError: If the file cannot be read
+            ValueError: If a record is malformed or references an unknown station
+        """
+        ...
+
+    def add(self, observation: Observation) -> None: ...
+    def clear(self) -> None: ...
+    def to_b3_file(self, filename: str) -> None:
+        """
+        Args:
+            filename: Path of the B3 file to write
+
+        !!! note
+            Observations must have an observed satellite ID and a sensor ID.  Angles are written in TEME of date,
+            and space-based observations are written as type 9.
+
+        Raises:
+            OSError: If the file cannot be written
+            ValueError: If an observation cannot be represented as a B3 record
+        """
+        ...
+
+    def to_tdm_file(self, filename: str) -> None:
+        """
+        Args:
+            filename: Path of the KVN Tracking Data Message to write
+
+        !!! note
+            One segment is written per sensor, satellite and angle type, with epochs in UTC and right ascension
+            and declination in EME2000.
+
+        Raises:
+            OSError: If the file cannot be written
+            ValueError: If an observation is missing its observed satellite ID
+        """
+        ...
+
+class SiteCatalog:
+    count: int
+    name: str | None
+    observatories: list[Observatory]
+    """Observatories sorted by site ID, suitable for resolving sensors in `ObservationCatalog` readers"""
+
+    def __init__(self) -> None: ...
+    @classmethod
+    def from_json_file(cls, filename: str) -> SiteCatalog:
+        """
+        Args:
+            filename: Path to a JSON file of site and sensor definitions
+
+        Returns:
+            Catalog of observatories keyed by site ID
+
+        !!! note
+            The file holds a `sites` array.  Each site requires `site_id`, `name`, `latitude` and `longitude` in
+            **_degrees_** and `altitude` in **_kilometers_**, and may list `sensors`.  Each sensor requires
+            `name` and `angular_noise` and may set `sensor_id`, `range_noise`, `range_rate_noise`,
+            `angular_rate_noise`, `min_elevation`, `max_range` and a `biases` object keyed by `right_ascension`,
+            `declination`, `range`, `range_rate`, `azimuth` and `elevation`.
+
+        Raises:
+            OSError: If the file cannot be read
+            ValueError: If the file is not valid JSON or a definition is incomplete
+        """
+        ...
+
+    def add(self, observatory: Observatory) -> None:
+        """
+        Raises:
+            ValueError: If the observatory has no site ID
+        """
+        ...
+
+    def get(self, site_id: int) -> Observatory | None: ...
+    def get_sensor(self, sensor_id: int) -> Sensor | None: ...
+    def get_observatory_for_sensor(self, sensor_id: int) -> Observatory | None: ...
+    def remove(self, site_id: int) -> None: ...
+    def keys(self) -> list[int]: ...
+    def clear(self) -> None: ...
+    def __getitem__(self, site_id: int) -> Observatory: ...
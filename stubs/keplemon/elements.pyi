@@ -1,7 +1,7 @@
 # flake8: noqa
 from __future__ import annotations
-from keplemon.time import Epoch
-from keplemon.enums import Classification, KeplerianType, ReferenceFrame
+from keplemon.time import Epoch, EpochRange
+from keplemon.enums import Classification, KeplerianType, ReferenceFrame, EarthRotationModel
 from keplemon.propagation import ForceProperties
 from keplemon.events import CloseApproach
 
@@ -201,14 +201,20 @@ class CartesianState:
         """Convert the Cartesian state to osculating Keplerian elements"""
         ...
 
-    def to_frame(self, frame: ReferenceFrame) -> CartesianState:
+    def to_frame(self, frame: ReferenceFrame, rotation: EarthRotationModel | None = None) -> CartesianState:
         """
 
         Args:
             frame: reference frame of the output state
+            rotation: Greenwich angle applied between TEME and EFG when crossing between inertial and Earth-fixed
+                frames, or None for the SAAL default
 
         Returns:
-            CartesianState: Cartesian state in the new frame"""
+            CartesianState: Cartesian state in the new frame
+
+        Raises:
+            ValueError: If the rotation is not FK4, FK5 or GMST82, since the apparent sidereal time, GMST06 and the
+                Earth rotation angle are measured from equinoxes other than the TEME one"""
         ...
 
 class KeplerianState:
@@ -280,6 +286,19 @@ class Ephemeris:
         other: Ephemeris,
         distance_threshold: float,
     ) -> CloseApproach: ...
+    def get_close_approach_over(
+        self,
+        other: Ephemeris,
+        epochs: EpochRange,
+        distance_threshold: float,
+    ) -> CloseApproach | None:
+        """
+        Args:
+            other: Ephemeris to compare against
+            epochs: UTC epochs searched, where the step sets the search interval
+            distance_threshold: Distance threshold for close approach screening in **_kilometers_**
+        """
+        ...
 
 class TopocentricElements:
     """
@@ -312,3 +331,26 @@ class TopocentricElements:
             dec: J2000 declination in **_degrees_**
         """
         ...
+
+class HorizonElements:
+    """
+    Args:
+        azimuth: Azimuth measured clockwise from north in **_degrees_**
+        elevation: Elevation above the local horizon in **_degrees_**
+    """
+
+    azimuth: float
+    elevation: float
+    range: float | None
+    """Range in **_kilometers_**"""
+
+    range_rate: float | None
+    """Range rate in **_kilometers per second_**"""
+
+    azimuth_rate: float | None
+    """Azimuth rate in **_degrees per second_**"""
+
+    elevation_rate: float | None
+    """Elevation rate in **_degrees per second_**"""
+
+    def __init__(self, azimuth: float, elevation: float) -> None: ...
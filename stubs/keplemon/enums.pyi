@@ -34,12 +34,18 @@ class TimeSystem(Enum):
         TAI (TimeSystem): International Atomic Time
         TT (TimeSystem): Terrestrial Time
         UT1 (TimeSystem): Universal Time
+        GPS (TimeSystem): GPS Time
+        TDB (TimeSystem): Barycentric Dynamical Time
+        TCG (TimeSystem): Geocentric Coordinate Time
     """
 
     UTC = ...
     TAI = ...
     TT = ...
     UT1 = ...
+    GPS = ...
+    TDB = ...
+    TCG = ...
 
 class Classification(Enum):
     """
@@ -86,3 +92,73 @@ class ReferenceFrame(Enum):
     J2000 = ...
     EFG = ...
     ECR = ...
+
+class SolverType(Enum):
+    """
+    Method used to solve for the state correction in a batch least squares iteration
+
+    Attributes:
+        GaussNewton (SolverType): LU solution of the normal equations
+        LevenbergMarquardt (SolverType): Damped normal equations with adaptive damping
+        QR (SolverType): QR factorization of the weighted Jacobian
+        SVD (SolverType): Pseudo-inverse of the weighted Jacobian with small singular values removed
+    """
+
+    GaussNewton = ...
+    LevenbergMarquardt = ...
+    QR = ...
+    SVD = ...
+
+class MeasurementType(Enum):
+    """
+    Component of an observation used as a measurement in orbit determination
+
+    Attributes:
+        RightAscension (MeasurementType): Topocentric right ascension in **_degrees_**
+        Declination (MeasurementType): Topocentric declination in **_degrees_**
+        Range (MeasurementType): Range in **_kilometers_**
+        RangeRate (MeasurementType): Range rate in **_kilometers per second_**
+        RightAscensionRate (MeasurementType): Right ascension rate in **_degrees per second_**
+        DeclinationRate (MeasurementType): Declination rate in **_degrees per second_**
+        Azimuth (MeasurementType): Azimuth in **_degrees_**
+        Elevation (MeasurementType): Elevation in **_degrees_**
+        AzimuthRate (MeasurementType): Azimuth rate in **_degrees per second_**
+        ElevationRate (MeasurementType): Elevation rate in **_degrees per second_**
+    """
+
+    RightAscension = ...
+    Declination = ...
+    Range = ...
+    RangeRate = ...
+    RightAscensionRate = ...
+    DeclinationRate = ...
+    Azimuth = ...
+    Elevation = ...
+    AzimuthRate = ...
+    ElevationRate = ...
+
+class EarthRotationModel(Enum):
+    """
+    Model of the Greenwich angle used to rotate between inertial and Earth-fixed frames
+
+    Attributes:
+        FK4 (EarthRotationModel): SAAL Greenwich angle using FK4 theory
+        FK5 (EarthRotationModel): SAAL Greenwich angle using FK5 theory
+        GMST82 (EarthRotationModel): IAU-82 Greenwich mean sidereal time
+        GAST82 (EarthRotationModel): IAU-82 mean sidereal time plus the IAU 1994 equation of the equinoxes
+        GMST06 (EarthRotationModel): IAU 2006 Greenwich mean sidereal time
+        GAST06 (EarthRotationModel): IAU 2006 mean sidereal time plus the IAU 2000A equation of the equinoxes
+        ERA (EarthRotationModel): IAU 2000 Earth Rotation Angle
+
+    !!! note
+        Only FK4, FK5 and GMST82 rotate TEME to EFG.  The other models are measured from the true equinox, the
+        IAU 2006 mean equinox or the CIO and are available as angles through `Epoch.to_greenwich_angle`.
+    """
+
+    FK4 = ...
+    FK5 = ...
+    GMST82 = ...
+    GAST82 = ...
+    GMST06 = ...
+    GAST06 = ...
+    ERA = ...
@@ -1,13 +1,40 @@
 # flake8: noqa
-from keplemon.elements import TopocentricElements, CartesianVector
-from keplemon.time import Epoch
-from keplemon.bodies import Satellite, Sensor
-from keplemon.enums import KeplerianType
+from keplemon.elements import TopocentricElements, HorizonElements, CartesianVector, CartesianState, Ephemeris, TLE
+from keplemon.time import Epoch, TimeSpan
+from keplemon.bodies import Satellite, Sensor, Observatory
+from keplemon.enums import KeplerianType, CovarianceType, SolverType, MeasurementType
+from keplemon.events import ManeuverEvent
 
 class Covariance:
+    """
+    Args:
+        covariance_type: Coordinate system of the covariance elements
+    """
+
     sigmas: list[float]
     """"""
 
+    covariance_type: CovarianceType
+    """"""
+
+    def __init__(self, covariance_type: CovarianceType) -> None: ...
+    def get_element(self, i: int, j: int) -> float:
+        """
+        Args:
+            i: Row index of the element
+            j: Column index of the element
+        """
+        ...
+
+    def set_element(self, i: int, j: int, value: float) -> None:
+        """
+        Args:
+            i: Row index of the element
+            j: Column index of the element
+            value: Value to assign to the element
+        """
+        ...
+
 class Observation:
     """
     Args:
@@ -23,6 +50,55 @@ class Observation:
     epoch: Epoch
     """Time the measurement was observed"""
 
+    observer_teme_position: CartesianVector
+    """Position of the observer in TEME coordinates in **_kilometers_**"""
+
+    observer_teme_velocity: CartesianVector | None
+    """Velocity of a space-based observer in TEME coordinates in **_kilometers per second_**
+
+    !!! note
+        This is None for ground-based observations.
+    """
+
+    space_based: bool
+    """Indicates the observer is a satellite rather than a ground site
+
+    !!! note
+        Space-based observations read from B3 type 9 records have no observer velocity, so the observer is
+        treated as momentarily at rest.
+    """
+
+    light_time_correction: bool
+    """Flag to evaluate the satellite at the time the signal left it instead of the observation epoch
+
+    !!! note
+        Defaults to False.
+    """
+
+    aberration_correction: bool
+    """Flag to apply annual and observer-motion aberration to predicted right ascension and declination
+
+    !!! note
+        Ground observers use diurnal aberration while space-based observers use their orbital velocity.
+        Defaults to False.
+    """
+
+    refraction_correction: bool
+    """Flag to apply standard tropospheric refraction to predicted elevation, right ascension and declination
+
+    !!! note
+        Right ascension and declination are refracted by lifting the line of sight toward the local zenith.
+        Refraction is ignored for space-based observers and below -1 degree of elevation.
+        Defaults to False.
+    """
+
+    measurement_types: list[MeasurementType]
+    """Measurements used in orbit determination
+
+    !!! note
+        Optional components such as range on an angles observation are only used when the sensor defines a noise for them.
+    """
+
     range: float | None
     """Observed range from the sensor to the satellite in **_kilometers_**"""
 
@@ -30,16 +106,55 @@ class Observation:
     """Observed range rate from the sensor to the satellite in **_kilometers per second_**"""
 
     right_ascension: float
-    """Observed TEME right ascension in **_degrees_**"""
+    """Observed TEME right ascension in **_degrees_**
+
+    !!! note
+        Azimuth/elevation observations are converted to right ascension and declination for convenience.
+
+    Raises:
+        ValueError: If read from a range or range-rate observation, or set on an observation that is not topocentric
+    """
 
     declination: float
-    """Observed TEME declination in **_degrees_**"""
+    """Observed TEME declination in **_degrees_**
+
+    Raises:
+        ValueError: If read from a range or range-rate observation, or set on an observation that is not topocentric
+    """
 
     right_ascension_rate: float | None
-    """Observed right ascension rate in **_degrees per second_**"""
+    """Observed right ascension rate in **_degrees per second_**
+
+    Raises:
+        ValueError: If set on an observation that is not topocentric
+    """
 
     declination_rate: float | None
-    """Observed declination rate in **_degrees per second_**"""
+    """Observed declination rate in **_degrees per second_**
+
+    Raises:
+        ValueError: If set on an observation that is not topocentric
+    """
+
+    azimuth: float | None
+    """Observed azimuth in **_degrees_**"""
+
+    elevation: float | None
+    """Observed elevation in **_degrees_**"""
+
+    azimuth_rate: float | None
+    """Observed azimuth rate in **_degrees per second_**
+
+    Raises:
+        ValueError: If set on an observation that is not azimuth/elevation
+    """
+
+    elevation_rate: float | None
+    """Observed elevation rate in **_degrees per second_**
+
+    Raises:
+        ValueError: If set on an observation that is not azimuth/elevation
+    """
 
     observed_satellite_id: int | None
     """Tagged satellite ID of the observation"""
@@ -51,6 +166,89 @@ class Observation:
         observed_teme_topo: TopocentricElements,
         observer_teme_pos: CartesianVector,
     ) -> None: ...
+    @staticmethod
+    def from_horizon(
+        sensor: Sensor,
+        epoch: Epoch,
+        observed_horizon: HorizonElements,
+        observer_teme_pos: CartesianVector,
+    ) -> Observation:
+        """
+        Args:
+            sensor: Sensor that made the observation
+            epoch: Time of the observation
+            observed_horizon: Azimuth and elevation of the satellite with optional range and rates
+            observer_teme_pos: Position of the observer in TEME coordinates
+
+        Returns:
+            Azimuth/elevation observation
+        """
+        ...
+
+    @staticmethod
+    def from_range(sensor: Sensor, epoch: Epoch, range: float, observer_teme_pos: CartesianVector) -> Observation:
+        """
+        Args:
+            sensor: Sensor that made the observation
+            epoch: Time of the observation
+            range: Observed range in **_kilometers_**
+            observer_teme_pos: Position of the observer in TEME coordinates
+
+        Returns:
+            Range-only observation
+
+        !!! note
+            The sensor must define a range noise for the observation to be used in orbit determination.
+        """
+        ...
+
+    @staticmethod
+    def from_range_rate(
+        sensor: Sensor,
+        epoch: Epoch,
+        range_rate: float,
+        observer_teme_pos: CartesianVector,
+    ) -> Observation:
+        """
+        Args:
+            sensor: Sensor that made the observation
+            epoch: Time of the observation
+            range_rate: Observed range rate in **_kilometers per second_**
+            observer_teme_pos: Position of the observer in TEME coordinates
+
+        Returns:
+            Range-rate-only (Doppler) observation
+
+        !!! note
+            The sensor must define a range rate noise for the observation to be used in orbit determination.
+        """
+        ...
+
+    @staticmethod
+    def from_space_observer(
+        sensor: Sensor,
+        epoch: Epoch,
+        observed_teme_topo: TopocentricElements,
+        observer: Satellite,
+    ) -> Observation:
+        """
+        Args:
+            sensor: Sensor that made the observation
+            epoch: Time of the observation
+            observed_teme_topo: Right ascension and declination of the satellite relative to the observer
+            observer: Satellite hosting the sensor
+
+        Returns:
+            Observation taken from the observer state propagated to the observation epoch
+
+        Raises:
+            RuntimeError: If the observer cannot be propagated to the observation epoch
+
+        !!! note
+            Azimuth and elevation are undefined for a space-based observer.
+        """
+        ...
+
     def get_residual(self, sat: Satellite) -> ObservationResidual | None:
         """
         Calculate the residual of the observation with respect to a given satellite state.
@@ -100,6 +298,345 @@ class ObservationResidual:
     height: float
     """Height difference between the observed and expected state in **_kilometers_**"""
 
+    accepted: bool
+    """Indicates if the observation was used in the solution or rejected by residual editing"""
+
+class SensorBias:
+    sensor_id: int | None
+    """Catalog ID of the sensor the bias applies to"""
+
+    sensor_name: str
+    """Name of the sensor the bias applies to"""
+
+    measurement_type: MeasurementType
+    """Measurement component the bias applies to"""
+
+    value: float
+    """Estimated bias in the units of the measurement"""
+
+    sigma: float | None
+    """Formal uncertainty of the bias from the last iteration"""
+
+class CorrelationCandidate:
+    satellite_id: int
+    """Catalog ID of the candidate satellite"""
+
+    chi_square: float
+    """Sum of squared residuals normalized by the sensor noise"""
+
+    degrees_of_freedom: int
+    """Number of measurements contributing to the chi-square statistic"""
+
+    p_value: float
+    """Probability of a chi-square at least this large if the candidate produced the observations"""
+
+    correlated: bool
+    """Indicates if the p-value meets the significance of the report"""
+
+class CorrelationReport:
+    """
+    Args:
+        significance: Minimum p-value for a candidate to be considered correlated
+    """
+
+    significance: float
+    """Minimum p-value for a candidate to be considered correlated"""
+
+    candidates: list[CorrelationCandidate]
+    """Candidates ranked from lowest to highest chi-square"""
+
+    best_candidate: CorrelationCandidate | None
+    """Lowest chi-square candidate that passes the significance test"""
+
+    uncorrelated: bool
+    """Indicates that no candidate passes the significance test"""
+
+    def __init__(self, significance: float) -> None: ...
+
+class Tracklet:
+    sensor: Sensor
+    """Sensor that made the grouped observations"""
+
+    epoch: Epoch
+    """Reference epoch of the fitted motion, taken from the middle observation"""
+
+    start: Epoch
+    """Epoch of the first observation in the tracklet"""
+
+    end: Epoch
+    """Epoch of the last observation in the tracklet"""
+
+    observations: list[Observation]
+    """Observations grouped into the tracklet in chronological order"""
+
+    observation: Observation
+    """Single observation at the reference epoch carrying the fitted angles and angular rates"""
+
+    right_ascension: float
+    """Fitted TEME right ascension at the reference epoch in **_degrees_**"""
+
+    declination: float
+    """Fitted TEME declination at the reference epoch in **_degrees_**"""
+
+    right_ascension_rate: float
+    """Fitted right ascension rate in **_degrees per second_**"""
+
+    declination_rate: float
+    """Fitted declination rate in **_degrees per second_**"""
+
+    right_ascension_acceleration: float | None
+    """Fitted right ascension acceleration in **_degrees per second squared_** when a quadratic fit is used"""
+
+    declination_acceleration: float | None
+    """Fitted declination acceleration in **_degrees per second squared_** when a quadratic fit is used"""
+
+    rms: float
+    """Root mean square of the on-sky fit residuals in **_degrees_**"""
+
+class TrackletBuilder:
+    max_time_gap: TimeSpan
+    """Largest time between consecutive observations of the same tracklet"""
+
+    max_angular_rate: float
+    """Largest on-sky rate between consecutive observations in **_degrees per second_**"""
+
+    rate_tolerance: float
+    """Largest change in on-sky rate for an observation to extend a tracklet in **_degrees per second_**"""
+
+    min_observations: int
+    """Fewest observations required to emit a tracklet
+
+    Raises:
+        ValueError: If set below two
+    """
+
+    use_quadratic_fit: bool
+    """Flag to fit angular accelerations when a tracklet has more than three observations"""
+
+    def __init__(self) -> None: ...
+    def build(self, obs: list[Observation]) -> list[Tracklet]:
+        """
+        Args:
+            obs: Observations from any number of sensors
+
+        Returns:
+            Tracklets ordered by sensor ID, sensor name and start epoch
+
+        !!! note
+            Only observations with right ascension and declination are grouped.  Observations from different
+            sensors, identified by sensor ID and name, are never combined.
+        """
+        ...
+
+class ObservationSimulator:
+    """
+    Args:
+        seed: Seed of the random number generator used to draw measurement noise
+    """
+
+    seed: int
+    """Seed of the random number generator
+
+    !!! note
+        Setting the seed restarts the random sequence.
+    """
+
+    min_elevation: float
+    """Lowest elevation at which a sensor can observe the satellite in **_degrees_**
+
+    !!! note
+        A sensor with its own `min_elevation` is limited by the higher of the two values.
+    """
+
+    max_range: float | None
+    """Largest range at which a sensor can observe the satellite in **_kilometers_**
+
+    !!! note
+        A sensor with its own `max_range` is limited by the lower of the two values.
+    """
+
+    add_noise: bool
+    """Flag to add Gaussian noise drawn from each sensor's noise to the simulated measurements"""
+
+    light_time_correction: bool
+    """Flag to simulate and tag observations with light-time correction"""
+
+    aberration_correction: bool
+    """Flag to simulate and tag observations with aberration correction"""
+
+    refraction_correction: bool
+    """Flag to simulate and tag observations with refraction correction"""
+
+    def __init__(self, seed: int) -> None: ...
+    def reset(self) -> None:
+        """Restart the random sequence from the current seed."""
+        ...
+
+    def generate(
+        self,
+        satellite: Satellite,
+        observatories: list[Observatory],
+        epochs: list[Epoch],
+    ) -> list[Observation]:
+        """
+        Args:
+            satellite: Truth satellite to be observed
+            observatories: Sites whose sensors observe the satellite
+            epochs: Schedule of observation times
+
+        Returns:
+            Observations ordered by epoch, then by observatory and sensor
+
+        Raises:
+            RuntimeError: If the satellite cannot be propagated to a scheduled epoch
+
+        !!! note
+            Sensors report azimuth and elevation when `horizon_angles` is set and right ascension and declination
+            otherwise.  Range, range rate and angular rates are included when the sensor defines a noise for
+            them.  Sensor biases are added to the simulated measurements and the returned observations carry a
+            copy of the sensor with zero biases.
+        """
+        ...
+
+class ManeuverDetector:
+    residual_threshold: float
+    """Root mean square of the noise-normalized residuals above which an observation disagrees with the orbit"""
+
+    min_observations: int
+    """Number of consecutive disagreeing observations required to declare a maneuver
+
+    Raises:
+        ValueError: If set to zero
+    """
+
+    fit_span: TimeSpan
+    """Span of observations after a detection used to fit the post-maneuver orbit"""
+
+    delta_v_threshold: float
+    """Smallest change in velocity reported when comparing TLEs in **_kilometers per second_**"""
+
+    def __init__(self) -> None: ...
+    def detect_from_observations(self, prior: Satellite, obs: list[Observation]) -> list[ManeuverEvent]:
+        """
+        Args:
+            prior: Satellite state known before the observations
+            obs: Observations of the satellite
+
+        Returns:
+            Maneuvers in chronological order
+
+        Raises:
+            RuntimeError: If the satellite cannot be propagated or an observation has no sensor noise
+
+        !!! note
+            After each detection the post-maneuver orbit is fit to the observations within the fit span and
+            becomes the reference for the remaining observations.  The maneuver epoch is placed where the
+            prior and post-maneuver trajectories meet, between the last consistent observation and the
+            first disagreeing one.
+        """
+        ...
+
+    def detect_from_tles(self, tles: list[TLE]) -> list[ManeuverEvent]:
+        """
+        Args:
+            tles: Element sets of one or more satellites
+
+        Returns:
+            Maneuvers ordered by satellite ID and epoch
+
+        Raises:
+            RuntimeError: If an element set cannot be propagated between consecutive epochs
+
+        !!! note
+            Consecutive element sets of each satellite are compared where their trajectories meet.
+        """
+        ...
+
+class TLEFitter:
+    """
+    Args:
+        satellite_id: Satellite ID assigned to the fitted TLE
+        states: Reference states of the satellite in any supported frame
+
+    Raises:
+        ValueError: If fewer than 3 states are provided
+
+    !!! note
+        States are converted to TEME and the TLE is fit to their positions at the epoch of the last state.
+    """
+
+    satellite_id: int
+    """"""
+
+    states: list[CartesianState]
+    """Reference states in TEME ordered by epoch"""
+
+    output_type: KeplerianType
+    """Type of element set to fit
+
+    Raises:
+        ValueError: If set to Osculating
+    """
+
+    estimate_drag: bool
+    """Flag to indicate if B* (GP) or the ballistic coefficient (XP) should be estimated"""
+
+    estimate_srp: bool
+    """Flag to indicate if AGOM should be estimated
+
+    !!! warning
+        Only supported for MeanBrouwerXP output types
+    """
+
+    max_iterations: int
+    """Maximum number of differential correction iterations"""
+
+    rms_tolerance: float
+    """Relative change in RMS between iterations below which the fit is converged"""
+
+    current_estimate: TLE
+    """Fitted TLE, or the first guess if the fit has not been solved"""
+
+    iteration_count: int
+    """Number of iterations performed to reach the solution"""
+
+    converged: bool
+    """Indicates if the solution meets the tolerance criteria"""
+
+    rms: float | None
+    """Root mean square of the position residuals in **_kilometers_**"""
+
+    residuals: list[tuple[Epoch, CartesianVector, CartesianVector]]
+    """Position and velocity of each reference state relative to the current estimate in RIC
+
+    !!! note
+        Positions are in **_kilometers_** and velocities in **_kilometers per second_**.
+    """
+
+    def __init__(self, satellite_id: int, states: list[CartesianState]) -> None: ...
+    @staticmethod
+    def from_ephemeris(ephemeris: Ephemeris, start: Epoch, end: Epoch, step: TimeSpan) -> TLEFitter:
+        """
+        Args:
+            ephemeris: Ephemeris to sample
+            start: Epoch of the first sampled state
+            end: Latest epoch to sample
+            step: Time between sampled states
+
+        Raises:
+            ValueError: If the step is not positive or the ephemeris does not cover a sampled epoch
+        """
+        ...
+
+    def solve(self) -> None:
+        """
+        Raises:
+            ValueError: If AGOM is estimated for an output type other than MeanBrouwerXP
+            RuntimeError: If the element set cannot be propagated, the normal equations are singular or no step
+                reduces the residuals
+        """
+        ...
+
 class BatchLeastSquares:
     """
     Args:
@@ -154,13 +691,110 @@ class BatchLeastSquares:
     output_type: KeplerianType
     """Type of Keplerian elements to be used in the output state"""
 
+    a_priori_covariance: Covariance | None
+    """Equinoctial covariance of the a priori state added as information to the normal equations
+
+    !!! note
+        Elements are ordered (Af, Ag, chi, psi, L, N) with L in **_degrees_** and N in **_revolutions per day_**.
+        The covariance is mapped from the a priori epoch to the solution epoch before it is applied.
+    """
+
+    consider_drag_sigma: float | None
+    """Uncertainty of the drag coefficient treated as a consider parameter when drag is not estimated
+
+    Only the drag and SRP coefficients can be considered; sensor biases are either estimated through
+    `estimate_biases` or ignored.
+    """
+
+    consider_srp_sigma: float | None
+    """Uncertainty of the SRP coefficient treated as a consider parameter when SRP is not estimated
+
+    Sensor biases cannot be considered (see `consider_drag_sigma`).
+    """
+
+    formal_covariance: Covariance | None
+    """Equinoctial covariance of the orbit elements from the last iteration, inflated by any consider parameters"""
+
+    edit_sigma: float | None
+    """Multiple of the weighted RMS beyond which an observation is rejected from the solution
+
+    !!! note
+        Editing is disabled when set to None.  Rejected observations are re-evaluated every iteration and
+        may be accepted again as the estimate improves.
+    """
+
+    first_pass_edit_multiplier: float
+    """Factor applied to the edit threshold on the first iteration, when the a priori state may be poor"""
+
+    rejection_history: list[list[int]]
+    """Indices of the observations rejected at each iteration"""
+
+    solver_type: SolverType
+    """Method used to solve for the state correction at each iteration"""
+
+    rms_tolerance: float
+    """Relative change in weighted RMS between iterations below which the solution is converged"""
+
+    correction_tolerance: float | None
+    """Multiple of the formal sigma below which every state correction must fall for the solution to converge
+
+    !!! note
+        The solution converges when either the RMS or the correction criterion is met.  With the
+        Levenberg-Marquardt solver both criteria are applied to the undamped step, so a heavily damped step
+        is not mistaken for convergence.
+    """
+
+    max_divergent_iterations: int
+    """Number of consecutive increases in weighted RMS before the solution is considered divergent"""
+
+    initial_damping: float
+    """Starting damping factor for the Levenberg-Marquardt solver"""
+
+    estimate_biases: bool
+    """Flag to indicate if per-sensor measurement biases should be estimated
+
+    !!! note
+        Angle, range and range rate biases are estimated for each sensor, identified by sensor ID and name,
+        that provides the measurement.  Estimated biases are written back onto the sensors of the
+        observations.
+    """
+
+    sensor_biases: list[SensorBias]
+    """Current bias estimates and their uncertainties when estimating biases"""
+
+    maneuver: ManeuverEvent | None
+    """Impulsive maneuver estimated along with the orbit
+
+    !!! note
+        Setting a maneuver adds its RIC delta-v to the solution so a single orbit can be fit across the
+        maneuver.  Any delta-v on the assigned event is used as the initial guess.  The current estimate
+        describes the orbit after the maneuver and the a priori state describes the orbit before it.
+    """
+
+    estimate_maneuver_epoch: bool
+    """Flag to indicate if the maneuver epoch should be estimated along with the delta-v
+
+    !!! note
+        The epoch is held fixed until the delta-v estimate is nonzero.
+    """
+
+    maneuver_sigmas: list[float] | None
+    """Formal uncertainties of the radial, in-track and cross-track delta-v in **_kilometers per second_**,
+    followed by the maneuver epoch in **_seconds_** when it is estimated"""
+
     def __init__(
         self,
         obs: list[Observation],
         a_priori: Satellite,
     ) -> None: ...
     def solve(self) -> None:
-        """Iterate until the solution converges or the maximum number of iterations is reached."""
+        """Iterate until the solution converges or the maximum number of iterations is reached.
+
+        Raises:
+            RuntimeError: If the solution diverges, the Levenberg-Marquardt solver stalls or the normal
+                equations cannot be solved
+            ValueError: If the maneuver epoch does not fall within the observations
+        """
         ...
 
     def iterate(self) -> None:
@@ -1,5 +1,6 @@
 # flake8: noqa
 from keplemon.time import Epoch
+from keplemon.elements import CartesianVector
 
 class CloseApproach:
     epoch: Epoch
@@ -27,3 +28,31 @@ class CloseApproachReport:
 
     distance_threshold: float
     def __init__(self, start: Epoch, end: Epoch, distance_threshold: float) -> None: ...
+
+class ManeuverEvent:
+    satellite_id: int
+    """Satellite ID of the maneuvering body"""
+
+    epoch: Epoch
+    """Estimated UTC epoch of the maneuver"""
+
+    delta_v: CartesianVector | None
+    """Estimated change in velocity in RIC coordinates in **_kilometers per second_**
+
+    !!! note
+        This is None when the maneuver was detected but a post-maneuver orbit could not be determined.
+    """
+
+    delta_v_magnitude: float | None
+    """Magnitude of the estimated change in velocity in **_kilometers per second_**"""
+    def __init__(self, satellite_id: int, epoch: Epoch, delta_v: CartesianVector | None = None) -> None:
+        """
+        Args:
+            satellite_id: Satellite ID of the maneuvering body
+            epoch: Epoch of the maneuver
+            delta_v: Change in velocity in RIC coordinates in **_kilometers per second_**
+
+        !!! note
+            Assign the event to `BatchLeastSquares.maneuver` to estimate a delta-v at a chosen epoch.
+        """
+        ...
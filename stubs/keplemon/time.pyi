@@ -1,8 +1,8 @@
 # flake8: noqa
 from __future__ import annotations
 from pathlib import Path
-from keplemon.enums import TimeSystem
-from typing import overload, Any
+from keplemon.enums import TimeSystem, EarthRotationModel
+from typing import overload, Any, Iterator
 
 def request_time_constants_update(output_path: str | Path) -> None:
     """
@@ -13,6 +13,48 @@ def request_time_constants_update(output_path: str | Path) -> None:
     """
     ...
 
+def get_leap_seconds() -> list[tuple[Epoch, float]]:
+    """
+    Returns:
+        UTC epochs from 1972 onward at which TAI - UTC changed, paired with the new offset in **_seconds_**
+    """
+    ...
+
+def load_finals_file(file_path: str | Path) -> None:
+    """
+    Replace the loaded time constants with UT1 - UTC, polar motion and length of day from an IERS finals file.
+
+    Args:
+        file_path: Path to a `finals2000A.all`, `finals2000A.data` or `finals.data` file
+
+    !!! note
+        Bulletin A values are used and rows are read until the first row without UT1 - UTC.  TAI - UTC is taken from
+        the built-in leap-second table.
+
+    Raises:
+        IOError: If the file cannot be read
+        ValueError: If a row cannot be parsed
+        RuntimeError: If the time constants cannot be loaded
+    """
+    ...
+
+def load_celestrak_eop_file(file_path: str | Path) -> None:
+    """
+    Replace the loaded time constants with UT1 - UTC, polar motion and length of day from a CelesTrak EOP file.
+
+    Args:
+        file_path: Path to an `EOP-All.csv` or `EOP-Last5Years.csv` file
+
+    Raises:
+        IOError: If the file cannot be read
+        ValueError: If the header or a row cannot be parsed
+        RuntimeError: If the time constants cannot be loaded
+    """
+    ...
+
+class IsoParseError(ValueError):
+    """Raised when an ISO 8601 timestamp is malformed or out of range"""
+
 class TimeSpan:
     @classmethod
     def from_days(cls, days: float) -> TimeSpan:
@@ -118,10 +160,16 @@ class TimeComponents:
             second: Second (0.0-59.999...)
         """
 
-    def to_iso(self) -> str:
+    def to_iso(self, precision: int = 3) -> str:
         """
+        Args:
+            precision: Number of decimals in the seconds, from 0 to 9
+
         Returns:
-            Epoch in ISO 8601 format (YYYY-MM-DDThh:mm:ss.sss)
+            Components in ISO 8601 format (YYYY-MM-DDThh:mm:ss.sss)
+
+        Raises:
+            ValueError: If `precision` exceeds 9
         """
         ...
 
@@ -129,22 +177,64 @@ class TimeComponents:
     def from_iso(cls, iso_str: str) -> TimeComponents:
         """
         Args:
-            iso_str: ISO 8601 formatted string (YYYY-MM-DDThh:mm:ss.sss)
+            iso_str: ISO 8601 or RFC 3339 timestamp
+
+        !!! note
+            Calendar (YYYY-MM-DD), ordinal (YYYY-DDD) and week (YYYY-Www-D) dates are accepted in extended or basic
+            form, with an optional time separated by `T` or a space and an optional `Z` or `±hh:mm` offset.  Offsets
+            are removed, so the components describe the same instant at zero offset.
+
+        Raises:
+            IsoParseError: If the timestamp is malformed or a field is out of range
         """
         ...
 
 class Epoch:
+    """
+    !!! note
+        Epochs keep a second floating-point term below `days_since_1950`, so differences, sums and components
+        are resolved to the nanosecond even though `days_since_1950` alone resolves only fractions of a microsecond.
+
+    !!! note
+        Comparisons, equality and hashing convert both epochs to TAI, so epochs in different time systems are
+        ordered by the instant they describe.
+    """
+
     days_since_1950: float
     """
     Core floating epoch representation used by the SAAL binaries.
     """
 
+    seconds_of_day: float
+    """Seconds elapsed since the start of the day at nanosecond precision"""
+
     time_system: TimeSystem
     """"""
 
     day_of_year: float
     """Decimal day of the year (1-365.999...)"""
 
+    tai_minus_utc: float
+    """TAI - UTC in **_seconds_** from the built-in leap-second table"""
+
+    julian_date: float
+    """Julian Date in the epoch's time system"""
+
+    split_julian_date: tuple[float, float]
+    """Julian Date as a whole-day part ending in .5 and a day fraction, preserving full precision"""
+
+    modified_julian_date: float
+    """Modified Julian Date in the epoch's time system"""
+
+    gps_week: int
+    """Weeks since the GPS epoch of 6 Jan 1980"""
+
+    gps_seconds: float
+    """GPS time into the current GPS week in **_seconds_**"""
+
+    unix_time: float
+    """**_Seconds_** since 1 Jan 1970 UTC, excluding leap seconds"""
+
     @classmethod
     def from_days_since_1950(cls, days: float, time_system: TimeSystem) -> Epoch:
         """
@@ -154,12 +244,93 @@ class Epoch:
         """
         ...
 
+    @classmethod
+    def from_days_and_seconds(cls, days: float, seconds: float, time_system: TimeSystem) -> Epoch:
+        """
+        Args:
+            days: Days since 1 Jan 1956
+            seconds: Seconds added to `days` without loss of precision
+            time_system: System used to calculate the number of days since 1 Jan 1956
+        """
+        ...
+
+    @classmethod
+    def from_jd(cls, jd: float, time_system: TimeSystem) -> Epoch:
+        """
+        Args:
+            jd: Julian Date
+            time_system: System used to calculate the Julian Date
+        """
+        ...
+
+    @classmethod
+    def from_split_jd(cls, jd1: float, jd2: float, time_system: TimeSystem) -> Epoch:
+        """
+        Args:
+            jd1: Leading part of the Julian Date, typically a whole day ending in .5
+            jd2: Remaining part of the Julian Date
+            time_system: System used to calculate the Julian Date
+        """
+        ...
+
+    @classmethod
+    def from_mjd(cls, mjd: float, time_system: TimeSystem) -> Epoch:
+        """
+        Args:
+            mjd: Modified Julian Date
+            time_system: System used to calculate the Modified Julian Date
+        """
+        ...
+
+    @classmethod
+    def from_gps_week(cls, week: int, seconds: float) -> Epoch:
+        """
+        Args:
+            week: Weeks since the GPS epoch of 6 Jan 1980, without rollover
+            seconds: Time into the week in **_seconds_**
+
+        Returns:
+            Epoch in the GPS time system
+        """
+        ...
+
+    @classmethod
+    def from_unix(cls, seconds: float) -> Epoch:
+        """
+        Args:
+            seconds: **_Seconds_** since 1 Jan 1970 UTC, excluding leap seconds
+
+        Returns:
+            Epoch in the UTC time system
+        """
+        ...
+
+    @classmethod
+    def from_tle_epoch(cls, tle_epoch: str) -> Epoch:
+        """
+        Args:
+            tle_epoch: Two-digit year and decimal day of year (YYDDD.DDDDDDDD), where years 57-99 are 1957-1999
+
+        Returns:
+            Epoch in the UTC time system
+
+        Raises:
+            ValueError: If the string is not a valid TLE epoch
+        """
+        ...
+
     @classmethod
     def from_iso(cls, iso_str: str, time_system: TimeSystem) -> Epoch:
         """
         Args:
-            iso_str: ISO 8601 formatted string (YYYY-MM-DDThh:mm:ss.sss)
+            iso_str: ISO 8601 or RFC 3339 timestamp in any layout accepted by `TimeComponents.from_iso`
             time_system: System used to calculate the epoch
+
+        !!! note
+            A UTC leap second such as `2016-12-31T23:59:60` is returned in TAI, as in `from_components`.
+
+        Raises:
+            IsoParseError: If the timestamp is malformed or is not a valid time in `time_system`
         """
         ...
 
@@ -169,6 +340,13 @@ class Epoch:
         Args:
             components: Epoch represented as individual components
             time_system: System used to calculate the epoch
+
+        !!! note
+            A UTC second of 60 is accepted only during an inserted leap second.  UTC days since 1950 skip over
+            leap seconds, so these epochs are returned in TAI.
+
+        Raises:
+            ValueError: If the components are not a valid time in `time_system`
         """
         ...
 
@@ -191,10 +369,16 @@ class Epoch:
         """
         ...
 
-    def to_iso(self) -> str:
+    def to_iso(self, precision: int = 3) -> str:
         """
+        Args:
+            precision: Number of decimals in the seconds, from 0 to 9
+
         Returns:
             Epoch in ISO 8601 format (YYYY-MM-DDThh:mm:ss.sss)
+
+        Raises:
+            ValueError: If `precision` exceeds 9
         """
         ...
 
@@ -226,6 +410,13 @@ class Epoch:
         """
         ...
 
+    def to_tle_epoch(self) -> str:
+        """
+        Returns:
+            UTC epoch as a two-digit year and decimal day of year (YYDDD.DDDDDDDD)
+        """
+        ...
+
     def to_time_components(self) -> TimeComponents:
         """
         Returns:
@@ -233,6 +424,13 @@ class Epoch:
         """
         ...
 
+    def to_utc_components(self) -> TimeComponents:
+        """
+        Returns:
+            Epoch as UTC components, with a second of 60 during an inserted leap second
+        """
+        ...
+
     def to_fk4_greenwich_angle(self) -> float:
         """
         Returns:
@@ -247,12 +445,51 @@ class Epoch:
         """
         ...
 
+    def to_greenwich_angle(self, model: EarthRotationModel) -> float:
+        """
+        Args:
+            model: Sidereal time or rotation angle to evaluate
+
+        Returns:
+            Greenwich angle in **_radians_** on [0, 2π)
+
+        !!! note
+            The apparent models use nutation series truncated to their leading terms, good to a few
+            milliarcseconds.
+        """
+        ...
+
+    def to_earth_rotation_angle(self) -> float:
+        """
+        Returns:
+            IAU 2000 Earth Rotation Angle in **_radians_** on [0, 2π)
+        """
+        ...
+
+    def to_equation_of_equinoxes(self, model: EarthRotationModel) -> float:
+        """
+        Args:
+            model: IAU-82 models use the IAU 1994 equation and IAU 2006 models use the IAU 2000 equation
+
+        Returns:
+            Equation of the equinoxes in **_radians_**
+
+        Raises:
+            ValueError: If the model is FK4, FK5 or ERA
+        """
+        ...
+
     def to_system(self, time_system: TimeSystem) -> Epoch:
         """
         Convert the epoch to a different time system.
 
         Args:
             time_system: Desired ouput system
+
+        !!! note
+            Conversions pass through TAI.  TT and GPS are fixed offsets from TAI, TCG follows the IAU 2000
+            definition, and TDB uses the leading periodic terms, which are accurate to tens of microseconds.
+            UTC uses the built-in leap-second table and UT1 depends on the loaded time constants.
         """
         ...
 
@@ -267,3 +504,73 @@ class Epoch:
     def __ne__(self, other: object) -> bool: ...
     def __ge__(self, other: Epoch) -> bool: ...
     def __le__(self, other: Epoch) -> bool: ...
+
+class EpochRange:
+    """
+    Evenly spaced epochs from `start` that include `end` when it falls on the grid.
+
+    Args:
+        start: First epoch of the range
+        end: Latest epoch that may be included
+        step: Positive spacing between epochs
+
+    Raises:
+        ValueError: If `step` is not positive or `end` precedes `start`
+    """
+
+    start: Epoch
+    """First epoch of the range"""
+
+    end: Epoch | None
+    """Last epoch of the range or None if the range is empty"""
+
+    step: TimeSpan
+    """Spacing between epochs"""
+
+    count: int
+    """Number of epochs in the range"""
+
+    def __init__(self, start: Epoch, end: Epoch, step: TimeSpan) -> None: ...
+    @classmethod
+    def from_count(cls, start: Epoch, end: Epoch, count: int) -> EpochRange:
+        """
+        Args:
+            start: First epoch of the range
+            end: Last epoch of the range
+            count: Number of epochs, at least 2
+
+        Raises:
+            ValueError: If `count` is less than 2 or `end` does not follow `start`
+        """
+        ...
+
+    def merge(self, other: EpochRange) -> EpochRange:
+        """
+        Args:
+            other: Range with the same step on the same grid that overlaps or directly follows this range
+
+        Returns:
+            Single range covering the epochs of both ranges
+
+        Raises:
+            ValueError: If the ranges are not on a common grid or leave a gap
+        """
+        ...
+
+    def intersection(self, other: EpochRange) -> EpochRange | None:
+        """
+        Args:
+            other: Range whose span from `start` to `end` bounds the result
+
+        Returns:
+            Epochs of this range that fall within the span of `other` or None if there are none
+        """
+        ...
+
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[Epoch]: ...
+    def __contains__(self, epoch: Epoch) -> bool: ...
+    @overload
+    def __getitem__(self, index: int) -> Epoch: ...
+    @overload
+    def __getitem__(self, index: slice) -> EpochRange: ...
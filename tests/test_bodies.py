@@ -2,7 +2,7 @@ import pytest
 from keplemon.bodies import Satellite, Constellation, Earth
 from keplemon.catalogs import TLECatalog
 from keplemon.elements import TLE
-from keplemon.time import Epoch
+from keplemon.time import Epoch, TimeSpan
 from keplemon.enums import TimeSystem
 
 
@@ -56,6 +56,12 @@ def test_satellite():
 
     start = Epoch.from_iso("2025-04-15T12:00:00.000000Z", TimeSystem.UTC)
     end = Epoch.from_iso("2025-04-16T12:00:00.000000Z", TimeSystem.UTC)
+    single_state = sat_1.get_ephemeris(end, start, TimeSpan.from_minutes(10))
+    assert single_state is not None
+    assert single_state.get_state_at_epoch(end) is not None
+    with pytest.raises(ValueError):
+        sat_1.get_ephemeris(start, end, TimeSpan.from_seconds(0))
+
     ca = sat_1.get_close_approach(sat_2, start, end, 25.0)
     assert ca
     assert ca.epoch.to_iso() == "2025-04-15T12:32:28.531"
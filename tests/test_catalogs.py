@@ -1,7 +1,171 @@
-from keplemon.catalogs import TLECatalog
+import os
+import tempfile
+
+import pytest
+from keplemon.bodies import Observatory, Satellite, Sensor
+from keplemon.catalogs import ObservationCatalog, SiteCatalog, TLECatalog
+from keplemon.elements import TLE, HorizonElements, TopocentricElements
+from keplemon.enums import TimeSystem
+from keplemon.estimation import Observation
+from keplemon.time import Epoch
+
+LINE_1 = "1 25544U 98067A   20200.51605324 +.00000884  00000 0  22898-4 0 0999"
+LINE_2 = "2 25544  51.6443  93.0000 0001400  84.0000 276.1505 15.49507896236008"
+
+
+def get_observatory(sensor_name: str = "SENSOR") -> Observatory:
+    observatory = Observatory("SITE", 40.0, -105.0, 1.6)
+    sensor = Sensor(sensor_name, 0.001)
+    sensor.sensor_id = 211
+    observatory.add_sensor(sensor)
+    return observatory
+
+
+def write_and_read(catalog: ObservationCatalog, suffix: str, observatory: Observatory) -> ObservationCatalog:
+    with tempfile.TemporaryDirectory() as directory:
+        path = os.path.join(directory, "obs" + suffix)
+        if suffix == ".b3":
+            catalog.to_b3_file(path)
+            return ObservationCatalog.from_b3_file(path, [observatory])
+        catalog.to_tdm_file(path)
+        return ObservationCatalog.from_tdm_file(path, [observatory])
 
 
 def test_tle_catalog():
     test_tle_file = "tests/2025-04-15-celestrak.tle"
     catalog = TLECatalog.from_tle_file(test_tle_file)
     assert catalog.count == 11305
+
+
+def test_b3_round_trip():
+    observatory = get_observatory()
+    sensor = observatory.sensors[0]
+    epoch = Epoch.from_iso("2025-04-15T04:02:42.420", TimeSystem.UTC)
+    site_position = observatory.get_state_at_epoch(epoch).position
+
+    horizon = HorizonElements(123.4567, 45.6789)
+    horizon.range = 1234.5678
+    observer = Satellite.from_tle(TLE.from_lines(LINE_1, LINE_2))
+    obs = [
+        Observation(sensor, epoch, TopocentricElements(201.2345, -12.345), site_position),
+        Observation.from_horizon(sensor, epoch, horizon, site_position),
+        Observation.from_space_observer(sensor, epoch, TopocentricElements(10.5, 20.25), observer),
+    ]
+    catalog = ObservationCatalog()
+    for ob in obs:
+        ob.observed_satellite_id = 25544
+        catalog.add(ob)
+
+    read = write_and_read(catalog, ".b3", observatory).observations
+    assert len(read) == 3
+    for original, copy in zip(obs, read):
+        assert copy.epoch.days_since_1950 == pytest.approx(original.epoch.days_since_1950, abs=1e-9)
+        assert copy.observed_satellite_id == 25544
+        assert copy.space_based == original.space_based
+
+    assert read[0].right_ascension == pytest.approx(201.2345, abs=1e-3)
+    assert read[0].declination == pytest.approx(-12.345, abs=1e-3)
+    assert read[1].azimuth == pytest.approx(123.4567, abs=1e-4)
+    assert read[1].elevation == pytest.approx(45.6789, abs=1e-4)
+    assert read[1].range == pytest.approx(1234.5678, abs=1e-4)
+    assert read[2].right_ascension == pytest.approx(10.5, abs=1e-3)
+    assert read[2].declination == pytest.approx(20.25, abs=1e-3)
+    assert read[2].observer_teme_position.distance(obs[2].observer_teme_position) < 1e-3
+
+
+def test_tdm_round_trip():
+    # A numeric sensor name must not be mistaken for the observed satellite
+    observatory = get_observatory("4321")
+    sensor = observatory.sensors[0]
+    epoch = Epoch.from_iso("2025-04-15T04:02:42.420", TimeSystem.TAI)
+    site_position = observatory.get_state_at_epoch(epoch).position
+
+    topocentric = TopocentricElements(201.2345, -12.345)
+    topocentric.range = 1234.5678
+    ob = Observation(sensor, epoch, topocentric, site_position)
+    ob.observed_satellite_id = 25544
+    catalog = ObservationCatalog()
+    catalog.add(ob)
+
+    read = write_and_read(catalog, ".tdm", observatory).observations
+    assert len(read) == 1
+    assert read[0].observed_satellite_id == 25544
+    assert read[0].epoch.to_system(TimeSystem.TAI).days_since_1950 == pytest.approx(epoch.days_since_1950, abs=1e-9)
+    assert read[0].right_ascension == pytest.approx(201.2345, abs=1e-6)
+    assert read[0].declination == pytest.approx(-12.345, abs=1e-6)
+    assert read[0].range == pytest.approx(1234.5678, abs=1e-6)
+
+
+def test_crd_reader():
+    observatory = get_observatory()
+    observatory.site_id = 7105
+    records = [
+        "H1 CRD 2 2025 04 15 04",
+        "H2 SITE 7105 01 01 04 NASA",
+        "H3 SAT 9207002 5557 25544 1 1 0",
+        "H4 0 2025 04 15 04 00 00 2025 04 15 04 10 00 0 0 0 0 1 0 2 0",
+        "10 14400.000000000000 0.040000000000 std1 2 0 0 0 na",
+        "10 14460.000000000000 0.030000000000 std1 0 0 0 0 na",
+        "H8",
+        "H9",
+    ]
+    with tempfile.TemporaryDirectory() as directory:
+        path = os.path.join(directory, "obs.crd")
+        with open(path, "w") as f:
+            f.write("\n".join(records) + "\n")
+        read = ObservationCatalog.from_crd_file(path, [observatory]).observations
+
+    start = Epoch.from_iso("2025-04-15T04:00:00", TimeSystem.UTC).days_since_1950
+    assert len(read) == 2
+    assert read[0].observed_satellite_id == 25544
+    assert read[0].range == pytest.approx(0.5 * 299792.458 * 0.04, abs=1e-9)
+    assert read[0].epoch.days_since_1950 == pytest.approx(start + 0.02 / 86400.0, abs=1e-9)
+    assert read[1].range == pytest.approx(0.5 * 299792.458 * 0.03, abs=1e-9)
+    assert read[1].epoch.days_since_1950 == pytest.approx(start + (60.0 - 0.015) / 86400.0, abs=1e-9)
+
+
+SITE_CATALOG = """{
+    "sites": [
+        {
+            "site_id": 1,
+            "name": "SITE \\uD83D\\uDE00",
+            "latitude": 40.0,
+            "longitude": -105.0,
+            "altitude": 1.6,
+            "sensors": [
+                {
+                    "sensor_id": 211,
+                    "name": "SENSOR",
+                    "angular_noise": 0.001,
+                    "range_noise": null,
+                    "min_elevation": 10.0,
+                    "biases": {"declination": 0.02}
+                }
+            ]
+        }
+    ]
+}"""
+
+
+def read_site_catalog(contents: str) -> SiteCatalog:
+    with tempfile.TemporaryDirectory() as directory:
+        path = os.path.join(directory, "sites.json")
+        with open(path, "w") as f:
+            f.write(contents)
+        return SiteCatalog.from_json_file(path)
+
+
+def test_site_catalog():
+    catalog = read_site_catalog(SITE_CATALOG)
+    assert catalog.count == 1
+    assert catalog[1].name == "SITE \U0001F600"
+    sensor = catalog.get_sensor(211)
+    assert sensor.min_elevation == 10.0
+    assert sensor.range_noise is None
+    assert sensor.declination_bias == 0.02
+    assert catalog.get_observatory_for_sensor(211).site_id == 1
+
+    with pytest.raises(ValueError):
+        read_site_catalog(SITE_CATALOG.replace('"site_id": 1,', ""))
+    with pytest.raises(ValueError):
+        read_site_catalog(SITE_CATALOG[:-1])
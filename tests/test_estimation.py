@@ -0,0 +1,284 @@
+import pytest
+from keplemon.bodies import Satellite, Sensor, Observatory
+from keplemon.elements import TLE, CartesianVector, HorizonElements, TopocentricElements
+from keplemon.enums import MeasurementType, SolverType, TimeSystem
+from keplemon.events import ManeuverEvent
+from keplemon.estimation import BatchLeastSquares, Observation, ObservationSimulator, TLEFitter, TrackletBuilder
+from keplemon.time import Epoch, TimeSpan
+
+LINE_1 = "1 25544U 98067A   20200.51605324 +.00000884  00000 0  22898-4 0 0999"
+LINE_2 = "2 25544  51.6443  93.0000 0001400  84.0000 276.1505 15.49507896236008"
+
+
+def get_truth() -> Satellite:
+    return Satellite.from_tle(TLE.from_lines(LINE_1, LINE_2))
+
+
+def get_epochs(hours: float) -> list[Epoch]:
+    start = TLE.from_lines(LINE_1, LINE_2).epoch
+    return [start + TimeSpan.from_minutes(minute) for minute in range(int(hours * 60))]
+
+
+def get_observatories() -> list[Observatory]:
+    sites = [("NORTH", 40.0, -105.0, 1.6), ("SOUTH", -30.0, -70.0, 2.2), ("EAST", 35.0, 139.0, 0.1)]
+    observatories = []
+    for site_id, (name, latitude, longitude, altitude) in enumerate(sites):
+        observatory = Observatory(name, latitude, longitude, altitude)
+        sensor = Sensor("SENSOR", 0.001)
+        sensor.sensor_id = site_id + 1
+        sensor.range_noise = 0.01
+        observatory.add_sensor(sensor)
+        observatories.append(observatory)
+    return observatories
+
+
+def test_sensor_bias_recovery():
+    truth = get_truth()
+    observatories = get_observatories()
+    biased_sensor = observatories[0].sensors[0]
+    biased_sensor.declination_bias = 0.02
+    observatories[0] = Observatory("NORTH", 40.0, -105.0, 1.6)
+    observatories[0].add_sensor(biased_sensor)
+
+    simulator = ObservationSimulator(42)
+    simulator.add_noise = False
+    obs = simulator.generate(truth, observatories, get_epochs(24.0))
+
+    bls = BatchLeastSquares(obs, truth)
+    bls.estimate_biases = True
+    bls.solve()
+
+    declination_biases = {
+        b.sensor_id: b.value for b in bls.sensor_biases if b.measurement_type == MeasurementType.Declination
+    }
+    assert bls.converged
+    assert declination_biases[1] == pytest.approx(0.02, abs=1e-3)
+    assert declination_biases[2] == pytest.approx(0.0, abs=1e-3)
+    assert declination_biases[3] == pytest.approx(0.0, abs=1e-3)
+
+
+def test_refracted_angles():
+    truth = get_truth()
+    observatories = get_observatories()
+    epochs = get_epochs(24.0)
+
+    simulator = ObservationSimulator(42)
+    simulator.add_noise = False
+    geometric = simulator.generate(truth, observatories, epochs)
+    simulator.reset()
+    simulator.refraction_correction = True
+    refracted = simulator.generate(truth, observatories, epochs)
+
+    assert len(refracted) == len(geometric)
+    assert max(abs(r.declination - g.declination) for r, g in zip(refracted, geometric)) > 1e-3
+
+    bls = BatchLeastSquares(refracted, truth)
+    bls.iterate()
+    assert bls.weighted_rms == pytest.approx(0.0, abs=1e-6)
+
+
+def get_perturbed_a_priori() -> Satellite:
+    return Satellite.from_tle(TLE.from_lines(LINE_1, LINE_2.replace("276.1505", "276.2505")))
+
+
+def test_levenberg_marquardt_convergence():
+    truth = get_truth()
+    simulator = ObservationSimulator(42)
+    simulator.add_noise = False
+    obs = simulator.generate(truth, get_observatories(), get_epochs(24.0))
+
+    bls = BatchLeastSquares(obs, get_perturbed_a_priori())
+    bls.solver_type = SolverType.LevenbergMarquardt
+    bls.correction_tolerance = 1e-3
+    bls.solve()
+
+    assert bls.converged
+    assert bls.rms == pytest.approx(0.0, abs=1e-2)
+
+
+def test_tle_fitter_convergence():
+    truth = get_truth()
+    states = [truth.get_state_at_epoch(epoch) for epoch in get_epochs(24.0)[::30]]
+    fitter = TLEFitter(25544, states)
+    fitter.estimate_drag = True
+    fitter.solve()
+
+    assert fitter.converged
+    assert fitter.rms < 1e-2
+
+
+def test_observation_angles():
+    observatory = get_observatories()[0]
+    sensor = observatory.sensors[0]
+    epoch = get_epochs(1.0)[0]
+    site_position = observatory.get_state_at_epoch(epoch).position
+
+    topocentric = Observation(sensor, epoch, TopocentricElements(201.2345, -12.345), site_position)
+    topocentric.declination = 10.0
+    assert topocentric.right_ascension == pytest.approx(201.2345)
+    assert topocentric.declination == pytest.approx(10.0)
+
+    horizon = Observation.from_horizon(sensor, epoch, HorizonElements(123.4567, 45.6789), site_position)
+    assert 0.0 <= horizon.right_ascension < 360.0
+    assert -90.0 <= horizon.declination <= 90.0
+    with pytest.raises(ValueError):
+        horizon.right_ascension = 10.0
+
+    range_only = Observation.from_range(sensor, epoch, 1234.5678, site_position)
+    with pytest.raises(ValueError):
+        range_only.right_ascension
+    with pytest.raises(ValueError):
+        range_only.declination
+
+
+def test_horizon_sensor_simulation():
+    truth = get_truth()
+    observatories = []
+    for observatory in get_observatories():
+        sensor = observatory.sensors[0]
+        sensor.horizon_angles = True
+        sensor.elevation_bias = 0.01
+        observatory = Observatory(observatory.name, observatory.latitude, observatory.longitude, observatory.altitude)
+        observatory.add_sensor(sensor)
+        observatories.append(observatory)
+
+    simulator = ObservationSimulator(42)
+    simulator.add_noise = False
+    obs = simulator.generate(truth, observatories, get_epochs(24.0))
+
+    assert len(obs) > 0
+    assert all(ob.measurement_types[:2] == [MeasurementType.Azimuth, MeasurementType.Elevation] for ob in obs)
+    assert all(ob.azimuth is not None and ob.elevation >= 9.99 for ob in obs)
+
+    bls = BatchLeastSquares(obs, truth)
+    bls.iterate()
+    assert bls.weighted_rms > 1.0
+    for ob in obs:
+        ob.elevation -= 0.01
+    bls = BatchLeastSquares(obs, truth)
+    bls.iterate()
+    assert bls.weighted_rms == pytest.approx(0.0, abs=1e-6)
+
+
+def get_position_error(estimate: Satellite, truth: Satellite, epoch: Epoch) -> float:
+    estimated = estimate.get_state_at_epoch(epoch).position
+    expected = truth.get_state_at_epoch(epoch).position
+    return ((estimated.x - expected.x) ** 2 + (estimated.y - expected.y) ** 2 + (estimated.z - expected.z) ** 2) ** 0.5
+
+
+def test_simulate_and_fit():
+    truth = get_truth()
+    obs = ObservationSimulator(7).generate(truth, get_observatories(), get_epochs(24.0))
+
+    a_priori = get_perturbed_a_priori()
+    assert get_position_error(a_priori, truth, obs[0].epoch) > 10.0
+    bls = BatchLeastSquares(obs, a_priori)
+    bls.solve()
+
+    assert bls.converged
+    assert bls.weighted_rms == pytest.approx(1.0, abs=0.3)
+    for ob in (obs[0], obs[-1]):
+        assert get_position_error(bls.current_estimate, truth, ob.epoch) < 0.1
+
+
+def test_outlier_editing():
+    truth = get_truth()
+    obs = ObservationSimulator(7).generate(truth, get_observatories(), get_epochs(24.0))
+    obs[5].declination = obs[5].declination + 0.5
+
+    bls = BatchLeastSquares(obs, get_perturbed_a_priori())
+    bls.edit_sigma = 3.0
+    bls.solve()
+
+    assert bls.converged
+    assert bls.rejection_history[-1] == [5]
+    accepted = [residual.accepted for _, residual in bls.residuals]
+    assert not accepted[5]
+    assert sum(accepted) == len(obs) - 1
+    assert get_position_error(bls.current_estimate, truth, obs[5].epoch) < 0.1
+
+    # With nothing accepted there is no RMS to report
+    assert BatchLeastSquares([], truth).rms is None
+
+
+def test_divergence():
+    obs = ObservationSimulator(7).generate(get_truth(), get_observatories(), get_epochs(24.0))
+    a_priori = Satellite.from_tle(TLE.from_lines(LINE_1, LINE_2.replace("276.1505", " 96.1505")))
+
+    bls = BatchLeastSquares(obs, a_priori)
+    with pytest.raises(RuntimeError):
+        bls.solve()
+    assert not bls.converged
+
+    bls = BatchLeastSquares(obs, get_perturbed_a_priori())
+    bls.max_iterations = 1
+    bls.solve()
+    assert not bls.converged
+    assert bls.iteration_count == 1
+
+
+def test_tracklet_grouping():
+    observatories = get_observatories()
+    sensor = observatories[0].sensors[0]
+    # A second sensor with the same name and an interleaved schedule must not join the first sensor's tracks
+    other_sensor = observatories[1].sensors[0]
+    assert other_sensor.name == sensor.name
+    start = get_epochs(1.0)[0]
+
+    obs = []
+    for offset in (0.0, 3600.0):
+        for step in range(5):
+            seconds = offset + 10.0 * step
+            epoch = start + TimeSpan.from_seconds(seconds)
+            # Epochs in mixed time systems are ordered by the instant they describe
+            if step % 2 == 1:
+                epoch = epoch.to_system(TimeSystem.TAI)
+            position = observatories[0].get_state_at_epoch(epoch).position
+            angles = TopocentricElements(100.0 + 0.01 * seconds, 20.0 + 0.005 * seconds)
+            obs.append(Observation(sensor, epoch, angles, position))
+            if offset == 0.0:
+                crossing = TopocentricElements(101.0 - 0.01 * seconds, 21.0)
+                obs.append(Observation(sensor, epoch, crossing, position))
+                other_epoch = epoch + TimeSpan.from_seconds(5.0)
+                other_position = observatories[1].get_state_at_epoch(other_epoch).position
+                other_angles = TopocentricElements(100.0 + 0.01 * (seconds + 5.0), 20.0 + 0.005 * (seconds + 5.0))
+                obs.append(Observation(other_sensor, other_epoch, other_angles, other_position))
+
+    tracklets = TrackletBuilder().build(obs)
+
+    assert len(tracklets) == 4
+    for tracklet in tracklets:
+        assert len(tracklet.observations) == 5
+        assert all(ob.sensor.sensor_id == tracklet.sensor.sensor_id for ob in tracklet.observations)
+        assert tracklet.rms == pytest.approx(0.0, abs=1e-6)
+    declination_rates = sorted(tracklet.declination_rate for tracklet in tracklets)
+    assert declination_rates[0] == pytest.approx(0.0, abs=1e-6)
+    for declination_rate in declination_rates[1:]:
+        assert declination_rate == pytest.approx(0.005, abs=1e-6)
+
+
+def test_maneuver_at_given_epoch():
+    truth = get_truth()
+    epochs = get_epochs(48.0)
+    maneuver_epoch = epochs[len(epochs) // 2]
+    delta_v = CartesianVector(0.0, 0.002, 0.0)
+
+    # Seeding a solve with a known impulse gives the post-maneuver truth
+    seed = BatchLeastSquares([], truth)
+    seed.maneuver = ManeuverEvent(25544, maneuver_epoch, delta_v)
+    post_maneuver = seed.current_estimate
+
+    simulator = ObservationSimulator(42)
+    simulator.add_noise = False
+    obs = simulator.generate(truth, get_observatories(), [epoch for epoch in epochs if epoch < maneuver_epoch])
+    obs += simulator.generate(post_maneuver, get_observatories(), [epoch for epoch in epochs if epoch >= maneuver_epoch])
+
+    bls = BatchLeastSquares(obs, truth)
+    bls.maneuver = ManeuverEvent(25544, maneuver_epoch)
+    bls.solve()
+
+    assert bls.converged
+    assert bls.maneuver.epoch == maneuver_epoch
+    assert bls.maneuver.delta_v.x == pytest.approx(0.0, abs=1e-5)
+    assert bls.maneuver.delta_v.y == pytest.approx(0.002, abs=1e-5)
+    assert bls.maneuver.delta_v.z == pytest.approx(0.0, abs=1e-5)
@@ -1,7 +1,7 @@
 import pytest
 
-from keplemon.time import TimeSpan, Epoch, TimeComponents
-from keplemon.enums import TimeSystem
+from keplemon.time import TimeSpan, Epoch, EpochRange, IsoParseError, TimeComponents
+from keplemon.enums import TimeSystem, EarthRotationModel
 
 
 def test_time_components():
@@ -87,3 +87,71 @@ def test_time_span():
     assert from_days.in_hours() == EXPECTED_HOURS
     assert from_days.in_minutes() == EXPECTED_MINUTES
     assert from_days.in_seconds() == EXPECTED_SECONDS
+
+
+def test_epoch_range():
+    start = Epoch.from_days_since_1950(27486.0, TimeSystem.UTC)
+    epochs = EpochRange(start, start + TimeSpan.from_hours(3), TimeSpan.from_hours(1))
+    assert len(epochs) == 4
+    assert epochs.end == start + TimeSpan.from_hours(3)
+    assert [epoch - start for epoch in epochs][2].in_hours() == pytest.approx(2.0)
+    assert epochs[-1] == epochs.end
+    assert epochs[1] in epochs
+    assert start + TimeSpan.from_minutes(30) not in epochs
+
+    every_other = epochs[::2]
+    assert every_other.count == 2
+    assert every_other.step.in_hours() == pytest.approx(2.0)
+
+    later = EpochRange(epochs[2], epochs[2] + TimeSpan.from_hours(4), TimeSpan.from_hours(1))
+    assert epochs.merge(later).count == 7
+    assert epochs.intersection(later).count == 2
+    with pytest.raises(ValueError):
+        epochs.merge(EpochRange(start, start, TimeSpan.from_minutes(7)))
+    with pytest.raises(ValueError):
+        EpochRange(start, start + TimeSpan.from_hours(3), TimeSpan.from_seconds(0))
+    with pytest.raises(ValueError):
+        EpochRange(start, start + TimeSpan.from_hours(3), TimeSpan.from_seconds(-60))
+
+
+def test_iso_parsing():
+    expected = Epoch.from_iso("2025-04-15T04:02:42.420", TimeSystem.UTC)
+    assert Epoch.from_iso("2025-105T06:02:42.42+02:00", TimeSystem.UTC) == expected
+    assert Epoch.from_iso("2025-W16-2T04:02:42.42Z", TimeSystem.UTC) == expected
+    assert expected.to_iso(6) == "2025-04-15T04:02:42.420000"
+    assert expected.to_iso(0) == "2025-04-15T04:02:42"
+    with pytest.raises(IsoParseError):
+        Epoch.from_iso("2025-04-31T00:00:00", TimeSystem.UTC)
+    with pytest.raises(ValueError):
+        TimeComponents.from_iso("04/15/2025")
+
+
+def test_greenwich_angle():
+    epoch = Epoch.from_iso("2012-12-20T00:00:00", TimeSystem.UTC)
+    assert epoch.to_greenwich_angle(EarthRotationModel.FK5) == epoch.to_fk5_greenwich_angle()
+    assert epoch.to_greenwich_angle(EarthRotationModel.ERA) == epoch.to_earth_rotation_angle()
+    assert epoch.to_greenwich_angle(EarthRotationModel.GMST82) == pytest.approx(
+        epoch.to_fk5_greenwich_angle(), abs=1e-7
+    )
+    gast = epoch.to_greenwich_angle(EarthRotationModel.GAST06)
+    gmst = epoch.to_greenwich_angle(EarthRotationModel.GMST06)
+    assert gast - gmst == pytest.approx(epoch.to_equation_of_equinoxes(EarthRotationModel.GAST06))
+    with pytest.raises(ValueError):
+        epoch.to_equation_of_equinoxes(EarthRotationModel.ERA)
+
+
+def test_cross_system_ordering():
+    utc = Epoch.from_iso("2025-04-15T04:02:42.420", TimeSystem.UTC)
+    tai = utc.to_system(TimeSystem.TAI)
+    assert tai == utc
+    assert Epoch.from_iso("2025-04-15T04:02:42.420", TimeSystem.TAI) < utc
+    assert Epoch.from_iso("2025-04-15T04:03:19.420", TimeSystem.TAI) > utc
+    assert Epoch.from_iso("2025-04-15T04:02:42.420", TimeSystem.GPS) < utc
+
+
+def test_leap_second_ordering():
+    leap_second = Epoch.from_iso("2016-12-31T23:59:60.500", TimeSystem.UTC)
+    assert leap_second.time_system == TimeSystem.TAI
+    assert Epoch.from_iso("2016-12-31T23:59:59.999", TimeSystem.UTC) < leap_second
+    assert leap_second < Epoch.from_iso("2017-01-01T00:00:00", TimeSystem.UTC)
+    assert sorted([Epoch.from_iso("2017-01-01T00:00:00", TimeSystem.UTC), leap_second])[0] == leap_second